@@ -28,10 +28,11 @@ use range_set_blaze::{prelude::*, DynSortedDisjoint, Integer, SortedDisjoint};
 use syntactic_for::syntactic_for;
 use tests_common::{k_sets, width_to_range_u32, How, MemorylessIter, MemorylessRange};
 
-#[cfg(feature = "from_slice")]
 const LANES: usize = 16;
-#[cfg(feature = "from_slice")]
-const SIMD_SUFFIX: &str = if cfg!(target_feature = "avx512f") {
+// On stable, from_slice picks its SIMD instructions at run time.
+const SIMD_SUFFIX: &str = if cfg!(not(feature = "from_slice")) {
+    "dispatch"
+} else if cfg!(target_feature = "avx512f") {
     "avx512f"
 } else if cfg!(target_feature = "avx2") {
     "avx2"
@@ -1166,7 +1167,6 @@ fn ingest_clumps_base(c: &mut Criterion) {
             },
         );
 
-        #[cfg(feature = "from_slice")]
         group.bench_with_input(
             BenchmarkId::new(
                 format!("RangeSetBlaze (integers, slice_{})", SIMD_SUFFIX),
@@ -1273,7 +1273,6 @@ fn ingest_clumps_integers(c: &mut Criterion) {
             },
         );
 
-        #[cfg(feature = "from_slice")]
         group.bench_with_input(
             BenchmarkId::new(
                 format!("RangeSetBlaze (from_slice_{})", SIMD_SUFFIX),
@@ -1329,7 +1328,6 @@ fn ingest_clumps_integers(c: &mut Criterion) {
     group.finish();
 }
 
-#[cfg(feature = "from_slice")]
 fn ingest_clumps_iter_v_slice(c: &mut Criterion) {
     let group_name = "ingest_clumps_iter_v_slice";
     let k = 1;
@@ -1567,7 +1565,6 @@ fn worst(c: &mut Criterion) {
             },
         );
 
-        #[cfg(feature = "from_slice")]
         group.bench_with_input(
             BenchmarkId::new(
                 format!("RangeSetBlaze (from_slice_{})", SIMD_SUFFIX),
@@ -2110,20 +2107,83 @@ fn worst_op_blaze(c: &mut Criterion) {
     group.finish();
 }
 
-// Define two separate criterion groups for different features
-#[cfg(feature = "from_slice")]
-criterion_group!(
-    name = benches_with_from_slice;
-    config = Criterion::default();
-    targets =
-    ingest_clumps_iter_v_slice,
-    ingest_clumps_integers,
-    ingest_clumps_base,
-    worst
-);
+fn contains_many(c: &mut Criterion) {
+    let group_name = "contains_many";
+    let probes_len = 1_000_000;
+    let range_len_list = [10usize, 1_000, 100_000];
+    let seed = 0;
+
+    let mut group = c.benchmark_group(group_name);
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for range_len in range_len_list {
+        let parameter = range_len;
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let set: RangeSetBlaze<u32> = (0..range_len)
+            .map(|_| {
+                let start = rng.gen_range(0..u32::MAX - 1_000);
+                start..=start + rng.gen_range(0..1_000)
+            })
+            .collect();
+        let uniform = Uniform::from(0..u32::MAX - 1);
+        let unsorted: Vec<u32> = (0..probes_len).map(|_| uniform.sample(&mut rng)).collect();
+        let mut sorted = unsorted.clone();
+        sorted.sort_unstable();
+
+        group.bench_with_input(
+            BenchmarkId::new("contains (unsorted)", parameter),
+            &parameter,
+            |b, _| {
+                b.iter(|| {
+                    let _answer: Vec<bool> =
+                        unsorted.iter().map(|&probe| set.contains(probe)).collect();
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("contains_many (unsorted)", parameter),
+            &parameter,
+            |b, _| {
+                b.iter(|| {
+                    let _answer = set.contains_many(&unsorted);
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("contains (sorted)", parameter),
+            &parameter,
+            |b, _| {
+                b.iter(|| {
+                    let _answer: Vec<bool> =
+                        sorted.iter().map(|&probe| set.contains(probe)).collect();
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("contains_many (sorted)", parameter),
+            &parameter,
+            |b, _| {
+                b.iter(|| {
+                    let _answer = set.contains_many(&sorted);
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("filter_members (sorted)", parameter),
+            &parameter,
+            |b, _| {
+                b.iter(|| {
+                    let _answer = set.filter_members(sorted.iter().copied()).count();
+                })
+            },
+        );
+    }
+    group.finish();
+}
 
 criterion_group!(
-    name = benches_without_from_slice;
+    name = benches;
     config = Criterion::default();
     targets =
     intersect_k_sets,
@@ -2135,12 +2195,9 @@ criterion_group!(
     ingest_clumps_integers,
     ingest_clumps_ranges,
     ingest_clumps_easy,
+    ingest_clumps_iter_v_slice,
     overflow,
-    worst_op_blaze
+    worst_op_blaze,
+    contains_many
 );
-
-// Conditionally select and execute the appropriate group based on the feature
-#[cfg(feature = "from_slice")]
-criterion_main!(benches_with_from_slice);
-#[cfg(not(feature = "from_slice"))]
-criterion_main!(benches_without_from_slice);
+criterion_main!(benches);
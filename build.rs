@@ -7,4 +7,24 @@ fn main() {
             println!("cargo:rerun-if-env-changed=BUILDFEATURES");
         }
     }
+    #[cfg(feature = "ffi")]
+    generate_header();
+}
+
+// Writes the C header for the `ffi` module to `$OUT_DIR/range_set_blaze.h`. A test checks that
+// it matches the checked-in `include/range_set_blaze.h`.
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let config = cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml"))
+        .expect("cbindgen.toml is valid");
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("the ffi module can be turned into a C header")
+        .write_to_file(format!("{out_dir}/range_set_blaze.h"));
 }
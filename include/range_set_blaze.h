@@ -0,0 +1,392 @@
+/* range-set-blaze C API. Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef RANGE_SET_BLAZE_H
+#define RANGE_SET_BLAZE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The result of an FFI call.
+typedef enum RsbStatus {
+  // The call succeeded.
+  RSB_STATUS_OK = 0,
+  // A required pointer argument was null.
+  RSB_STATUS_NULL_POINTER = 1,
+  // A range's `start` was greater than its `end`.
+  RSB_STATUS_INVALID_RANGE = 2,
+  // The caller's buffer was too small. The required length was still written.
+  RSB_STATUS_BUFFER_TOO_SMALL = 3,
+  // The data to deserialize was malformed.
+  RSB_STATUS_INVALID_DATA = 4,
+  // The result doesn't fit in the out-pointer's type.
+  RSB_STATUS_OVERFLOW = 5,
+  // The call panicked. This is a bug in the library.
+  RSB_STATUS_PANIC = 6,
+} RsbStatus;
+
+// An opaque handle to a `RangeSetBlaze<i64>`.
+typedef struct RangeSetI64 RangeSetI64;
+
+// An opaque handle to a `RangeSetBlaze<u64>`.
+typedef struct RangeSetU64 RangeSetU64;
+
+// An inclusive range of `u64`s, as written by [`rsb_u64_ranges`].
+typedef struct RsbRangeU64 {
+  // The first integer in the range.
+  uint64_t start;
+  // The last integer in the range.
+  uint64_t end;
+} RsbRangeU64;
+
+// An inclusive range of `i64`s, as written by [`rsb_i64_ranges`].
+typedef struct RsbRangeI64 {
+  // The first integer in the range.
+  int64_t start;
+  // The last integer in the range.
+  int64_t end;
+} RsbRangeI64;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates an empty set and writes it to `*out`. Free it with [`rsb_u64_free`].
+//
+// # Safety
+//
+// `out` must be null or valid for writes.
+enum RsbStatus rsb_u64_new(struct RangeSetU64 **out);
+
+// Creates an empty set and writes it to `*out`. Free it with [`rsb_i64_free`].
+//
+// # Safety
+//
+// `out` must be null or valid for writes.
+enum RsbStatus rsb_i64_new(struct RangeSetI64 **out);
+
+// Copies `set` into a new set and writes it to `*out`.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `out` must be null or valid for writes.
+enum RsbStatus rsb_u64_clone(const struct RangeSetU64 *set, struct RangeSetU64 **out);
+
+// Copies `set` into a new set and writes it to `*out`.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `out` must be null or valid for writes.
+enum RsbStatus rsb_i64_clone(const struct RangeSetI64 *set, struct RangeSetI64 **out);
+
+// Frees a set. Null is ignored.
+//
+// # Safety
+//
+// `set` must be null or a live handle, which must not be used afterwards.
+void rsb_u64_free(struct RangeSetU64 *set);
+
+// Frees a set. Null is ignored.
+//
+// # Safety
+//
+// `set` must be null or a live handle, which must not be used afterwards.
+void rsb_i64_free(struct RangeSetI64 *set);
+
+// Adds `value` to the set. If `inserted` isn't null, writes whether the value was new.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `inserted` must be null or valid for writes.
+enum RsbStatus rsb_u64_insert(struct RangeSetU64 *set, uint64_t value, bool *inserted);
+
+// Adds `value` to the set. If `inserted` isn't null, writes whether the value was new.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `inserted` must be null or valid for writes.
+enum RsbStatus rsb_i64_insert(struct RangeSetI64 *set, int64_t value, bool *inserted);
+
+// Adds the inclusive range `start..=end` to the set. If `inserted` isn't null, writes whether
+// any value was new.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `inserted` must be null or valid for writes.
+enum RsbStatus rsb_u64_ranges_insert(struct RangeSetU64 *set,
+                                     uint64_t start,
+                                     uint64_t end,
+                                     bool *inserted);
+
+// Adds the inclusive range `start..=end` to the set. If `inserted` isn't null, writes whether
+// any value was new.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `inserted` must be null or valid for writes.
+enum RsbStatus rsb_i64_ranges_insert(struct RangeSetI64 *set,
+                                     int64_t start,
+                                     int64_t end,
+                                     bool *inserted);
+
+// Removes `value` from the set. If `removed` isn't null, writes whether it was present.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `removed` must be null or valid for writes.
+enum RsbStatus rsb_u64_remove(struct RangeSetU64 *set, uint64_t value, bool *removed);
+
+// Removes `value` from the set. If `removed` isn't null, writes whether it was present.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `removed` must be null or valid for writes.
+enum RsbStatus rsb_i64_remove(struct RangeSetI64 *set, int64_t value, bool *removed);
+
+// Removes the inclusive range `start..=end` from the set. If `removed` isn't null, writes
+// whether any value was present.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `removed` must be null or valid for writes.
+enum RsbStatus rsb_u64_ranges_remove(struct RangeSetU64 *set,
+                                     uint64_t start,
+                                     uint64_t end,
+                                     bool *removed);
+
+// Removes the inclusive range `start..=end` from the set. If `removed` isn't null, writes
+// whether any value was present.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `removed` must be null or valid for writes.
+enum RsbStatus rsb_i64_ranges_remove(struct RangeSetI64 *set,
+                                     int64_t start,
+                                     int64_t end,
+                                     bool *removed);
+
+// Writes whether the set contains `value` to `*contained`.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `contained` must be null or valid for writes.
+enum RsbStatus rsb_u64_contains(const struct RangeSetU64 *set, uint64_t value, bool *contained);
+
+// Writes whether the set contains `value` to `*contained`.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `contained` must be null or valid for writes.
+enum RsbStatus rsb_i64_contains(const struct RangeSetI64 *set, int64_t value, bool *contained);
+
+// Writes the number of integers in the set to `*len`. Returns [`RsbStatus::Overflow`] for the full
+// set, whose length, 2^64, doesn't fit.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `len` must be null or valid for writes.
+enum RsbStatus rsb_u64_len(const struct RangeSetU64 *set, uint64_t *len);
+
+// Writes the number of integers in the set to `*len`. Returns [`RsbStatus::Overflow`] for the full
+// set, whose length, 2^64, doesn't fit.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `len` must be null or valid for writes.
+enum RsbStatus rsb_i64_len(const struct RangeSetI64 *set, uint64_t *len);
+
+// Writes the number of ranges in the set to `*ranges_len`.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `ranges_len` must be null or valid for writes.
+enum RsbStatus rsb_u64_ranges_len(const struct RangeSetU64 *set, size_t *ranges_len);
+
+// Writes the number of ranges in the set to `*ranges_len`.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `ranges_len` must be null or valid for writes.
+enum RsbStatus rsb_i64_ranges_len(const struct RangeSetI64 *set, size_t *ranges_len);
+
+// Copies the set's ranges, in order, into `buffer`, which holds `capacity` ranges. Always writes
+// the number of ranges to `*ranges_len`. If `capacity` is too small, writes no ranges and returns
+// [`RsbStatus::BufferTooSmall`], so a caller can pass a null `buffer` to get the length.
+//
+// # Safety
+//
+// `set` must be null or a live handle, `buffer` must be null or valid for writing `capacity`
+// ranges, and `ranges_len` must be null or valid for writes.
+enum RsbStatus rsb_u64_ranges(const struct RangeSetU64 *set,
+                              struct RsbRangeU64 *buffer,
+                              size_t capacity,
+                              size_t *ranges_len);
+
+// Copies the set's ranges, in order, into `buffer`, which holds `capacity` ranges. Always writes
+// the number of ranges to `*ranges_len`. If `capacity` is too small, writes no ranges and returns
+// [`RsbStatus::BufferTooSmall`], so a caller can pass a null `buffer` to get the length.
+//
+// # Safety
+//
+// `set` must be null or a live handle, `buffer` must be null or valid for writing `capacity`
+// ranges, and `ranges_len` must be null or valid for writes.
+enum RsbStatus rsb_i64_ranges(const struct RangeSetI64 *set,
+                              struct RsbRangeI64 *buffer,
+                              size_t capacity,
+                              size_t *ranges_len);
+
+// Writes the union of `a` and `b` to `*out` as a new set.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+enum RsbStatus rsb_u64_union(const struct RangeSetU64 *a,
+                             const struct RangeSetU64 *b,
+                             struct RangeSetU64 **out);
+
+// Writes the union of `a` and `b` to `*out` as a new set.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+enum RsbStatus rsb_i64_union(const struct RangeSetI64 *a,
+                             const struct RangeSetI64 *b,
+                             struct RangeSetI64 **out);
+
+// Writes the intersection of `a` and `b` to `*out` as a new set.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+enum RsbStatus rsb_u64_intersection(const struct RangeSetU64 *a,
+                                    const struct RangeSetU64 *b,
+                                    struct RangeSetU64 **out);
+
+// Writes the intersection of `a` and `b` to `*out` as a new set.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+enum RsbStatus rsb_i64_intersection(const struct RangeSetI64 *a,
+                                    const struct RangeSetI64 *b,
+                                    struct RangeSetI64 **out);
+
+// Writes the integers in `a` but not in `b` to `*out` as a new set.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+enum RsbStatus rsb_u64_difference(const struct RangeSetU64 *a,
+                                  const struct RangeSetU64 *b,
+                                  struct RangeSetU64 **out);
+
+// Writes the integers in `a` but not in `b` to `*out` as a new set.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+enum RsbStatus rsb_i64_difference(const struct RangeSetI64 *a,
+                                  const struct RangeSetI64 *b,
+                                  struct RangeSetI64 **out);
+
+// Writes the integers in exactly one of `a` and `b` to `*out` as a new set.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+enum RsbStatus rsb_u64_symmetric_difference(const struct RangeSetU64 *a,
+                                            const struct RangeSetU64 *b,
+                                            struct RangeSetU64 **out);
+
+// Writes the integers in exactly one of `a` and `b` to `*out` as a new set.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+enum RsbStatus rsb_i64_symmetric_difference(const struct RangeSetI64 *a,
+                                            const struct RangeSetI64 *b,
+                                            struct RangeSetI64 **out);
+
+// Writes the integers not in `set` to `*out` as a new set.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `out` must be null or valid for writes.
+enum RsbStatus rsb_u64_complement(const struct RangeSetU64 *set, struct RangeSetU64 **out);
+
+// Writes the integers not in `set` to `*out` as a new set.
+//
+// # Safety
+//
+// `set` must be null or a live handle, and `out` must be null or valid for writes.
+enum RsbStatus rsb_i64_complement(const struct RangeSetI64 *set, struct RangeSetI64 **out);
+
+// Writes whether `a` and `b` have the same integers to `*equal`.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `equal` must be null or valid for writes.
+enum RsbStatus rsb_u64_equal(const struct RangeSetU64 *a, const struct RangeSetU64 *b, bool *equal);
+
+// Writes whether `a` and `b` have the same integers to `*equal`.
+//
+// # Safety
+//
+// `a` and `b` must be null or live handles, and `equal` must be null or valid for writes.
+enum RsbStatus rsb_i64_equal(const struct RangeSetI64 *a, const struct RangeSetI64 *b, bool *equal);
+
+// Serializes the set into `buffer`, which holds `capacity` bytes. Always writes the
+// serialized length to `*len`. If `capacity` is too small, writes nothing else and returns
+// [`RsbStatus::BufferTooSmall`], so a caller can pass a null `buffer` to get the length. See the
+// module documentation for the format.
+//
+// # Safety
+//
+// `set` must be null or a live handle, `buffer` must be null or valid for writing `capacity`
+// bytes, and `len` must be null or valid for writes.
+enum RsbStatus rsb_u64_serialize(const struct RangeSetU64 *set,
+                                 uint8_t *buffer,
+                                 size_t capacity,
+                                 size_t *len);
+
+// Serializes the set into `buffer`, which holds `capacity` bytes. Always writes the
+// serialized length to `*len`. If `capacity` is too small, writes nothing else and returns
+// [`RsbStatus::BufferTooSmall`], so a caller can pass a null `buffer` to get the length. See the
+// module documentation for the format.
+//
+// # Safety
+//
+// `set` must be null or a live handle, `buffer` must be null or valid for writing `capacity`
+// bytes, and `len` must be null or valid for writes.
+enum RsbStatus rsb_i64_serialize(const struct RangeSetI64 *set,
+                                 uint8_t *buffer,
+                                 size_t capacity,
+                                 size_t *len);
+
+// Creates a set from the `len` bytes written by [`rsb_u64_serialize`] and writes it to `*out`.
+// Returns [`RsbStatus::InvalidData`] if the bytes are malformed, including if the ranges aren't
+// sorted and disjoint.
+//
+// # Safety
+//
+// `data` must be null or valid for reading `len` bytes, and `out` must be null or valid for
+// writes.
+enum RsbStatus rsb_u64_deserialize(const uint8_t *data, size_t len, struct RangeSetU64 **out);
+
+// Creates a set from the `len` bytes written by [`rsb_i64_serialize`] and writes it to `*out`.
+// Returns [`RsbStatus::InvalidData`] if the bytes are malformed, including if the ranges aren't
+// sorted and disjoint.
+//
+// # Safety
+//
+// `data` must be null or valid for reading `len` bytes, and `out` must be null or valid for
+// writes.
+enum RsbStatus rsb_i64_deserialize(const uint8_t *data, size_t len, struct RangeSetI64 **out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RANGE_SET_BLAZE_H */
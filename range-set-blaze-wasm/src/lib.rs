@@ -0,0 +1,208 @@
+//! JavaScript bindings for [`range-set-blaze`](https://crates.io/crates/range-set-blaze), built
+//! with `wasm-bindgen`.
+//!
+//! Each class wraps a [`RangeSetBlaze`] of one integer type:
+//!
+//! | Class                 | Integers                                                | JavaScript type |
+//! |-----------------------|---------------------------------------------------------|-----------------|
+//! | `RangeSetU32`         | `u32`                                                   | `number`        |
+//! | `RangeSetI32`         | `i32`                                                   | `number`        |
+//! | `RangeSetSafeInteger` | `-Number.MAX_SAFE_INTEGER` to `Number.MAX_SAFE_INTEGER` | `number`        |
+//! | `RangeSetU64`         | `u64`                                                   | `BigInt`        |
+//!
+//! Build the package with `wasm-pack build range-set-blaze-wasm`.
+#![no_std]
+extern crate alloc;
+use alloc::{format, string::String, string::ToString, vec::Vec};
+use core::ops::RangeInclusive;
+use range_set_blaze::{Integer, RangeSetBlaze};
+use wasm_bindgen::prelude::*;
+
+// The largest integer that a JavaScript `number` represents exactly, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+fn identity<T>(value: T) -> Result<T, JsError> {
+    Ok(value)
+}
+
+fn f64_to_safe_integer(value: f64) -> Result<i64, JsError> {
+    if value.trunc() == value && value.abs() <= MAX_SAFE_INTEGER as f64 {
+        Ok(value as i64)
+    } else {
+        Err(JsError::new(&format!("{value} is not a safe integer")))
+    }
+}
+
+fn safe_integer_to_f64(value: i64) -> f64 {
+    value as f64
+}
+
+// u128 lengths become a BigInt by way of their decimal string.
+fn u128_to_big_int(len: u128) -> js_sys::BigInt {
+    js_sys::BigInt::new(&JsValue::from_str(&len.to_string())).unwrap_throw()
+}
+
+// Converts a flat array `[start0, end0, start1, end1, ...]` of inclusive ranges.
+fn pairs_to_ranges<T, J>(
+    pairs: Vec<J>,
+    to_inner: fn(J) -> Result<T, JsError>,
+) -> Result<Vec<RangeInclusive<T>>, JsError>
+where
+    J: Copy,
+{
+    if !pairs.len().is_multiple_of(2) {
+        return Err(JsError::new(
+            "ranges must be a flat array of [start, end] pairs",
+        ));
+    }
+    pairs
+        .chunks_exact(2)
+        .map(|pair| Ok(to_inner(pair[0])?..=to_inner(pair[1])?))
+        .collect()
+}
+
+// Defines a JavaScript `RangeSet` class over one integer type. `$js` is the type JavaScript
+// sees, and `$domain` is the set of integers the class can hold, which `complement` respects.
+macro_rules! define_range_set {
+    (
+        $(#[$meta:meta])*
+        $class:ident, $js_name:literal, $t:ty, $js:ty,
+        to_inner: $to_inner:path, from_inner: $from_inner:path,
+        domain: $domain:expr,
+        len: $len_js:ty, $len_to_js:expr
+    ) => {
+        $(#[$meta])*
+        #[wasm_bindgen(js_name = $js_name)]
+        #[derive(Clone, Debug, Default)]
+        pub struct $class(RangeSetBlaze<$t>);
+
+        #[wasm_bindgen(js_class = $js_name)]
+        impl $class {
+            /// Creates an empty set.
+            #[wasm_bindgen(constructor)]
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Creates a set from an array of integers. Duplicates and out-of-order
+            /// integers are fine.
+            #[wasm_bindgen(js_name = fromArray)]
+            pub fn from_array(values: Vec<$js>) -> Result<$class, JsError> {
+                let values = values
+                    .into_iter()
+                    .map($to_inner)
+                    .collect::<Result<Vec<$t>, JsError>>()?;
+                Ok(Self(RangeSetBlaze::from_slice(values)))
+            }
+
+            /// Creates a set from a flat array of inclusive `[start, end]` pairs.
+            /// Overlapping, out-of-order, and empty ranges are fine.
+            #[wasm_bindgen(js_name = fromRanges)]
+            pub fn from_ranges(pairs: Vec<$js>) -> Result<$class, JsError> {
+                Ok(Self(pairs_to_ranges(pairs, $to_inner)?.into_iter().collect()))
+            }
+
+            /// Returns the integers in either set.
+            pub fn union(&self, other: &$class) -> $class {
+                Self(&self.0 | &other.0)
+            }
+
+            /// Returns the integers in both sets.
+            pub fn intersection(&self, other: &$class) -> $class {
+                Self(&self.0 & &other.0)
+            }
+
+            /// Returns the integers in this set but not in `other`.
+            pub fn difference(&self, other: &$class) -> $class {
+                Self(&self.0 - &other.0)
+            }
+
+            /// Returns the integers in exactly one of the sets.
+            #[wasm_bindgen(js_name = symmetricDifference)]
+            pub fn symmetric_difference(&self, other: &$class) -> $class {
+                Self(&self.0 ^ &other.0)
+            }
+
+            /// Returns the integers of the domain that aren't in this set.
+            pub fn complement(&self) -> $class {
+                Self(RangeSetBlaze::from_iter([$domain]) - &self.0)
+            }
+
+            /// Returns `true` if the set contains `value`.
+            pub fn contains(&self, value: $js) -> Result<bool, JsError> {
+                Ok(self.0.contains($to_inner(value)?))
+            }
+
+            /// Returns the ranges as a flat array of inclusive `[start, end]` pairs.
+            pub fn ranges(&self) -> Vec<$js> {
+                self.0
+                    .ranges()
+                    .flat_map(|range| [$from_inner(*range.start()), $from_inner(*range.end())])
+                    .collect()
+            }
+
+            /// The number of integers in the set.
+            #[wasm_bindgen(getter)]
+            pub fn len(&self) -> $len_js {
+                $len_to_js(self.0.len())
+            }
+
+            /// The number of ranges in the set.
+            #[wasm_bindgen(getter, js_name = rangesLen)]
+            pub fn ranges_len(&self) -> usize {
+                self.0.ranges_len()
+            }
+
+            /// Returns `true` if the set is empty.
+            #[wasm_bindgen(js_name = isEmpty)]
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            /// Returns `true` if the sets have the same integers.
+            pub fn equals(&self, other: &$class) -> bool {
+                self.0 == other.0
+            }
+
+            /// Returns the ranges as a string, for example, `"1..=3, 100..=100"`.
+            #[wasm_bindgen(js_name = toString)]
+            pub fn to_js_string(&self) -> String {
+                self.0.to_string()
+            }
+        }
+    };
+}
+
+define_range_set!(
+    /// A set of `u32` integers, seen from JavaScript as numbers.
+    RangeSetU32, "RangeSetU32", u32, u32,
+    to_inner: identity, from_inner: core::convert::identity,
+    domain: 0..=u32::MAX,
+    len: f64, <u32 as Integer>::safe_len_to_f64
+);
+
+define_range_set!(
+    /// A set of `i32` integers, seen from JavaScript as numbers.
+    RangeSetI32, "RangeSetI32", i32, i32,
+    to_inner: identity, from_inner: core::convert::identity,
+    domain: i32::MIN..=i32::MAX,
+    len: f64, <i32 as Integer>::safe_len_to_f64
+);
+
+define_range_set!(
+    /// A set of JavaScript safe integers, from `-Number.MAX_SAFE_INTEGER` to
+    /// `Number.MAX_SAFE_INTEGER`, seen from JavaScript as numbers. Methods throw if given a
+    /// number that isn't a safe integer.
+    RangeSetSafeInteger, "RangeSetSafeInteger", i64, f64,
+    to_inner: f64_to_safe_integer, from_inner: safe_integer_to_f64,
+    domain: -MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER,
+    len: f64, <i64 as Integer>::safe_len_to_f64
+);
+
+define_range_set!(
+    /// A set of `u64` integers, seen from JavaScript as BigInts.
+    RangeSetU64, "RangeSetU64", u64, u64,
+    to_inner: identity, from_inner: core::convert::identity,
+    domain: 0..=u64::MAX,
+    len: js_sys::BigInt, u128_to_big_int
+);
@@ -0,0 +1,57 @@
+#![cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+wasm_bindgen_test_configure!(run_in_browser);
+
+use range_set_blaze_wasm::{RangeSetI32, RangeSetSafeInteger, RangeSetU32, RangeSetU64};
+
+#[wasm_bindgen_test]
+fn u32_constructors() {
+    let a = RangeSetU32::from_array(vec![3, 2, 1, 100, 1]).unwrap();
+    assert_eq!(a.to_js_string(), "1..=3, 100..=100");
+    assert_eq!(a.ranges(), [1, 3, 100, 100]);
+    assert_eq!(a.len(), 4.0);
+    assert_eq!(a.ranges_len(), 2);
+
+    let b = RangeSetU32::from_ranges(vec![100, 100, 1, 3, 5, 4]).unwrap();
+    assert!(a.equals(&b));
+    assert!(RangeSetU32::from_ranges(vec![1, 2, 3]).is_err());
+    assert!(RangeSetU32::new().is_empty());
+}
+
+#[wasm_bindgen_test]
+fn i32_set_operations() {
+    let a = RangeSetI32::from_ranges(vec![-10, 10]).unwrap();
+    let b = RangeSetI32::from_ranges(vec![0, 20]).unwrap();
+    assert_eq!(a.union(&b).ranges(), [-10, 20]);
+    assert_eq!(a.intersection(&b).ranges(), [0, 10]);
+    assert_eq!(a.difference(&b).ranges(), [-10, -1]);
+    assert_eq!(a.symmetric_difference(&b).ranges(), [-10, -1, 11, 20]);
+    assert_eq!(a.complement().ranges(), [i32::MIN, -11, 11, i32::MAX]);
+    assert!(a.contains(-10).unwrap());
+    assert!(!a.contains(11).unwrap());
+    assert_eq!(RangeSetI32::new().complement().len(), 4_294_967_296.0);
+}
+
+#[wasm_bindgen_test]
+fn safe_integer_domain() {
+    let max = 9_007_199_254_740_991.0;
+    let a = RangeSetSafeInteger::from_array(vec![max, max - 1.0, -max]).unwrap();
+    assert_eq!(a.ranges(), [-max, -max, max - 1.0, max]);
+    assert_eq!(a.complement().ranges(), [-max + 1.0, max - 2.0]);
+    assert_eq!(
+        RangeSetSafeInteger::new().complement().ranges(),
+        [-max, max]
+    );
+    assert!(RangeSetSafeInteger::from_array(vec![max + 1.0]).is_err());
+    assert!(RangeSetSafeInteger::from_array(vec![1.5]).is_err());
+    assert!(RangeSetSafeInteger::from_ranges(vec![0.0, f64::NAN]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn u64_big_int() {
+    let a = RangeSetU64::from_ranges(vec![0, u64::MAX]).unwrap();
+    assert_eq!(a.ranges(), [0, u64::MAX]);
+    assert_eq!(a.len().to_string(10).unwrap(), "18446744073709551616");
+    assert!(a.complement().is_empty());
+    assert!(a.contains(u64::MAX).unwrap());
+}
@@ -0,0 +1,423 @@
+use core::ops::RangeInclusive;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::RangeSetBlaze;
+use crate::{sorted_disjoint::impl_sorted_disjoint_and_ops, Integer, SortedDisjoint};
+
+// Computes `value + delta`, or `None` if the result is outside `T::min_value()..=T::safe_max_value()`.
+pub(crate) fn checked_add<T: Integer>(value: T, delta: T) -> Option<T> {
+    value
+        .checked_add(&delta)
+        .filter(|sum| *sum <= T::safe_max_value())
+}
+
+// Computes `value + delta`, saturating at `T::min_value()` and `T::safe_max_value()`.
+pub(crate) fn saturating_add<T: Integer>(value: T, delta: T) -> T {
+    checked_add(value, delta).unwrap_or_else(|| {
+        if delta < T::zero() {
+            T::min_value()
+        } else {
+            T::safe_max_value()
+        }
+    })
+}
+
+/// A [`SortedDisjoint`] iterator that adds a constant to every integer.
+///
+/// This `struct` is created by the [`shift`] and [`saturating_shift`] methods on [`SortedDisjoint`].
+/// See their documentation for more.
+///
+/// [`shift`]: SortedDisjoint::shift
+/// [`saturating_shift`]: SortedDisjoint::saturating_shift
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ShiftIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    delta: T,
+    saturating: bool,
+    pending: Option<RangeInclusive<T>>,
+}
+
+impl<T, I> ShiftIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, delta: T, saturating: bool) -> Self {
+        ShiftIter {
+            iter,
+            delta,
+            saturating,
+            pending: None,
+        }
+    }
+}
+
+impl<T, I> Iterator for ShiftIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        if !self.saturating {
+            return self.iter.next().map(|range| {
+                let (start, end) = range.into_inner();
+                let shift = |value| checked_add(value, self.delta).expect("shift overflowed");
+                shift(start)..=shift(end)
+            });
+        }
+        // Saturating can squash several ranges into one at either end of the domain.
+        loop {
+            let Some(range) = self.iter.next() else {
+                return self.pending.take();
+            };
+            let (start, end) = range.into_inner();
+            let (start, end) = (
+                saturating_add(start, self.delta),
+                saturating_add(end, self.delta),
+            );
+            let Some(pending) = self.pending.take() else {
+                self.pending = Some(start..=end);
+                continue;
+            };
+            let (pending_start, pending_end) = pending.into_inner();
+            if pending_end == T::safe_max_value() || start <= pending_end + T::one() {
+                self.pending = Some(pending_start..=end);
+            } else {
+                self.pending = Some(start..=end);
+                return Some(pending_start..=pending_end);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        let pending = usize::from(self.pending.is_some());
+        if self.saturating {
+            let low = if low + pending > 0 { 1 } else { 0 };
+            (low, high.and_then(|high| high.checked_add(pending)))
+        } else {
+            (low, high)
+        }
+    }
+}
+
+/// A [`SortedDisjoint`] iterator that turns every integer `x` into the block `x * factor ..= x * factor + factor - 1`.
+///
+/// This `struct` is created by the [`scale`] method on [`SortedDisjoint`]. See [`scale`]'s
+/// documentation for more.
+///
+/// [`scale`]: SortedDisjoint::scale
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ScaleIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    factor: T,
+}
+
+impl<T, I> ScaleIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, factor: T) -> Self {
+        assert!(
+            T::zero() < factor && factor <= T::safe_max_value(),
+            "factor must be > 0 and <= T::safe_max_value()"
+        );
+        ScaleIter { iter, factor }
+    }
+}
+
+impl<T, I> Iterator for ScaleIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        let (start, end) = self.iter.next()?.into_inner();
+        let factor_less_one = self.factor - T::one();
+        // Division truncates toward zero, so min_value / factor is the smallest start that fits.
+        assert!(
+            T::min_value() / self.factor <= start
+                && end <= (T::safe_max_value() - factor_less_one) / self.factor,
+            "scale overflowed"
+        );
+        Some(start * self.factor..=end * self.factor + factor_less_one)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// A [`SortedDisjoint`] iterator that maps every integer `x` to `x` divided by a divisor, rounding down.
+///
+/// This `struct` is created by the [`quotient`] method on [`SortedDisjoint`]. See [`quotient`]'s
+/// documentation for more.
+///
+/// [`quotient`]: SortedDisjoint::quotient
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct QuotientIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    divisor: T,
+    pending: Option<RangeInclusive<T>>,
+}
+
+impl<T, I> QuotientIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, divisor: T) -> Self {
+        assert!(T::zero() < divisor, "divisor must be > 0");
+        QuotientIter {
+            iter,
+            divisor,
+            pending: None,
+        }
+    }
+}
+
+impl<T, I> Iterator for QuotientIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        loop {
+            let Some(range) = self.iter.next() else {
+                return self.pending.take();
+            };
+            let (start, end) = range.into_inner();
+            let (start, end) = (start.div_floor(&self.divisor), end.div_floor(&self.divisor));
+            let Some(pending) = self.pending.take() else {
+                self.pending = Some(start..=end);
+                continue;
+            };
+            let (pending_start, pending_end) = pending.into_inner();
+            // A later range exists, so pending_end < T::safe_max_value() and +1 is safe.
+            if start <= pending_end + T::one() {
+                self.pending = Some(pending_start..=end);
+            } else {
+                self.pending = Some(start..=end);
+                return Some(pending_start..=pending_end);
+            }
+        }
+    }
+
+    // We could merge all the ranges into one.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        let pending = usize::from(self.pending.is_some());
+        let low = if low + pending > 0 { 1 } else { 0 };
+        let high = high.and_then(|high| high.checked_add(pending));
+        (low, high)
+    }
+}
+
+impl_sorted_disjoint_and_ops!(ShiftIter, ScaleIter, QuotientIter);
+
+#[cfg(feature = "alloc")]
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Adds `delta` to every integer in the set. `delta` may be negative for signed types.
+    ///
+    /// See [`SortedDisjoint::shift`] for a lazy version.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any integer would move outside `T::min_value()..=T::safe_max_value()`.
+    /// Use [`checked_shift`], [`saturating_shift`], or [`wrapping_shift`] to handle overflow.
+    ///
+    /// [`checked_shift`]: RangeSetBlaze::checked_shift
+    /// [`saturating_shift`]: RangeSetBlaze::saturating_shift
+    /// [`wrapping_shift`]: RangeSetBlaze::wrapping_shift
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut a = RangeSetBlaze::from_iter([0..=9, 20..=29]);
+    /// a.shift(1);
+    /// assert_eq!(a.to_string(), "1..=10, 21..=30");
+    /// a.shift(-21);
+    /// assert_eq!(a.to_string(), "-20..=-11, 0..=9");
+    /// ```
+    pub fn shift(&mut self, delta: T) {
+        assert!(self.checked_shift(delta), "shift overflowed");
+    }
+
+    /// Adds `delta` to every integer in the set and returns `true`. If any integer would move
+    /// outside `T::min_value()..=T::safe_max_value()`, leaves the set unchanged and returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut a = RangeSetBlaze::from_iter([0u8..=9, 200..=209]);
+    /// assert!(!a.checked_shift(50));
+    /// assert_eq!(a.to_string(), "0..=9, 200..=209");
+    /// assert!(a.checked_shift(46));
+    /// assert_eq!(a.to_string(), "46..=55, 246..=255");
+    /// ```
+    #[must_use = "the set is unchanged when this returns false"]
+    pub fn checked_shift(&mut self, delta: T) -> bool {
+        let (Some(first), Some(last)) = (self.first(), self.last()) else {
+            return true;
+        };
+        if checked_add(first, delta).is_none() || checked_add(last, delta).is_none() {
+            return false;
+        }
+        let ranges = core::mem::replace(self, RangeSetBlaze::new()).into_ranges();
+        *self = RangeSetBlaze::from_sorted_disjoint(ranges.shift(delta));
+        true
+    }
+
+    /// Adds `delta` to every integer in the set, saturating at `T::min_value()` and
+    /// `T::safe_max_value()`. Integers that saturate pile up at the end of the domain, so the set
+    /// may shrink.
+    ///
+    /// See [`SortedDisjoint::saturating_shift`] for a lazy version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut a = RangeSetBlaze::from_iter([0u8..=9, 200..=209, 250..=250]);
+    /// a.saturating_shift(50);
+    /// assert_eq!(a.to_string(), "50..=59, 250..=255");
+    /// ```
+    pub fn saturating_shift(&mut self, delta: T) {
+        let ranges = core::mem::replace(self, RangeSetBlaze::new()).into_ranges();
+        *self = RangeSetBlaze::from_sorted_disjoint(ranges.saturating_shift(delta));
+    }
+
+    /// Adds `delta` to every integer in the set, wrapping around at the ends of the domain.
+    /// `delta` may be negative for signed types. For unsigned types, a large `delta`
+    /// shifts down, for example, `wrapping_shift(u8::MAX)` subtracts one.
+    ///
+    /// Wrapping changes the order of the ranges, so there is no lazy version.
+    ///
+    /// # Panics
+    ///
+    /// For `u128` and `i128`, panics if an integer would wrap to `T::MAX`, which is not allowed
+    /// in a set.
+    /// When it panics, the set is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut a = RangeSetBlaze::from_iter([1u8..=9, 250..=255]);
+    /// a.wrapping_shift(10);
+    /// assert_eq!(a.to_string(), "4..=9, 11..=19");
+    /// a.wrapping_shift(u8::MAX);
+    /// assert_eq!(a.to_string(), "3..=8, 10..=18");
+    /// ```
+    pub fn wrapping_shift(&mut self, delta: T) {
+        let negative_delta = T::zero().wrapping_sub(&delta);
+        let mut ranges = Vec::with_capacity(self.btree_map.len() + 1);
+        for (start, end) in self.ranges().map(RangeInclusive::into_inner) {
+            let (start, end) = (
+                start.wrapping_sub(&negative_delta),
+                end.wrapping_sub(&negative_delta),
+            );
+            if start <= end {
+                ranges.push(start..=end);
+            } else {
+                ranges.push(start..=T::max_value());
+                ranges.push(T::min_value()..=end);
+            }
+        }
+        assert!(
+            ranges
+                .iter()
+                .all(|range| *range.end() <= T::safe_max_value()),
+            "value must be <= T::safe_max_value()"
+        );
+        *self = RangeSetBlaze::from_iter(ranges);
+    }
+
+    /// Turns every integer `x` in the set into the block `x * factor ..= x * factor + factor - 1`.
+    ///
+    /// See [`SortedDisjoint::scale`] for a lazy version.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor <= 0` or `factor > T::safe_max_value()`.
+    ///
+    /// Panics if any block would fall outside `T::min_value()..=T::safe_max_value()`.
+    /// When it panics, the set is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// // pages 0, 1, and 3 as byte offsets, with 4096-byte pages
+    /// let mut a = RangeSetBlaze::from_iter([0u32..=1, 3..=3]);
+    /// a.scale(4096);
+    /// assert_eq!(a.to_string(), "0..=8191, 12288..=16383");
+    /// ```
+    pub fn scale(&mut self, factor: T) {
+        // Build from borrowed ranges, so a panic leaves the set as it was.
+        *self = RangeSetBlaze::from_sorted_disjoint(self.ranges().scale(factor));
+    }
+
+    /// Maps every integer `x` in the set to `x / divisor`, rounding down (toward negative
+    /// infinity). This undoes [`scale`].
+    ///
+    /// See [`SortedDisjoint::quotient`] for a lazy version.
+    ///
+    /// [`scale`]: RangeSetBlaze::scale
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor <= 0`.
+    /// When it panics, the set is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// // byte offsets to the pages that contain them, with 4096-byte pages
+    /// let mut a = RangeSetBlaze::from_iter([100u32..=200, 4000..=5000, 12288..=12288]);
+    /// a.quotient(4096);
+    /// assert_eq!(a.to_string(), "0..=1, 3..=3");
+    ///
+    /// let mut b = RangeSetBlaze::from_iter([-3..=-1, 4..=5]);
+    /// b.quotient(2);
+    /// assert_eq!(b.to_string(), "-2..=-1, 2..=2");
+    /// ```
+    pub fn quotient(&mut self, divisor: T) {
+        // Build from borrowed ranges, so a panic leaves the set as it was.
+        *self = RangeSetBlaze::from_sorted_disjoint(self.ranges().quotient(divisor));
+    }
+}
@@ -0,0 +1,260 @@
+use core::ops::{Bound, RangeInclusive};
+
+use num_traits::Zero;
+
+use crate::{Integer, RangeSetBlaze};
+
+// Allocator-style methods treat the set as a free list: its elements are the free IDs.
+//
+// They search the ranges directly, so that sets that are never used this way pay nothing for
+// an index. `RangeAllocator` wraps a set with indexes that make the same searches O(log n).
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Treating the set as a free list, returns the first block of `n` consecutive elements
+    /// that starts at or after `hint`. The set is not changed.
+    ///
+    /// If `hint` is inside a range, the block may start at `hint`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n* + *k*), where *n* is the number of ranges in the set and *k* is the number of
+    /// ranges after `hint` that are too short. [`RangeAllocator`] does this search in
+    /// O(log *n*).
+    ///
+    /// [`RangeAllocator`]: crate::RangeAllocator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// Panics if `hint > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let free = RangeSetBlaze::from_iter([0u64..=3, 10..=19, 30..=99]);
+    /// assert_eq!(free.find_free(5, 0), Some(10..=14));
+    /// assert_eq!(free.find_free(5, 12), Some(12..=16));
+    /// assert_eq!(free.find_free(5, 17), Some(30..=34));
+    /// assert_eq!(free.find_free(5, 40), Some(40..=44));
+    /// assert_eq!(free.find_free(100, 0), None);
+    /// ```
+    #[must_use]
+    pub fn find_free(&self, n: <T as Integer>::SafeLen, hint: T) -> Option<RangeInclusive<T>> {
+        assert!(n > <T as Integer>::SafeLen::zero(), "n must be > 0");
+        assert!(
+            hint <= T::safe_max_value(),
+            "hint must be <= T::safe_max_value()"
+        );
+        if let Some((_, &end)) = self.btree_map.range(..=hint).next_back() {
+            if hint <= end && T::safe_len(&(hint..=end)) >= n {
+                return Some(hint..=T::add_len_less_one(hint, n));
+            }
+        }
+        self.btree_map
+            .range((Bound::Excluded(hint), Bound::Unbounded))
+            .find(|(&start, &end)| T::safe_len(&(start..=end)) >= n)
+            .map(|(&start, _)| start..=T::add_len_less_one(start, n))
+    }
+
+    /// Treating the set as a free list, returns the first `n` elements of the smallest range
+    /// that has at least `n` elements. Ties go to the lowest range. The set is not changed.
+    ///
+    /// # Performance
+    ///
+    /// O(*n*), where *n* is the number of ranges in the set. [`RangeAllocator`] does this search
+    /// in O(log *n*).
+    ///
+    /// [`RangeAllocator`]: crate::RangeAllocator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let free = RangeSetBlaze::from_iter([0u64..=3, 10..=19, 30..=35]);
+    /// assert_eq!(free.find_best_fit(5), Some(30..=34));
+    /// assert_eq!(free.find_best_fit(4), Some(0..=3));
+    /// assert_eq!(free.find_best_fit(11), None);
+    /// ```
+    #[must_use]
+    pub fn find_best_fit(&self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        assert!(n > <T as Integer>::SafeLen::zero(), "n must be > 0");
+        let mut best: Option<(T, <T as Integer>::SafeLen)> = None;
+        for (&start, &end) in self.btree_map.iter() {
+            let len = T::safe_len(&(start..=end));
+            if len >= n && best.is_none_or(|(_, best_len)| len < best_len) {
+                best = Some((start, len));
+                if len == n {
+                    break; // can't do better than an exact fit
+                }
+            }
+        }
+        best.map(|(start, _)| start..=T::add_len_less_one(start, n))
+    }
+
+    /// Treating the set as a free list, returns the first `n` elements of the largest range,
+    /// if it has at least `n` elements. Ties go to the lowest range. The set is not changed.
+    ///
+    /// # Performance
+    ///
+    /// O(*n*), where *n* is the number of ranges in the set. [`RangeAllocator`] does this search
+    /// in O(log *n*).
+    ///
+    /// [`RangeAllocator`]: crate::RangeAllocator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let free = RangeSetBlaze::from_iter([0u64..=3, 10..=19, 30..=35]);
+    /// assert_eq!(free.find_worst_fit(2), Some(10..=11));
+    /// assert_eq!(free.find_worst_fit(11), None);
+    /// ```
+    #[must_use]
+    pub fn find_worst_fit(&self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        assert!(n > <T as Integer>::SafeLen::zero(), "n must be > 0");
+        let mut worst: Option<(T, <T as Integer>::SafeLen)> = None;
+        for (&start, &end) in self.btree_map.iter() {
+            let len = T::safe_len(&(start..=end));
+            if worst.is_none_or(|(_, worst_len)| len > worst_len) {
+                worst = Some((start, len));
+            }
+        }
+        worst
+            .filter(|&(_, len)| len >= n)
+            .map(|(start, _)| start..=T::add_len_less_one(start, n))
+    }
+
+    /// Treating the set as a free list, removes and returns the lowest block of `n` consecutive elements.
+    ///
+    /// This is [`allocate_first_fit`] with a hint of `T::min_value()`.
+    ///
+    /// [`allocate_first_fit`]: RangeSetBlaze::allocate_first_fit
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut free = RangeSetBlaze::from_iter([0u64..=3, 10..=19]);
+    /// assert_eq!(free.allocate(5), Some(10..=14));
+    /// assert_eq!(free.allocate(5), Some(15..=19));
+    /// assert_eq!(free.allocate(5), None);
+    /// assert_eq!(free.to_string(), "0..=3");
+    /// ```
+    pub fn allocate(&mut self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        self.allocate_first_fit(n, T::min_value())
+    }
+
+    /// Treating the set as a free list, removes and returns the first block of `n` consecutive elements
+    /// that starts at or after `hint`. See [`find_free`] for details.
+    ///
+    /// [`find_free`]: RangeSetBlaze::find_free
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// Panics if `hint > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut free = RangeSetBlaze::from_iter([0u64..=99]);
+    /// assert_eq!(free.allocate_first_fit(10, 50), Some(50..=59));
+    /// assert_eq!(free.to_string(), "0..=49, 60..=99");
+    /// ```
+    pub fn allocate_first_fit(
+        &mut self,
+        n: <T as Integer>::SafeLen,
+        hint: T,
+    ) -> Option<RangeInclusive<T>> {
+        let block = self.find_free(n, hint)?;
+        self.remove_block(&block, n);
+        Some(block)
+    }
+
+    /// Treating the set as a free list, removes and returns the block found by [`find_best_fit`].
+    ///
+    /// [`find_best_fit`]: RangeSetBlaze::find_best_fit
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut free = RangeSetBlaze::from_iter([0u64..=3, 10..=19, 30..=35]);
+    /// assert_eq!(free.allocate_best_fit(5), Some(30..=34));
+    /// assert_eq!(free.to_string(), "0..=3, 10..=19, 35..=35");
+    /// ```
+    pub fn allocate_best_fit(&mut self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        let block = self.find_best_fit(n)?;
+        self.remove_block(&block, n);
+        Some(block)
+    }
+
+    /// Treating the set as a free list, removes and returns the block found by [`find_worst_fit`].
+    ///
+    /// [`find_worst_fit`]: RangeSetBlaze::find_worst_fit
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut free = RangeSetBlaze::from_iter([0u64..=3, 10..=19, 30..=35]);
+    /// assert_eq!(free.allocate_worst_fit(5), Some(10..=14));
+    /// assert_eq!(free.to_string(), "0..=3, 15..=19, 30..=35");
+    /// ```
+    pub fn allocate_worst_fit(&mut self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        let block = self.find_worst_fit(n)?;
+        self.remove_block(&block, n);
+        Some(block)
+    }
+
+    // Removes a block of `n` elements that lies inside a single range of the set.
+    pub(crate) fn remove_block(&mut self, block: &RangeInclusive<T>, n: <T as Integer>::SafeLen) {
+        let (start, end) = block.clone().into_inner();
+        let (start_before, end_before) = self
+            .btree_map
+            .range_mut(..=start)
+            .next_back()
+            .expect("real assert: the block is inside a range");
+        let (start_before, end_before_old) = (*start_before, *end_before);
+        debug_assert!(end <= end_before_old); // real assert
+        if start_before < start {
+            *end_before = start - T::one();
+        } else {
+            self.btree_map.remove(&start_before);
+        }
+        if end < end_before_old {
+            self.btree_map.insert(end + T::one(), end_before_old);
+        }
+        self.len -= n;
+    }
+}
@@ -0,0 +1,130 @@
+use core::{fmt, ops::RangeInclusive};
+
+use alloc::vec::Vec;
+use num_traits::NumCast;
+
+use crate::{CheckSortedDisjoint, Integer, RangeSetBlaze};
+
+/// The error returned by [`RangeSetBlaze::try_convert`] when the set contains an integer that
+/// the target type can't hold.
+///
+/// # Examples
+///
+/// ```
+/// use range_set_blaze::{ConversionError, RangeSetBlaze};
+///
+/// let a = RangeSetBlaze::from_iter([-1i64..=10]);
+/// assert_eq!(a.try_convert::<u64>(), Err(ConversionError));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ConversionError;
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "integer out of range for the target type")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConversionError {}
+
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Converts the set to a [`RangeSetBlaze`] of another integer type, one range at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConversionError`] if the set contains an integer that doesn't fit in
+    /// `U::min_value()..=U::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O(*n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{ConversionError, RangeSetBlaze};
+    ///
+    /// let a = RangeSetBlaze::from_iter([0i64..=10, 300..=400]);
+    /// let b = a.try_convert::<u16>()?;
+    /// assert_eq!(b.to_string(), "0..=10, 300..=400");
+    /// assert_eq!(a.try_convert::<u8>(), Err(ConversionError));
+    /// # Ok::<(), ConversionError>(())
+    /// ```
+    pub fn try_convert<U: Integer>(&self) -> Result<RangeSetBlaze<U>, ConversionError> {
+        let ranges = self
+            .ranges()
+            .map(|range| {
+                let (start, end) = range.into_inner();
+                match (<U as NumCast>::from(start), <U as NumCast>::from(end)) {
+                    (Some(start), Some(end)) if end <= U::safe_max_value() => Ok(start..=end),
+                    _ => Err(ConversionError),
+                }
+            })
+            .collect::<Result<Vec<RangeInclusive<U>>, _>>()?;
+        // Casting keeps the order and the gaps, so the ranges stay sorted & disjoint.
+        Ok(RangeSetBlaze::from_sorted_disjoint(
+            CheckSortedDisjoint::new(ranges.into_iter()),
+        ))
+    }
+
+    /// Converts the set to a [`RangeSetBlaze`] of another integer type, one range at a time,
+    /// keeping only the integers that fit in `U::min_value()..=U::safe_max_value()`. Ranges that
+    /// straddle the target's bounds are clipped, and ranges outside them are dropped.
+    ///
+    /// # Performance
+    ///
+    /// O(*n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([-100i64..=-50, -10..=10, 250..=300, 1000..=2000]);
+    /// let b = a.convert_clamped::<u8>();
+    /// assert_eq!(b.to_string(), "0..=10, 250..=255");
+    /// ```
+    #[must_use]
+    pub fn convert_clamped<U: Integer>(&self) -> RangeSetBlaze<U> {
+        // U's bounds, as T. If U::min_value() doesn't fit in T, it is below T::min_value(),
+        // and if U::safe_max_value() doesn't fit, it is above T::safe_max_value().
+        let low = <T as NumCast>::from(U::min_value()).unwrap_or_else(T::min_value);
+        let high = <T as NumCast>::from(U::safe_max_value())
+            .map_or(T::safe_max_value(), |high| high.min(T::safe_max_value()));
+        let ranges = self.ranges().filter_map(|range| {
+            let (start, end) = range.into_inner();
+            let (start, end) = (start.max(low), end.min(high));
+            (start <= end).then(|| {
+                let cast = |value| <U as NumCast>::from(value).expect("real assert: value is in U");
+                cast(start)..=cast(end)
+            })
+        });
+        RangeSetBlaze::from_sorted_disjoint(CheckSortedDisjoint::new(ranges))
+    }
+}
+
+// Widening conversions can't fail, so they get `From` impls.
+macro_rules! impl_widening_from {
+    ($from:ty => $($to:ty),*) => {$(
+        impl From<RangeSetBlaze<$from>> for RangeSetBlaze<$to> {
+            /// Converts the set to a wider integer type, one range at a time.
+            ///
+            /// See [`RangeSetBlaze::try_convert`] for narrowing conversions.
+            fn from(value: RangeSetBlaze<$from>) -> Self {
+                value
+                    .try_convert()
+                    .expect("real assert: widening conversions can't fail")
+            }
+        }
+    )*};
+}
+
+impl_widening_from!(u8 => u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+impl_widening_from!(u16 => u32, u64, u128, usize, i32, i64, i128);
+impl_widening_from!(u32 => u64, u128, i64, i128);
+impl_widening_from!(u64 => u128, i128);
+impl_widening_from!(i8 => i16, i32, i64, i128, isize);
+impl_widening_from!(i16 => i32, i64, i128, isize);
+impl_widening_from!(i32 => i64, i128);
+impl_widening_from!(i64 => i128);
@@ -0,0 +1,187 @@
+use core::{iter::FusedIterator, ops::RangeInclusive};
+
+use num_traits::{One, Zero};
+
+use crate::{sorted_disjoint::impl_sorted_disjoint_and_ops, Integer, SortedDisjoint};
+
+/// An iterator that pairs each range of a [`SortedDisjoint`] iterator with the number of
+/// elements in it and in all the ranges before it.
+///
+/// This `struct` is created by the [`with_running_len`] method on [`SortedDisjoint`]. See
+/// [`with_running_len`]'s documentation for more.
+///
+/// [`with_running_len`]: SortedDisjoint::with_running_len
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct WithRunningLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    len: <T as Integer>::SafeLen,
+}
+
+impl<T, I> WithRunningLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I) -> Self {
+        WithRunningLenIter {
+            iter,
+            len: <T as Integer>::SafeLen::zero(),
+        }
+    }
+}
+
+impl<T, I> FusedIterator for WithRunningLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T> + FusedIterator,
+{
+}
+
+impl<T, I> Iterator for WithRunningLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = (RangeInclusive<T>, <T as Integer>::SafeLen);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = self.iter.next()?;
+        self.len += T::safe_len(&range);
+        Some((range, self.len))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// A [`SortedDisjoint`] iterator that stops after a given number of elements, shortening the
+/// range that crosses that boundary.
+///
+/// This `struct` is created by the [`take_len`] method on [`SortedDisjoint`]. See
+/// [`take_len`]'s documentation for more.
+///
+/// [`take_len`]: SortedDisjoint::take_len
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TakeLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    remaining: <T as Integer>::SafeLen,
+}
+
+impl<T, I> TakeLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, n: <T as Integer>::SafeLen) -> Self {
+        TakeLenIter { iter, remaining: n }
+    }
+}
+
+impl<T, I> Iterator for TakeLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        if self.remaining.is_zero() {
+            return None;
+        }
+        let range = self.iter.next()?;
+        let len = T::safe_len(&range);
+        if len < self.remaining {
+            self.remaining -= len;
+            return Some(range);
+        }
+        let start = *range.start();
+        let end = T::add_len_less_one(start, self.remaining);
+        self.remaining = <T as Integer>::SafeLen::zero();
+        Some(start..=end)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining.is_zero() {
+            (0, Some(0))
+        } else {
+            let (low, high) = self.iter.size_hint();
+            (low.min(1), high)
+        }
+    }
+}
+
+/// A [`SortedDisjoint`] iterator that skips a given number of elements, shortening the range
+/// that crosses that boundary.
+///
+/// This `struct` is created by the [`skip_len`] method on [`SortedDisjoint`]. See
+/// [`skip_len`]'s documentation for more.
+///
+/// [`skip_len`]: SortedDisjoint::skip_len
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SkipLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    remaining: <T as Integer>::SafeLen,
+}
+
+impl<T, I> SkipLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, n: <T as Integer>::SafeLen) -> Self {
+        SkipLenIter { iter, remaining: n }
+    }
+}
+
+impl<T, I> Iterator for SkipLenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        while !self.remaining.is_zero() {
+            let range = self.iter.next()?;
+            let len = T::safe_len(&range);
+            if len <= self.remaining {
+                self.remaining -= len;
+                continue;
+            }
+            // remaining < len, so remaining + 1 can't overflow
+            let start = T::add_len_less_one(
+                *range.start(),
+                self.remaining + <T as Integer>::SafeLen::one(),
+            );
+            self.remaining = <T as Integer>::SafeLen::zero();
+            return Some(start..=*range.end());
+        }
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining.is_zero() {
+            self.iter.size_hint()
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+impl_sorted_disjoint_and_ops!(TakeLenIter, SkipLenIter);
@@ -0,0 +1,557 @@
+use core::{
+    mem,
+    ops::{Bound, RangeInclusive},
+};
+
+use crate::{range_map, Integer, RangeMap, RangeSetBlaze};
+
+// The ranges after `current` or, from the ghost position, all the ranges.
+fn ranges_after<'a, T: Integer>(
+    btree_map: &'a RangeMap<T, T>,
+    current: Option<&RangeInclusive<T>>,
+) -> range_map::Range<'a, T, T> {
+    match current {
+        Some(range) => btree_map.range((Bound::Excluded(*range.start()), Bound::Unbounded)),
+        None => btree_map.range(..),
+    }
+}
+
+// The ranges before `current` or, from the ghost position, all the ranges.
+fn ranges_before<'a, T: Integer>(
+    btree_map: &'a RangeMap<T, T>,
+    current: Option<&RangeInclusive<T>>,
+) -> range_map::Range<'a, T, T> {
+    match current {
+        Some(range) => btree_map.range(..*range.start()),
+        None => btree_map.range(..),
+    }
+}
+
+// The range after `current` or, from the ghost position, the first range.
+fn next_range<T: Integer>(
+    btree_map: &RangeMap<T, T>,
+    current: Option<&RangeInclusive<T>>,
+) -> Option<RangeInclusive<T>> {
+    ranges_after(btree_map, current)
+        .next()
+        .map(|(start, end)| *start..=*end)
+}
+
+// The range before `current` or, from the ghost position, the last range.
+fn prev_range<T: Integer>(
+    btree_map: &RangeMap<T, T>,
+    current: Option<&RangeInclusive<T>>,
+) -> Option<RangeInclusive<T>> {
+    ranges_before(btree_map, current)
+        .next_back()
+        .map(|(start, end)| *start..=*end)
+}
+
+// The integers between `prev` (or `T::min_value()`) and `current`.
+fn gap_before<T: Integer>(
+    prev: Option<RangeInclusive<T>>,
+    current: Option<&RangeInclusive<T>>,
+) -> Option<RangeInclusive<T>> {
+    let start = *current?.start();
+    // Ranges are disjoint and not touching, so prev_end + 1 < start.
+    let gap_start = match prev {
+        Some(prev) => *prev.end() + T::one(),
+        None if T::min_value() < start => T::min_value(),
+        None => return None,
+    };
+    Some(gap_start..=start - T::one())
+}
+
+// The integers between `current` and `next` (or `T::safe_max_value()`).
+fn gap_after<T: Integer>(
+    next: Option<RangeInclusive<T>>,
+    current: Option<&RangeInclusive<T>>,
+) -> Option<RangeInclusive<T>> {
+    let end = *current?.end();
+    // Ranges are disjoint and not touching, so end + 1 < next_start.
+    let gap_end = match next {
+        Some(next) => *next.start() - T::one(),
+        None if end < T::safe_max_value() => T::safe_max_value(),
+        None => return None,
+    };
+    Some(end + T::one()..=gap_end)
+}
+
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Returns a [`Cursor`] pointing at the range that contains `value` or, if there is none,
+    /// at the first range after `value`. If there is no such range, the cursor points at the
+    /// "ghost" position, past the last range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// assert_eq!(set.cursor_at(15).range(), Some(10..=20));
+    /// assert_eq!(set.cursor_at(25).range(), Some(30..=40));
+    /// assert_eq!(set.cursor_at(45).range(), None);
+    /// ```
+    pub fn cursor_at(&self, value: T) -> Cursor<'_, T> {
+        Cursor::new(self, self.next_range_at_or_after(value))
+    }
+
+    /// Returns a [`Cursor`] pointing at the first range, or at the "ghost" position if the set
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// let mut cursor = set.cursor_front();
+    /// assert_eq!(cursor.range(), Some(10..=20));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.range(), Some(30..=40));
+    /// ```
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor::new(self, next_range(&self.btree_map, None))
+    }
+
+    /// Returns a [`Cursor`] pointing at the last range, or at the "ghost" position if the set
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// let mut cursor = set.cursor_back();
+    /// assert_eq!(cursor.range(), Some(30..=40));
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.range(), Some(10..=20));
+    /// ```
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor::new(self, prev_range(&self.btree_map, None))
+    }
+
+    /// Returns a [`CursorMut`] pointing at the range that contains `value` or, if there is
+    /// none, at the first range after `value`. If there is no such range, the cursor points at
+    /// the "ghost" position, past the last range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// let mut cursor = set.cursor_at_mut(25);
+    /// cursor.extend(22..=40);
+    /// assert_eq!(set.to_string(), "10..=20, 22..=40");
+    /// ```
+    pub fn cursor_at_mut(&mut self, value: T) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.next_range_at_or_after(value),
+            set: self,
+        }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the first range, or at the "ghost" position if the
+    /// set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([1..=1, 10..=20, 30..=40]);
+    /// let mut cursor = set.cursor_front_mut();
+    /// // Remove ranges of fewer than 5 integers.
+    /// while let Some(range) = cursor.range() {
+    ///     if range.end() - range.start() < 4 {
+    ///         cursor.remove_current();
+    ///     } else {
+    ///         cursor.move_next();
+    ///     }
+    /// }
+    /// assert_eq!(set.to_string(), "10..=20, 30..=40");
+    /// ```
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: next_range(&self.btree_map, None),
+            set: self,
+        }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the last range, or at the "ghost" position if the
+    /// set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// let mut cursor = set.cursor_back_mut();
+    /// cursor.shrink(30..=35);
+    /// assert_eq!(set.to_string(), "10..=20, 30..=35");
+    /// ```
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: prev_range(&self.btree_map, None),
+            set: self,
+        }
+    }
+}
+
+/// A cursor over the ranges of a [`RangeSetBlaze`].
+///
+/// A cursor points at one range of the set, or at a "ghost" position between the last range
+/// and the first. Moving next from the last range, or previous from the first, reaches the
+/// ghost position; moving again wraps around.
+///
+/// This `struct` is created by the [`cursor_at`], [`cursor_front`], and [`cursor_back`] methods
+/// on [`RangeSetBlaze`]. See their documentation for more.
+///
+/// # Performance
+///
+/// The cursor holds iterators over the ranges on each side of it, so moving and looking at a
+/// neighbor or a gap are amortized O(1) while it keeps moving the same way. Making a cursor,
+/// the first move back the way it came, and wrapping around through the ghost position are
+/// O(log *n*), where *n* is the number of ranges in the set.
+///
+/// [`cursor_at`]: RangeSetBlaze::cursor_at
+/// [`cursor_front`]: RangeSetBlaze::cursor_front
+/// [`cursor_back`]: RangeSetBlaze::cursor_back
+#[derive(Clone, Debug)]
+pub struct Cursor<'a, T: Integer> {
+    set: &'a RangeSetBlaze<T>,
+    current: Option<RangeInclusive<T>>,
+    after: Side<'a, T>,
+    before: Side<'a, T>,
+}
+
+// The ranges on one side of a `Cursor`. After a move, the side the cursor came from is only
+// known one range deep, so moving that way again starts with a fresh lookup.
+#[derive(Clone, Debug)]
+enum Side<'a, T: Integer> {
+    // Every range on this side, nearest first.
+    Ranges(range_map::Range<'a, T, T>),
+    // Only the nearest range on this side, or `None` if there is none.
+    Nearest(Option<RangeInclusive<T>>),
+}
+
+impl<'a, T: Integer> Cursor<'a, T> {
+    fn new(set: &'a RangeSetBlaze<T>, current: Option<RangeInclusive<T>>) -> Self {
+        Cursor {
+            after: Side::Ranges(ranges_after(&set.btree_map, current.as_ref())),
+            before: Side::Ranges(ranges_before(&set.btree_map, current.as_ref())),
+            set,
+            current,
+        }
+    }
+
+    /// Returns the range the cursor points at, or `None` at the ghost position.
+    #[must_use]
+    pub fn range(&self) -> Option<RangeInclusive<T>> {
+        self.current.clone()
+    }
+
+    /// Moves the cursor to the next range. From the last range, it moves to the ghost
+    /// position, and from the ghost position, to the first range.
+    pub fn move_next(&mut self) {
+        let btree_map = &self.set.btree_map;
+        let mut after = match mem::replace(&mut self.after, Side::Nearest(None)) {
+            Side::Ranges(ranges) => ranges,
+            Side::Nearest(_) => ranges_after(btree_map, self.current.as_ref()),
+        };
+        let next = after.next().map(|(start, end)| *start..=*end);
+        if next.is_none() {
+            // At the ghost position, the next range is the first range.
+            after = btree_map.range(..);
+        }
+        self.after = Side::Ranges(after);
+        self.before = Side::Nearest(mem::replace(&mut self.current, next));
+    }
+
+    /// Moves the cursor to the previous range. From the first range, it moves to the ghost
+    /// position, and from the ghost position, to the last range.
+    pub fn move_prev(&mut self) {
+        let btree_map = &self.set.btree_map;
+        let mut before = match mem::replace(&mut self.before, Side::Nearest(None)) {
+            Side::Ranges(ranges) => ranges,
+            Side::Nearest(_) => ranges_before(btree_map, self.current.as_ref()),
+        };
+        let prev = before.next_back().map(|(start, end)| *start..=*end);
+        if prev.is_none() {
+            // At the ghost position, the previous range is the last range.
+            before = btree_map.range(..);
+        }
+        self.before = Side::Ranges(before);
+        self.after = Side::Nearest(mem::replace(&mut self.current, prev));
+    }
+
+    /// Returns the range that [`move_next`] would move to, without moving.
+    ///
+    /// [`move_next`]: Cursor::move_next
+    #[must_use]
+    pub fn peek_next(&self) -> Option<RangeInclusive<T>> {
+        match &self.after {
+            Side::Ranges(ranges) => ranges.clone().next().map(|(start, end)| *start..=*end),
+            Side::Nearest(next) => next.clone(),
+        }
+    }
+
+    /// Returns the range that [`move_prev`] would move to, without moving.
+    ///
+    /// [`move_prev`]: Cursor::move_prev
+    #[must_use]
+    pub fn peek_prev(&self) -> Option<RangeInclusive<T>> {
+        match &self.before {
+            Side::Ranges(ranges) => ranges.clone().next_back().map(|(start, end)| *start..=*end),
+            Side::Nearest(prev) => prev.clone(),
+        }
+    }
+
+    /// Returns the integers between the previous range (or `T::min_value()`) and the current
+    /// range. Returns `None` if there are none, or at the ghost position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([0u8..=20, 30..=40]);
+    /// let mut cursor = set.cursor_front();
+    /// assert_eq!(cursor.gap_before(), None);
+    /// assert_eq!(cursor.gap_after(), Some(21..=29));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.gap_before(), Some(21..=29));
+    /// assert_eq!(cursor.gap_after(), Some(41..=255));
+    /// ```
+    #[must_use]
+    pub fn gap_before(&self) -> Option<RangeInclusive<T>> {
+        gap_before(self.peek_prev(), self.current.as_ref())
+    }
+
+    /// Returns the integers between the current range and the next range (or
+    /// `T::safe_max_value()`). Returns `None` if there are none, or at the ghost position.
+    ///
+    /// See [`gap_before`] for an example.
+    ///
+    /// [`gap_before`]: Cursor::gap_before
+    #[must_use]
+    pub fn gap_after(&self) -> Option<RangeInclusive<T>> {
+        gap_after(self.peek_next(), self.current.as_ref())
+    }
+}
+
+/// A cursor over the ranges of a [`RangeSetBlaze`] that can also edit the current range.
+///
+/// Moving works as with [`Cursor`]. The editing methods keep the set's ranges sorted and
+/// disjoint and its [`len`] correct.
+///
+/// This `struct` is created by the [`cursor_at_mut`], [`cursor_front_mut`], and
+/// [`cursor_back_mut`] methods on [`RangeSetBlaze`]. See their documentation for more.
+///
+/// # Performance
+///
+/// Each move, look at a neighbor, and edit is O(log *n*), where *n* is the number of ranges in
+/// the set. [`extend`] also costs O(*m*), where *m* is the number of ranges it merges.
+///
+/// [`len`]: RangeSetBlaze::len
+/// [`cursor_at_mut`]: RangeSetBlaze::cursor_at_mut
+/// [`cursor_front_mut`]: RangeSetBlaze::cursor_front_mut
+/// [`cursor_back_mut`]: RangeSetBlaze::cursor_back_mut
+/// [`extend`]: CursorMut::extend
+#[derive(Debug)]
+pub struct CursorMut<'a, T: Integer> {
+    set: &'a mut RangeSetBlaze<T>,
+    current: Option<RangeInclusive<T>>,
+}
+
+impl<T: Integer> CursorMut<'_, T> {
+    /// Returns the range the cursor points at, or `None` at the ghost position.
+    #[must_use]
+    pub fn range(&self) -> Option<RangeInclusive<T>> {
+        self.current.clone()
+    }
+
+    /// Moves the cursor to the next range. From the last range, it moves to the ghost
+    /// position, and from the ghost position, to the first range.
+    pub fn move_next(&mut self) {
+        self.current = next_range(&self.set.btree_map, self.current.as_ref());
+    }
+
+    /// Moves the cursor to the previous range. From the first range, it moves to the ghost
+    /// position, and from the ghost position, to the last range.
+    pub fn move_prev(&mut self) {
+        self.current = prev_range(&self.set.btree_map, self.current.as_ref());
+    }
+
+    /// Returns the range that [`move_next`] would move to, without moving.
+    ///
+    /// [`move_next`]: CursorMut::move_next
+    #[must_use]
+    pub fn peek_next(&self) -> Option<RangeInclusive<T>> {
+        next_range(&self.set.btree_map, self.current.as_ref())
+    }
+
+    /// Returns the range that [`move_prev`] would move to, without moving.
+    ///
+    /// [`move_prev`]: CursorMut::move_prev
+    #[must_use]
+    pub fn peek_prev(&self) -> Option<RangeInclusive<T>> {
+        prev_range(&self.set.btree_map, self.current.as_ref())
+    }
+
+    /// Returns the integers between the previous range (or `T::min_value()`) and the current
+    /// range. Returns `None` if there are none, or at the ghost position.
+    #[must_use]
+    pub fn gap_before(&self) -> Option<RangeInclusive<T>> {
+        gap_before(self.peek_prev(), self.current.as_ref())
+    }
+
+    /// Returns the integers between the current range and the next range (or
+    /// `T::safe_max_value()`). Returns `None` if there are none, or at the ghost position.
+    #[must_use]
+    pub fn gap_after(&self) -> Option<RangeInclusive<T>> {
+        gap_after(self.peek_next(), self.current.as_ref())
+    }
+
+    /// Returns a read-only [`Cursor`] pointing at the same position.
+    #[must_use]
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor::new(self.set, self.current.clone())
+    }
+
+    /// Replaces the current range with `range`, a non-empty part of it. The cursor stays on
+    /// the shrunken range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cursor is at the ghost position or if `range` is empty or not inside the
+    /// current range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// let mut cursor = set.cursor_at_mut(15);
+    /// cursor.shrink(12..=18);
+    /// assert_eq!(cursor.range(), Some(12..=18));
+    /// assert_eq!(set.to_string(), "12..=18, 30..=40");
+    /// assert_eq!(set.len(), 18usize);
+    /// ```
+    pub fn shrink(&mut self, range: RangeInclusive<T>) {
+        let current = self.current.clone().expect("cursor must point at a range");
+        let (start, end) = range.clone().into_inner();
+        assert!(
+            start <= end && *current.start() <= start && end <= *current.end(),
+            "range must be a non-empty part of the current range"
+        );
+        if start != *current.start() {
+            self.set.btree_map.remove(current.start());
+        }
+        self.set.btree_map.insert(start, end);
+        self.set.len -= T::safe_len(&current);
+        self.set.len += T::safe_len(&range);
+        self.current = Some(range);
+    }
+
+    /// Replaces the current range with `range`, which must contain it, merging with any ranges
+    /// that `range` overlaps or touches. The cursor moves to the merged range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cursor is at the ghost position, if `range` doesn't contain the current
+    /// range, or if `range.end() > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([10..=20, 30..=40, 50..=60]);
+    /// let mut cursor = set.cursor_at_mut(15);
+    /// cursor.extend(5..=29);
+    /// assert_eq!(cursor.range(), Some(5..=40));
+    /// assert_eq!(set.to_string(), "5..=40, 50..=60");
+    /// assert_eq!(set.len(), 47usize);
+    /// ```
+    pub fn extend(&mut self, range: RangeInclusive<T>) {
+        let current = self.current.clone().expect("cursor must point at a range");
+        let start = *range.start();
+        assert!(
+            start <= *current.start() && *current.end() <= *range.end(),
+            "range must contain the current range"
+        );
+        self.set.internal_add(range);
+        self.current = self.set.prev_range_at_or_before(start);
+    }
+
+    /// Removes `gap`, which must lie strictly inside the current range, splitting the range in
+    /// two. The cursor moves to the lower part.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cursor is at the ghost position or if `gap` is empty or doesn't lie
+    /// strictly inside the current range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([10..=20]);
+    /// let mut cursor = set.cursor_front_mut();
+    /// cursor.split(14..=15);
+    /// assert_eq!(cursor.range(), Some(10..=13));
+    /// assert_eq!(cursor.peek_next(), Some(16..=20));
+    /// assert_eq!(set.len(), 9usize);
+    /// ```
+    pub fn split(&mut self, gap: RangeInclusive<T>) {
+        let current = self.current.clone().expect("cursor must point at a range");
+        let (gap_start, gap_end) = gap.clone().into_inner();
+        assert!(
+            gap_start <= gap_end && *current.start() < gap_start && gap_end < *current.end(),
+            "gap must be non-empty and strictly inside the current range"
+        );
+        let lower = *current.start()..=gap_start - T::one();
+        self.set.btree_map.insert(*lower.start(), *lower.end());
+        self.set
+            .btree_map
+            .insert(gap_end + T::one(), *current.end());
+        self.set.len -= T::safe_len(&gap);
+        self.current = Some(lower);
+    }
+
+    /// Removes the current range and returns it. The cursor moves to the next range (or to the
+    /// ghost position). At the ghost position, does nothing and returns `None`.
+    ///
+    /// See [`cursor_front_mut`] for an example.
+    ///
+    /// [`cursor_front_mut`]: RangeSetBlaze::cursor_front_mut
+    pub fn remove_current(&mut self) -> Option<RangeInclusive<T>> {
+        let current = self.current.take()?;
+        self.set.btree_map.remove(current.start());
+        self.set.len -= T::safe_len(&current);
+        self.current = next_range(&self.set.btree_map, Some(&current));
+        Some(current)
+    }
+}
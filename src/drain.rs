@@ -0,0 +1,197 @@
+use alloc::vec::Vec;
+use core::{
+    iter::FusedIterator,
+    mem,
+    ops::{Bound, RangeBounds, RangeInclusive},
+};
+
+use crate::{
+    sorted_disjoint::impl_sorted_disjoint_and_ops, ChangedRanges, Integer, IntoRangesIter,
+    RangeSetBlaze,
+};
+
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Removes every range from the set and returns them as a
+    /// [`SortedDisjoint`](crate::SortedDisjoint) iterator. The set is left empty.
+    ///
+    /// # Performance
+    ///
+    /// O(1) to empty the set. The returned iterator takes O(*n*) to consume, where *n* is the
+    /// number of ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let mut a = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// let b = CheckSortedDisjoint::from([15..=35]);
+    /// assert_eq!((a.drain() - b).to_string(), "10..=14, 36..=40");
+    /// assert!(a.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> IntoRangesIter<T> {
+        mem::replace(self, RangeSetBlaze::new()).into_ranges()
+    }
+
+    /// Removes the integers in `range` from the set and returns them as ranges. Ranges of the
+    /// set that stick out of `range` are clipped, and the parts outside stay in the set.
+    ///
+    /// The range may also be entered as `(Bound<T>, Bound<T>)`, as with
+    /// [`range`](RangeSetBlaze::range).
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`, or if range `end > T::safe_max_value()`. A range that is
+    /// empty only because of an excluded bound, such as `5..5` or `..0`, removes nothing.
+    ///
+    /// # Performance
+    ///
+    /// O(*k* log *n*), where *n* is the number of ranges in the set and *k* is the number of
+    /// ranges removed. It makes one pass, unlike calling [`range`] and then `-`.
+    ///
+    /// This is not O(log *n* + *k*): [`BTreeMap`] can't cut out a run of keys that fast.
+    /// Its `split_off` also counts the keys on the smaller side, and `append` rebuilds both
+    /// maps, so splitting the set around `range` and joining the rest would be O(*n*). Instead,
+    /// each removed range costs its own O(log *n*) removal.
+    ///
+    /// [`BTreeMap`]: alloc::collections::BTreeMap
+    ///
+    /// [`range`]: RangeSetBlaze::range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut a = RangeSetBlaze::from_iter([500..=1200, 1500..=1600, 1900..=2500]);
+    /// let b = RangeSetBlaze::from_sorted_disjoint(a.drain_range(1000..=2000));
+    /// assert_eq!(a.to_string(), "500..=999, 2001..=2500");
+    /// assert_eq!(b.to_string(), "1000..=1200, 1500..=1600, 1900..=2000");
+    /// ```
+    pub fn drain_range<R>(&mut self, range: R) -> ChangedRanges<T>
+    where
+        R: RangeBounds<T>,
+    {
+        if let (
+            Bound::Included(start) | Bound::Excluded(start),
+            Bound::Included(end) | Bound::Excluded(end),
+        ) = (range.start_bound(), range.end_bound())
+        {
+            assert!(start <= end, "start must be <= end");
+        }
+        // An excluded bound at the edge of the domain, or `x..x`, leaves nothing to remove.
+        let start = match range.start_bound() {
+            Bound::Included(n) => Some(*n),
+            Bound::Excluded(n) => n.checked_add(&T::one()),
+            Bound::Unbounded => Some(T::min_value()),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(n) => Some(*n),
+            Bound::Excluded(n) if *n == T::min_value() => None,
+            Bound::Excluded(n) => Some(*n - T::one()),
+            Bound::Unbounded => Some(T::safe_max_value()),
+        };
+        match (start, end) {
+            (Some(start), Some(end)) if start <= end => self.ranges_remove_diff(start..=end),
+            _ => ChangedRanges {
+                iter: Vec::new().into_iter(),
+            },
+        }
+    }
+
+    /// Returns an iterator that removes the ranges for which `predicate` returns `true` and
+    /// yields them, in order. The ranges are whole ranges of the set; they are not split.
+    ///
+    /// The ranges are removed as the iterator reaches them. If the iterator is dropped before
+    /// it is used up, the ranges it hasn't reached stay in the set.
+    ///
+    /// # Performance
+    ///
+    /// Each step is O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([1..=1, 10..=20, 25..=26, 30..=40]);
+    /// // Move out the ranges of fewer than 5 integers.
+    /// let small = RangeSetBlaze::from_sorted_disjoint(
+    ///     set.extract_if_ranges(|range| range.end() - range.start() < 4),
+    /// );
+    /// assert_eq!(small.to_string(), "1..=1, 25..=26");
+    /// assert_eq!(set.to_string(), "10..=20, 30..=40");
+    /// ```
+    pub fn extract_if_ranges<F>(&mut self, predicate: F) -> ExtractIfRanges<'_, T, F>
+    where
+        F: FnMut(&RangeInclusive<T>) -> bool,
+    {
+        ExtractIfRanges {
+            set: self,
+            next_start: Some(T::min_value()),
+            predicate,
+        }
+    }
+}
+
+/// An iterator that removes the ranges of a [`RangeSetBlaze`] that match a predicate and
+/// yields them. It is a [`SortedDisjoint`](crate::SortedDisjoint) iterator.
+///
+/// This `struct` is created by the [`extract_if_ranges`] method on [`RangeSetBlaze`]. See
+/// [`extract_if_ranges`]'s documentation for more.
+///
+/// [`extract_if_ranges`]: RangeSetBlaze::extract_if_ranges
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ExtractIfRanges<'a, T, F>
+where
+    T: Integer,
+    F: FnMut(&RangeInclusive<T>) -> bool,
+{
+    set: &'a mut RangeSetBlaze<T>,
+    // The smallest start not yet visited, or `None` once every range has been visited.
+    next_start: Option<T>,
+    predicate: F,
+}
+
+impl<T, F> Iterator for ExtractIfRanges<'_, T, F>
+where
+    T: Integer,
+    F: FnMut(&RangeInclusive<T>) -> bool,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        while let Some(next_start) = self.next_start {
+            let Some((&start, &end)) = self.set.btree_map.range(next_start..).next() else {
+                self.next_start = None;
+                break;
+            };
+            // Ranges don't touch, so a range that ends at the max is the last one.
+            self.next_start = end.checked_add(&T::one());
+            let range = start..=end;
+            if (self.predicate)(&range) {
+                self.set.btree_map.remove(&start);
+                self.set.len -= T::safe_len(&range);
+                return Some(range);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.next_start {
+            Some(_) => (0, Some(self.set.ranges_len())),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+impl<T, F> FusedIterator for ExtractIfRanges<'_, T, F>
+where
+    T: Integer,
+    F: FnMut(&RangeInclusive<T>) -> bool,
+{
+}
+
+impl_sorted_disjoint_and_ops!(<'a, T, F> ExtractIfRanges<'a, T, F> where F: FnMut(&RangeInclusive<T>) -> bool);
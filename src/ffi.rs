@@ -0,0 +1,960 @@
+//! A C ABI for `RangeSetBlaze<u64>` and `RangeSetBlaze<i64>`, enabled by the `ffi` feature.
+//!
+//! Sets are opaque handles, [`RangeSetU64`] and [`RangeSetI64`], created and freed through
+//! the `rsb_u64_*` and `rsb_i64_*` functions. Every function that can fail returns an
+//! [`RsbStatus`] and writes its results through out-pointers, and no panic crosses the FFI
+//! boundary. The matching C header, `include/range_set_blaze.h`, is generated by `cbindgen`
+//! when the crate is built with the `ffi` feature.
+//!
+//! To build a library for C or C++, use, for example:
+//! ```bash
+//! cargo rustc --release --features ffi --lib --crate-type staticlib
+//! ```
+//!
+//! # Serialization
+//!
+//! [`rsb_u64_serialize`] and [`rsb_i64_serialize`] write a set as the magic bytes `RSB`, a
+//! format version (1), a type tag (`U` for `u64`, `I` for `i64`), the number of ranges as a
+//! little-endian `u64`, and then each range's inclusive `start` and `end` as little-endian
+//! 8-byte integers.
+#![allow(unsafe_code)]
+
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    slice,
+};
+
+use num_traits::One;
+
+use crate::{Integer, RangeSetBlaze};
+
+/// The result of an FFI call.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RsbStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// A range's `start` was greater than its `end`.
+    InvalidRange = 2,
+    /// The caller's buffer was too small. The required length was still written.
+    BufferTooSmall = 3,
+    /// The data to deserialize was malformed.
+    InvalidData = 4,
+    /// The result doesn't fit in the out-pointer's type.
+    Overflow = 5,
+    /// The call panicked. This is a bug in the library.
+    Panic = 6,
+}
+
+/// An inclusive range of `u64`s, as written by [`rsb_u64_ranges`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RsbRangeU64 {
+    /// The first integer in the range.
+    pub start: u64,
+    /// The last integer in the range.
+    pub end: u64,
+}
+
+/// An inclusive range of `i64`s, as written by [`rsb_i64_ranges`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RsbRangeI64 {
+    /// The first integer in the range.
+    pub start: i64,
+    /// The last integer in the range.
+    pub end: i64,
+}
+
+/// An opaque handle to a `RangeSetBlaze<u64>`.
+#[derive(Clone, Debug, Default)]
+pub struct RangeSetU64(RangeSetBlaze<u64>);
+
+/// An opaque handle to a `RangeSetBlaze<i64>`.
+#[derive(Clone, Debug, Default)]
+pub struct RangeSetI64(RangeSetBlaze<i64>);
+
+const MAGIC: &[u8; 4] = b"RSB\x01";
+const HEADER_LEN: usize = MAGIC.len() + 1 + 8;
+
+// What the generic implementations need to know about a handle type.
+trait Handle: Clone + Default {
+    type T: Integer;
+    type Range: Copy;
+    const TAG: u8;
+
+    fn from_set(set: RangeSetBlaze<Self::T>) -> Self;
+    fn set(&self) -> &RangeSetBlaze<Self::T>;
+    fn set_mut(&mut self) -> &mut RangeSetBlaze<Self::T>;
+    fn to_range(start: Self::T, end: Self::T) -> Self::Range;
+    fn to_le_bytes(value: Self::T) -> [u8; 8];
+    fn from_le_bytes(bytes: [u8; 8]) -> Self::T;
+}
+
+impl Handle for RangeSetU64 {
+    type T = u64;
+    type Range = RsbRangeU64;
+    const TAG: u8 = b'U';
+
+    fn from_set(set: RangeSetBlaze<u64>) -> Self {
+        Self(set)
+    }
+    fn set(&self) -> &RangeSetBlaze<u64> {
+        &self.0
+    }
+    fn set_mut(&mut self) -> &mut RangeSetBlaze<u64> {
+        &mut self.0
+    }
+    fn to_range(start: u64, end: u64) -> RsbRangeU64 {
+        RsbRangeU64 { start, end }
+    }
+    fn to_le_bytes(value: u64) -> [u8; 8] {
+        value.to_le_bytes()
+    }
+    fn from_le_bytes(bytes: [u8; 8]) -> u64 {
+        u64::from_le_bytes(bytes)
+    }
+}
+
+impl Handle for RangeSetI64 {
+    type T = i64;
+    type Range = RsbRangeI64;
+    const TAG: u8 = b'I';
+
+    fn from_set(set: RangeSetBlaze<i64>) -> Self {
+        Self(set)
+    }
+    fn set(&self) -> &RangeSetBlaze<i64> {
+        &self.0
+    }
+    fn set_mut(&mut self) -> &mut RangeSetBlaze<i64> {
+        &mut self.0
+    }
+    fn to_range(start: i64, end: i64) -> RsbRangeI64 {
+        RsbRangeI64 { start, end }
+    }
+    fn to_le_bytes(value: i64) -> [u8; 8] {
+        value.to_le_bytes()
+    }
+    fn from_le_bytes(bytes: [u8; 8]) -> i64 {
+        i64::from_le_bytes(bytes)
+    }
+}
+
+// Runs `f`, turning a panic into `RsbStatus::Panic`.
+fn guard(f: impl FnOnce() -> RsbStatus) -> RsbStatus {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(RsbStatus::Panic)
+}
+
+// Writes `value` through `out` if `out` isn't null.
+unsafe fn write_optional<V>(out: *mut V, value: V) {
+    if !out.is_null() {
+        out.write(value);
+    }
+}
+
+// Moves `handle` to the heap and writes the pointer through `out`.
+unsafe fn write_handle<H: Handle>(out: *mut *mut H, handle: H) -> RsbStatus {
+    if out.is_null() {
+        return RsbStatus::NullPointer;
+    }
+    out.write(Box::into_raw(Box::new(handle)));
+    RsbStatus::Ok
+}
+
+unsafe fn new<H: Handle>(out: *mut *mut H) -> RsbStatus {
+    guard(|| write_handle(out, H::default()))
+}
+
+unsafe fn clone<H: Handle>(set: *const H, out: *mut *mut H) -> RsbStatus {
+    guard(|| match set.as_ref() {
+        Some(set) => write_handle(out, set.clone()),
+        None => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn free<H: Handle>(set: *mut H) {
+    if !set.is_null() {
+        // Dropping a set can't panic, but be safe anyway.
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(set))));
+    }
+}
+
+unsafe fn insert<H: Handle>(set: *mut H, value: H::T, inserted: *mut bool) -> RsbStatus {
+    guard(|| match set.as_mut() {
+        Some(set) => {
+            write_optional(inserted, set.set_mut().insert(value));
+            RsbStatus::Ok
+        }
+        None => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn ranges_insert<H: Handle>(
+    set: *mut H,
+    start: H::T,
+    end: H::T,
+    inserted: *mut bool,
+) -> RsbStatus {
+    guard(|| match set.as_mut() {
+        Some(_) if start > end => RsbStatus::InvalidRange,
+        Some(set) => {
+            write_optional(inserted, set.set_mut().ranges_insert(start..=end));
+            RsbStatus::Ok
+        }
+        None => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn remove<H: Handle>(set: *mut H, value: H::T, removed: *mut bool) -> RsbStatus {
+    guard(|| match set.as_mut() {
+        Some(set) => {
+            write_optional(removed, set.set_mut().remove(value));
+            RsbStatus::Ok
+        }
+        None => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn ranges_remove<H: Handle>(
+    set: *mut H,
+    start: H::T,
+    end: H::T,
+    removed: *mut bool,
+) -> RsbStatus {
+    guard(|| match set.as_mut() {
+        Some(_) if start > end => RsbStatus::InvalidRange,
+        Some(set) => {
+            let set = set.set_mut();
+            let len_before = set.len();
+            set.internal_remove_diff(start..=end, None);
+            write_optional(removed, set.len() != len_before);
+            RsbStatus::Ok
+        }
+        None => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn contains<H: Handle>(set: *const H, value: H::T, contained: *mut bool) -> RsbStatus {
+    guard(|| match (set.as_ref(), contained.is_null()) {
+        (Some(set), false) => {
+            contained.write(set.set().contains(value));
+            RsbStatus::Ok
+        }
+        _ => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn len<H: Handle>(set: *const H, len: *mut u64) -> RsbStatus
+where
+    u64: TryFrom<<H::T as Integer>::SafeLen>,
+{
+    guard(|| match (set.as_ref(), len.is_null()) {
+        (Some(set), false) => match u64::try_from(set.set().len()) {
+            Ok(set_len) => {
+                len.write(set_len);
+                RsbStatus::Ok
+            }
+            Err(_) => RsbStatus::Overflow,
+        },
+        _ => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn ranges_len<H: Handle>(set: *const H, ranges_len: *mut usize) -> RsbStatus {
+    guard(|| match (set.as_ref(), ranges_len.is_null()) {
+        (Some(set), false) => {
+            ranges_len.write(set.set().ranges_len());
+            RsbStatus::Ok
+        }
+        _ => RsbStatus::NullPointer,
+    })
+}
+
+type SetOp<T> = fn(&RangeSetBlaze<T>, &RangeSetBlaze<T>) -> RangeSetBlaze<T>;
+
+// Applies a binary set operation and writes the result as a new handle.
+unsafe fn binary<H: Handle>(
+    a: *const H,
+    b: *const H,
+    out: *mut *mut H,
+    op: SetOp<H::T>,
+) -> RsbStatus {
+    guard(|| match (a.as_ref(), b.as_ref()) {
+        (Some(a), Some(b)) => write_handle(out, H::from_set(op(a.set(), b.set()))),
+        _ => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn complement<H: Handle>(set: *const H, out: *mut *mut H) -> RsbStatus {
+    guard(|| match set.as_ref() {
+        Some(set) => write_handle(out, H::from_set(!set.set())),
+        None => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn equal<H: Handle>(a: *const H, b: *const H, equal: *mut bool) -> RsbStatus {
+    guard(|| match (a.as_ref(), b.as_ref(), equal.is_null()) {
+        (Some(a), Some(b), false) => {
+            equal.write(a.set() == b.set());
+            RsbStatus::Ok
+        }
+        _ => RsbStatus::NullPointer,
+    })
+}
+
+unsafe fn ranges<H: Handle>(
+    set: *const H,
+    buffer: *mut H::Range,
+    capacity: usize,
+    ranges_len: *mut usize,
+) -> RsbStatus {
+    guard(|| {
+        let Some(set) = set.as_ref() else {
+            return RsbStatus::NullPointer;
+        };
+        if ranges_len.is_null() {
+            return RsbStatus::NullPointer;
+        }
+        let set_ranges_len = set.set().ranges_len();
+        ranges_len.write(set_ranges_len);
+        if capacity < set_ranges_len {
+            return RsbStatus::BufferTooSmall;
+        }
+        if set_ranges_len == 0 {
+            return RsbStatus::Ok;
+        }
+        if buffer.is_null() {
+            return RsbStatus::NullPointer;
+        }
+        for (index, range) in set.set().ranges().enumerate() {
+            buffer
+                .add(index)
+                .write(H::to_range(*range.start(), *range.end()));
+        }
+        RsbStatus::Ok
+    })
+}
+
+unsafe fn serialize<H: Handle>(
+    set: *const H,
+    buffer: *mut u8,
+    capacity: usize,
+    len: *mut usize,
+) -> RsbStatus {
+    guard(|| {
+        let Some(set) = set.as_ref() else {
+            return RsbStatus::NullPointer;
+        };
+        if len.is_null() {
+            return RsbStatus::NullPointer;
+        }
+        let ranges_len = set.set().ranges_len();
+        let Some(serialized_len) = ranges_len
+            .checked_mul(16)
+            .and_then(|ranges_bytes| ranges_bytes.checked_add(HEADER_LEN))
+        else {
+            return RsbStatus::Overflow;
+        };
+        len.write(serialized_len);
+        if capacity < serialized_len {
+            return RsbStatus::BufferTooSmall;
+        }
+        if buffer.is_null() {
+            return RsbStatus::NullPointer;
+        }
+        let buffer = slice::from_raw_parts_mut(buffer, serialized_len);
+        let (header, mut rest) = buffer.split_at_mut(HEADER_LEN);
+        header[..MAGIC.len()].copy_from_slice(MAGIC);
+        header[MAGIC.len()] = H::TAG;
+        header[MAGIC.len() + 1..].copy_from_slice(&(ranges_len as u64).to_le_bytes());
+        for range in set.set().ranges() {
+            let (pair, tail) = rest.split_at_mut(16);
+            pair[..8].copy_from_slice(&H::to_le_bytes(*range.start()));
+            pair[8..].copy_from_slice(&H::to_le_bytes(*range.end()));
+            rest = tail;
+        }
+        RsbStatus::Ok
+    })
+}
+
+// Parses serialized ranges, checking that they are sorted and disjoint.
+fn parse<H: Handle>(data: &[u8]) -> Option<RangeSetBlaze<H::T>> {
+    let (header, mut rest) = data.split_at_checked(HEADER_LEN)?;
+    if &header[..MAGIC.len()] != MAGIC || header[MAGIC.len()] != H::TAG {
+        return None;
+    }
+    let ranges_len = u64::from_le_bytes(header[MAGIC.len() + 1..].try_into().ok()?);
+    if u64::try_from(rest.len()).ok()? != ranges_len.checked_mul(16)? {
+        return None;
+    }
+    let mut set = RangeSetBlaze::new();
+    let mut previous_end: Option<H::T> = None;
+    while let Some((pair, tail)) = rest.split_at_checked(16) {
+        let start = H::from_le_bytes(pair[..8].try_into().ok()?);
+        let end = H::from_le_bytes(pair[8..].try_into().ok()?);
+        // previous_end < start, so previous_end + 1 can't overflow
+        let disjoint = previous_end
+            .is_none_or(|previous_end| previous_end < start && previous_end + H::T::one() < start);
+        if start > end || !disjoint {
+            return None;
+        }
+        set.ranges_insert(start..=end);
+        previous_end = Some(end);
+        rest = tail;
+    }
+    Some(set)
+}
+
+unsafe fn deserialize<H: Handle>(data: *const u8, len: usize, out: *mut *mut H) -> RsbStatus {
+    guard(|| {
+        if data.is_null() || out.is_null() {
+            return RsbStatus::NullPointer;
+        }
+        match parse::<H>(slice::from_raw_parts(data, len)) {
+            Some(set) => write_handle(out, H::from_set(set)),
+            None => RsbStatus::InvalidData,
+        }
+    })
+}
+
+// The exported functions are written out, rather than generated by a macro, so that cbindgen
+// can see them.
+
+/// Creates an empty set and writes it to `*out`. Free it with [`rsb_u64_free`].
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_new(out: *mut *mut RangeSetU64) -> RsbStatus {
+    new(out)
+}
+
+/// Creates an empty set and writes it to `*out`. Free it with [`rsb_i64_free`].
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_new(out: *mut *mut RangeSetI64) -> RsbStatus {
+    new(out)
+}
+
+/// Copies `set` into a new set and writes it to `*out`.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_clone(
+    set: *const RangeSetU64,
+    out: *mut *mut RangeSetU64,
+) -> RsbStatus {
+    clone(set, out)
+}
+
+/// Copies `set` into a new set and writes it to `*out`.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_clone(
+    set: *const RangeSetI64,
+    out: *mut *mut RangeSetI64,
+) -> RsbStatus {
+    clone(set, out)
+}
+
+/// Frees a set. Null is ignored.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, which must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_free(set: *mut RangeSetU64) {
+    free(set);
+}
+
+/// Frees a set. Null is ignored.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, which must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_free(set: *mut RangeSetI64) {
+    free(set);
+}
+
+/// Adds `value` to the set. If `inserted` isn't null, writes whether the value was new.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `inserted` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_insert(
+    set: *mut RangeSetU64,
+    value: u64,
+    inserted: *mut bool,
+) -> RsbStatus {
+    insert(set, value, inserted)
+}
+
+/// Adds `value` to the set. If `inserted` isn't null, writes whether the value was new.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `inserted` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_insert(
+    set: *mut RangeSetI64,
+    value: i64,
+    inserted: *mut bool,
+) -> RsbStatus {
+    insert(set, value, inserted)
+}
+
+/// Adds the inclusive range `start..=end` to the set. If `inserted` isn't null, writes whether
+/// any value was new.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `inserted` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_ranges_insert(
+    set: *mut RangeSetU64,
+    start: u64,
+    end: u64,
+    inserted: *mut bool,
+) -> RsbStatus {
+    ranges_insert(set, start, end, inserted)
+}
+
+/// Adds the inclusive range `start..=end` to the set. If `inserted` isn't null, writes whether
+/// any value was new.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `inserted` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_ranges_insert(
+    set: *mut RangeSetI64,
+    start: i64,
+    end: i64,
+    inserted: *mut bool,
+) -> RsbStatus {
+    ranges_insert(set, start, end, inserted)
+}
+
+/// Removes `value` from the set. If `removed` isn't null, writes whether it was present.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `removed` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_remove(
+    set: *mut RangeSetU64,
+    value: u64,
+    removed: *mut bool,
+) -> RsbStatus {
+    remove(set, value, removed)
+}
+
+/// Removes `value` from the set. If `removed` isn't null, writes whether it was present.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `removed` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_remove(
+    set: *mut RangeSetI64,
+    value: i64,
+    removed: *mut bool,
+) -> RsbStatus {
+    remove(set, value, removed)
+}
+
+/// Removes the inclusive range `start..=end` from the set. If `removed` isn't null, writes
+/// whether any value was present.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `removed` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_ranges_remove(
+    set: *mut RangeSetU64,
+    start: u64,
+    end: u64,
+    removed: *mut bool,
+) -> RsbStatus {
+    ranges_remove(set, start, end, removed)
+}
+
+/// Removes the inclusive range `start..=end` from the set. If `removed` isn't null, writes
+/// whether any value was present.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `removed` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_ranges_remove(
+    set: *mut RangeSetI64,
+    start: i64,
+    end: i64,
+    removed: *mut bool,
+) -> RsbStatus {
+    ranges_remove(set, start, end, removed)
+}
+
+/// Writes whether the set contains `value` to `*contained`.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `contained` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_contains(
+    set: *const RangeSetU64,
+    value: u64,
+    contained: *mut bool,
+) -> RsbStatus {
+    contains(set, value, contained)
+}
+
+/// Writes whether the set contains `value` to `*contained`.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `contained` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_contains(
+    set: *const RangeSetI64,
+    value: i64,
+    contained: *mut bool,
+) -> RsbStatus {
+    contains(set, value, contained)
+}
+
+/// Writes the number of integers in the set to `*len`. Returns [`RsbStatus::Overflow`] for the full
+/// set, whose length, 2^64, doesn't fit.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_len(set: *const RangeSetU64, len: *mut u64) -> RsbStatus {
+    self::len(set, len)
+}
+
+/// Writes the number of integers in the set to `*len`. Returns [`RsbStatus::Overflow`] for the full
+/// set, whose length, 2^64, doesn't fit.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_len(set: *const RangeSetI64, len: *mut u64) -> RsbStatus {
+    self::len(set, len)
+}
+
+/// Writes the number of ranges in the set to `*ranges_len`.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `ranges_len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_ranges_len(
+    set: *const RangeSetU64,
+    ranges_len: *mut usize,
+) -> RsbStatus {
+    self::ranges_len(set, ranges_len)
+}
+
+/// Writes the number of ranges in the set to `*ranges_len`.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `ranges_len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_ranges_len(
+    set: *const RangeSetI64,
+    ranges_len: *mut usize,
+) -> RsbStatus {
+    self::ranges_len(set, ranges_len)
+}
+
+/// Copies the set's ranges, in order, into `buffer`, which holds `capacity` ranges. Always writes
+/// the number of ranges to `*ranges_len`. If `capacity` is too small, writes no ranges and returns
+/// [`RsbStatus::BufferTooSmall`], so a caller can pass a null `buffer` to get the length.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, `buffer` must be null or valid for writing `capacity`
+/// ranges, and `ranges_len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_ranges(
+    set: *const RangeSetU64,
+    buffer: *mut RsbRangeU64,
+    capacity: usize,
+    ranges_len: *mut usize,
+) -> RsbStatus {
+    ranges(set, buffer, capacity, ranges_len)
+}
+
+/// Copies the set's ranges, in order, into `buffer`, which holds `capacity` ranges. Always writes
+/// the number of ranges to `*ranges_len`. If `capacity` is too small, writes no ranges and returns
+/// [`RsbStatus::BufferTooSmall`], so a caller can pass a null `buffer` to get the length.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, `buffer` must be null or valid for writing `capacity`
+/// ranges, and `ranges_len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_ranges(
+    set: *const RangeSetI64,
+    buffer: *mut RsbRangeI64,
+    capacity: usize,
+    ranges_len: *mut usize,
+) -> RsbStatus {
+    ranges(set, buffer, capacity, ranges_len)
+}
+
+/// Writes the union of `a` and `b` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_union(
+    a: *const RangeSetU64,
+    b: *const RangeSetU64,
+    out: *mut *mut RangeSetU64,
+) -> RsbStatus {
+    binary(a, b, out, |a, b| a | b)
+}
+
+/// Writes the union of `a` and `b` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_union(
+    a: *const RangeSetI64,
+    b: *const RangeSetI64,
+    out: *mut *mut RangeSetI64,
+) -> RsbStatus {
+    binary(a, b, out, |a, b| a | b)
+}
+
+/// Writes the intersection of `a` and `b` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_intersection(
+    a: *const RangeSetU64,
+    b: *const RangeSetU64,
+    out: *mut *mut RangeSetU64,
+) -> RsbStatus {
+    binary(a, b, out, |a, b| a & b)
+}
+
+/// Writes the intersection of `a` and `b` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_intersection(
+    a: *const RangeSetI64,
+    b: *const RangeSetI64,
+    out: *mut *mut RangeSetI64,
+) -> RsbStatus {
+    binary(a, b, out, |a, b| a & b)
+}
+
+/// Writes the integers in `a` but not in `b` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_difference(
+    a: *const RangeSetU64,
+    b: *const RangeSetU64,
+    out: *mut *mut RangeSetU64,
+) -> RsbStatus {
+    binary(a, b, out, |a, b| a - b)
+}
+
+/// Writes the integers in `a` but not in `b` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_difference(
+    a: *const RangeSetI64,
+    b: *const RangeSetI64,
+    out: *mut *mut RangeSetI64,
+) -> RsbStatus {
+    binary(a, b, out, |a, b| a - b)
+}
+
+/// Writes the integers in exactly one of `a` and `b` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_symmetric_difference(
+    a: *const RangeSetU64,
+    b: *const RangeSetU64,
+    out: *mut *mut RangeSetU64,
+) -> RsbStatus {
+    binary(a, b, out, |a, b| a ^ b)
+}
+
+/// Writes the integers in exactly one of `a` and `b` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_symmetric_difference(
+    a: *const RangeSetI64,
+    b: *const RangeSetI64,
+    out: *mut *mut RangeSetI64,
+) -> RsbStatus {
+    binary(a, b, out, |a, b| a ^ b)
+}
+
+/// Writes the integers not in `set` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_complement(
+    set: *const RangeSetU64,
+    out: *mut *mut RangeSetU64,
+) -> RsbStatus {
+    complement(set, out)
+}
+
+/// Writes the integers not in `set` to `*out` as a new set.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_complement(
+    set: *const RangeSetI64,
+    out: *mut *mut RangeSetI64,
+) -> RsbStatus {
+    complement(set, out)
+}
+
+/// Writes whether `a` and `b` have the same integers to `*equal`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `equal` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_equal(
+    a: *const RangeSetU64,
+    b: *const RangeSetU64,
+    equal: *mut bool,
+) -> RsbStatus {
+    self::equal(a, b, equal)
+}
+
+/// Writes whether `a` and `b` have the same integers to `*equal`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or live handles, and `equal` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_equal(
+    a: *const RangeSetI64,
+    b: *const RangeSetI64,
+    equal: *mut bool,
+) -> RsbStatus {
+    self::equal(a, b, equal)
+}
+
+/// Serializes the set into `buffer`, which holds `capacity` bytes. Always writes the
+/// serialized length to `*len`. If `capacity` is too small, writes nothing else and returns
+/// [`RsbStatus::BufferTooSmall`], so a caller can pass a null `buffer` to get the length. See the
+/// module documentation for the format.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, `buffer` must be null or valid for writing `capacity`
+/// bytes, and `len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_serialize(
+    set: *const RangeSetU64,
+    buffer: *mut u8,
+    capacity: usize,
+    len: *mut usize,
+) -> RsbStatus {
+    serialize(set, buffer, capacity, len)
+}
+
+/// Serializes the set into `buffer`, which holds `capacity` bytes. Always writes the
+/// serialized length to `*len`. If `capacity` is too small, writes nothing else and returns
+/// [`RsbStatus::BufferTooSmall`], so a caller can pass a null `buffer` to get the length. See the
+/// module documentation for the format.
+///
+/// # Safety
+///
+/// `set` must be null or a live handle, `buffer` must be null or valid for writing `capacity`
+/// bytes, and `len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_serialize(
+    set: *const RangeSetI64,
+    buffer: *mut u8,
+    capacity: usize,
+    len: *mut usize,
+) -> RsbStatus {
+    serialize(set, buffer, capacity, len)
+}
+
+/// Creates a set from the `len` bytes written by [`rsb_u64_serialize`] and writes it to `*out`.
+/// Returns [`RsbStatus::InvalidData`] if the bytes are malformed, including if the ranges aren't
+/// sorted and disjoint.
+///
+/// # Safety
+///
+/// `data` must be null or valid for reading `len` bytes, and `out` must be null or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_u64_deserialize(
+    data: *const u8,
+    len: usize,
+    out: *mut *mut RangeSetU64,
+) -> RsbStatus {
+    deserialize(data, len, out)
+}
+
+/// Creates a set from the `len` bytes written by [`rsb_i64_serialize`] and writes it to `*out`.
+/// Returns [`RsbStatus::InvalidData`] if the bytes are malformed, including if the ranges aren't
+/// sorted and disjoint.
+///
+/// # Safety
+///
+/// `data` must be null or valid for reading `len` bytes, and `out` must be null or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn rsb_i64_deserialize(
+    data: *const u8,
+    len: usize,
+    out: *mut *mut RangeSetI64,
+) -> RsbStatus {
+    deserialize(data, len, out)
+}
@@ -0,0 +1,354 @@
+use core::{iter::FusedIterator, mem::size_of, ops::RangeInclusive, slice};
+
+use crate::Integer;
+
+// The number of bytes checked at once: one AVX-512 register, two AVX2 registers,
+// or four SSE2/NEON registers.
+const CHUNK_BYTES: usize = 64;
+
+// Returns whether the `CHUNK_BYTES` bytes at the pointer hold consecutive integers of one width,
+// that is, whether `chunk[i] == chunk[0] + i` (wrapping) for every lane `i`.
+//
+// Safety: the pointer must be valid for reading `CHUNK_BYTES` bytes (no alignment needed),
+// and the running CPU must support the instructions the kernel was compiled with.
+type Kernel = unsafe fn(*const u8) -> bool;
+
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+macro_rules! x86_detected {
+    ($feature:tt) => {
+        std::arch::is_x86_feature_detected!($feature)
+    };
+}
+
+#[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+macro_rules! x86_detected {
+    ($feature:tt) => {
+        cfg!(target_feature = $feature)
+    };
+}
+
+#[cfg(all(target_arch = "aarch64", feature = "std"))]
+macro_rules! aarch64_detected {
+    ($feature:tt) => {
+        std::arch::is_aarch64_feature_detected!($feature)
+    };
+}
+
+#[cfg(all(target_arch = "aarch64", not(feature = "std")))]
+macro_rules! aarch64_detected {
+    ($feature:tt) => {
+        cfg!(target_feature = $feature)
+    };
+}
+
+// Defines the kernels for one integer width. Signed types share the kernels of the unsigned
+// type of the same width because wrapping addition is the same for both.
+macro_rules! define_kernels {
+    (
+        $width:ident, $uint:ty,
+        x86: $sse2_set1:ident, $sse2_add:ident, $avx2_set1:ident, $avx2_add:ident,
+        $avx512_set1:ident, $avx512_add:ident, $avx512_features:literal, $avx512_extra:tt,
+        neon: $neon_dup:ident, $neon_load:ident, $neon_add:ident, $neon_eq:path, $neon_to_u8:path
+    ) => {
+        mod $width {
+            #[cfg(target_arch = "aarch64")]
+            use core::arch::aarch64::*;
+            #[cfg(target_arch = "x86_64")]
+            use core::arch::x86_64::*;
+
+            use super::{size_of, Kernel, CHUNK_BYTES};
+
+            const LANES: usize = CHUNK_BYTES / size_of::<$uint>();
+
+            // `0, 1, 2, ...`, the differences from the first lane of a consecutive chunk.
+            #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+            static OFFSETS: [$uint; LANES] = {
+                let mut offsets = [0; LANES];
+                let mut i = 0;
+                while i < LANES {
+                    offsets[i] = i as $uint;
+                    i += 1;
+                }
+                offsets
+            };
+
+            pub(super) unsafe fn scalar(chunk: *const u8) -> bool {
+                let chunk = chunk.cast::<$uint>();
+                let first = chunk.read_unaligned();
+                (1..LANES).all(|i| chunk.add(i).read_unaligned() == first.wrapping_add(i as $uint))
+            }
+
+            #[cfg(target_arch = "x86_64")]
+            #[target_feature(enable = "sse2")]
+            pub(super) unsafe fn sse2(chunk: *const u8) -> bool {
+                let first = $sse2_set1(chunk.cast::<$uint>().read_unaligned() as _);
+                let mut all_equal = _mm_set1_epi8(-1);
+                for i in 0..4 {
+                    let values = _mm_loadu_si128(chunk.add(16 * i).cast());
+                    let offsets = _mm_loadu_si128(OFFSETS.as_ptr().cast::<u8>().add(16 * i).cast());
+                    let equal = _mm_cmpeq_epi8(values, $sse2_add(first, offsets));
+                    all_equal = _mm_and_si128(all_equal, equal);
+                }
+                _mm_movemask_epi8(all_equal) == 0xFFFF
+            }
+
+            #[cfg(target_arch = "x86_64")]
+            #[target_feature(enable = "avx2")]
+            pub(super) unsafe fn avx2(chunk: *const u8) -> bool {
+                let first = $avx2_set1(chunk.cast::<$uint>().read_unaligned() as _);
+                let mut all_equal = _mm256_set1_epi8(-1);
+                for i in 0..2 {
+                    let values = _mm256_loadu_si256(chunk.add(32 * i).cast());
+                    let offsets =
+                        _mm256_loadu_si256(OFFSETS.as_ptr().cast::<u8>().add(32 * i).cast());
+                    let equal = _mm256_cmpeq_epi8(values, $avx2_add(first, offsets));
+                    all_equal = _mm256_and_si256(all_equal, equal);
+                }
+                _mm256_movemask_epi8(all_equal) == -1
+            }
+
+            #[cfg(target_arch = "x86_64")]
+            #[target_feature(enable = $avx512_features)]
+            pub(super) unsafe fn avx512(chunk: *const u8) -> bool {
+                let first = $avx512_set1(chunk.cast::<$uint>().read_unaligned() as _);
+                let values = _mm512_loadu_si512(chunk.cast());
+                let offsets = _mm512_loadu_si512(OFFSETS.as_ptr().cast());
+                _mm512_cmpeq_epi32_mask(values, $avx512_add(first, offsets)) == 0xFFFF
+            }
+
+            #[cfg(target_arch = "aarch64")]
+            #[target_feature(enable = "neon")]
+            pub(super) unsafe fn neon(chunk: *const u8) -> bool {
+                let first = $neon_dup(chunk.cast::<$uint>().read_unaligned());
+                let mut all_equal = vdupq_n_u8(u8::MAX);
+                for i in 0..4 {
+                    let values = $neon_load(chunk.add(16 * i).cast::<$uint>());
+                    let offsets = $neon_load(OFFSETS.as_ptr().add(LANES / 4 * i));
+                    let equal = $neon_eq(values, $neon_add(first, offsets));
+                    all_equal = vandq_u8(all_equal, $neon_to_u8(equal));
+                }
+                vminvq_u8(all_equal) == u8::MAX
+            }
+
+            // Picks the fastest kernel the running CPU supports. SSE2 is part of the x86_64
+            // baseline. Without `std`, only the features enabled at compile time are used.
+            #[allow(unreachable_code)]
+            pub(super) fn select() -> Kernel {
+                #[cfg(target_arch = "x86_64")]
+                {
+                    if x86_detected!("avx512f") && x86_detected!($avx512_extra) {
+                        return avx512;
+                    }
+                    if x86_detected!("avx2") {
+                        return avx2;
+                    }
+                    return sse2;
+                }
+                #[cfg(target_arch = "aarch64")]
+                {
+                    if aarch64_detected!("neon") {
+                        return neon;
+                    }
+                }
+                scalar
+            }
+
+            #[cfg(test)]
+            pub(super) fn kernels() -> alloc::vec::Vec<(&'static str, Kernel)> {
+                #[allow(unused_mut)]
+                let mut kernels: alloc::vec::Vec<(&'static str, Kernel)> =
+                    alloc::vec![("scalar", scalar)];
+                #[cfg(target_arch = "x86_64")]
+                {
+                    kernels.push(("sse2", sse2));
+                    if x86_detected!("avx2") {
+                        kernels.push(("avx2", avx2));
+                    }
+                    if x86_detected!("avx512f") && x86_detected!($avx512_extra) {
+                        kernels.push(("avx512", avx512));
+                    }
+                }
+                #[cfg(target_arch = "aarch64")]
+                if aarch64_detected!("neon") {
+                    kernels.push(("neon", neon));
+                }
+                kernels
+            }
+        }
+    };
+}
+
+define_kernels!(
+    width8, u8,
+    x86: _mm_set1_epi8, _mm_add_epi8, _mm256_set1_epi8, _mm256_add_epi8,
+    _mm512_set1_epi8, _mm512_add_epi8, "avx512f,avx512bw", "avx512bw",
+    neon: vdupq_n_u8, vld1q_u8, vaddq_u8, vceqq_u8, core::convert::identity
+);
+define_kernels!(
+    width16, u16,
+    x86: _mm_set1_epi16, _mm_add_epi16, _mm256_set1_epi16, _mm256_add_epi16,
+    _mm512_set1_epi16, _mm512_add_epi16, "avx512f,avx512bw", "avx512bw",
+    neon: vdupq_n_u16, vld1q_u16, vaddq_u16, vceqq_u16, vreinterpretq_u8_u16
+);
+define_kernels!(
+    width32, u32,
+    x86: _mm_set1_epi32, _mm_add_epi32, _mm256_set1_epi32, _mm256_add_epi32,
+    _mm512_set1_epi32, _mm512_add_epi32, "avx512f", "avx512f",
+    neon: vdupq_n_u32, vld1q_u32, vaddq_u32, vceqq_u32, vreinterpretq_u8_u32
+);
+define_kernels!(
+    width64, u64,
+    x86: _mm_set1_epi64x, _mm_add_epi64, _mm256_set1_epi64x, _mm256_add_epi64,
+    _mm512_set1_epi64, _mm512_add_epi64, "avx512f", "avx512f",
+    neon: vdupq_n_u64, vld1q_u64, vaddq_u64, vceqq_u64, vreinterpretq_u8_u64
+);
+
+/// The integer types whose slices [`FromSliceIter`] can scan for blocks of consecutive
+/// integers.
+pub(crate) trait IsConsecutive: Integer {
+    /// Returns the fastest kernel for this type's width that the running CPU supports.
+    fn kernel() -> Kernel;
+}
+
+macro_rules! impl_is_consecutive {
+    ($($type:ty => $width:ident),*) => {
+        $(
+            impl IsConsecutive for $type {
+                #[inline]
+                fn kernel() -> Kernel {
+                    $width::select()
+                }
+            }
+        )*
+    };
+}
+
+impl_is_consecutive!(
+    i8 => width8, u8 => width8,
+    i16 => width16, u16 => width16,
+    i32 => width32, u32 => width32,
+    i64 => width64, u64 => width64
+);
+#[cfg(target_pointer_width = "32")]
+impl_is_consecutive!(isize => width32, usize => width32);
+#[cfg(target_pointer_width = "64")]
+impl_is_consecutive!(isize => width64, usize => width64);
+
+/// Turns a slice of integers into ranges, checking one 64-byte chunk at a time with the
+/// kernel picked by [`IsConsecutive::kernel`]. A chunk of consecutive integers becomes one
+/// range (merged with an adjacent previous chunk); any other chunk becomes singletons.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub(crate) struct FromSliceIter<'a, T>
+where
+    T: IsConsecutive,
+{
+    singles: slice::Iter<'a, T>,
+    previous_range: Option<RangeInclusive<T>>,
+    chunks: slice::ChunksExact<'a, T>,
+    remainder: &'a [T],
+    kernel: Kernel,
+    slice_len: usize,
+}
+
+impl<'a, T> FromSliceIter<'a, T>
+where
+    T: IsConsecutive,
+{
+    pub(crate) fn new(slice: &'a [T]) -> Self {
+        let chunks = slice.chunks_exact(CHUNK_BYTES / size_of::<T>());
+        FromSliceIter {
+            singles: [].iter(),
+            previous_range: None,
+            remainder: chunks.remainder(),
+            chunks,
+            kernel: T::kernel(),
+            slice_len: slice.len(),
+        }
+    }
+}
+
+impl<T> FusedIterator for FromSliceIter<'_, T> where T: IsConsecutive {}
+
+impl<T> Iterator for FromSliceIter<'_, T>
+where
+    T: IsConsecutive,
+{
+    type Item = RangeInclusive<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        if let Some(single) = self.singles.next() {
+            return Some(*single..=*single);
+        }
+        for chunk in self.chunks.by_ref() {
+            let this_start = chunk[0];
+            let this_end = chunk[chunk.len() - 1];
+            // SAFETY: the chunk is `CHUNK_BYTES` long, and `T::kernel` only returns kernels
+            // that the running CPU supports. The second test rejects runs that wrap around.
+            if unsafe { (self.kernel)(chunk.as_ptr().cast()) } && this_start <= this_end {
+                match self.previous_range.as_mut() {
+                    Some(previous)
+                        if *previous.end() < T::max_value()
+                            && *previous.end() + T::one() == this_start =>
+                    {
+                        *previous = *previous.start()..=this_end;
+                    }
+                    Some(previous) => {
+                        let result = previous.clone();
+                        *previous = this_start..=this_end;
+                        return Some(result);
+                    }
+                    None => self.previous_range = Some(this_start..=this_end),
+                }
+            } else {
+                self.singles = chunk.iter();
+                if let Some(previous) = self.previous_range.take() {
+                    return Some(previous);
+                }
+                if let Some(single) = self.singles.next() {
+                    return Some(*single..=*single);
+                }
+            }
+        }
+
+        if let Some(previous) = self.previous_range.take() {
+            return Some(previous);
+        }
+        self.singles = self.remainder.iter();
+        self.remainder = &[];
+        self.singles.next().map(|single| *single..=*single)
+    }
+
+    // At most one range per integer.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.slice_len))
+    }
+}
+
+#[test]
+fn test_kernels() {
+    macro_rules! check_width {
+        ($width:ident, $uint:ty) => {{
+            const LANES: usize = CHUNK_BYTES / size_of::<$uint>();
+            for (name, kernel) in $width::kernels() {
+                for first in [0, 1, <$uint>::MAX / 2, <$uint>::MAX - 3, <$uint>::MAX] {
+                    let mut chunk: [$uint; LANES] =
+                        core::array::from_fn(|i| first.wrapping_add(i as $uint));
+                    // SAFETY: the chunk is `CHUNK_BYTES` long and the CPU supports the kernel.
+                    assert!(unsafe { kernel(chunk.as_ptr().cast()) }, "{name} {first}");
+                    for lane in [0, 1, LANES / 2, LANES - 1] {
+                        chunk[lane] = chunk[lane].wrapping_add(1);
+                        let consecutive = unsafe { kernel(chunk.as_ptr().cast()) };
+                        assert!(!consecutive, "{name} {first} {lane}");
+                        chunk[lane] = chunk[lane].wrapping_sub(1);
+                    }
+                }
+            }
+        }};
+    }
+    check_width!(width8, u8);
+    check_width!(width16, u16);
+    check_width!(width32, u32);
+    check_width!(width64, u64);
+}
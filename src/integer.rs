@@ -1,5 +1,9 @@
 #[cfg(feature = "from_slice")]
-use crate::{from_slice::FromSliceIter, RangeSetBlaze};
+use crate::from_slice::FromSliceIter;
+#[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+use crate::from_slice_stable::FromSliceIter as StableFromSliceIter;
+#[cfg(feature = "alloc")]
+use crate::RangeSetBlaze;
 use core::ops::RangeInclusive;
 
 #[cfg(feature = "from_slice")]
@@ -18,6 +22,11 @@ impl Integer for i8 {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
         r.end().overflowing_sub(*r.start()).0 as u8 as <Self as Integer>::SafeLen + 1
@@ -28,11 +37,13 @@ impl Integer for i8 {
     fn f64_to_safe_len(f: f64) -> Self::SafeLen {
         f as Self::SafeLen
     }
+    // `b - 1` may not fit in `Self` (e.g., 200 for i8) even when the result does,
+    // so the cast and the add both wrap.
     fn add_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a + (b - 1) as Self
+        a.wrapping_add((b - 1) as Self)
     }
     fn sub_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a - (b - 1) as Self
+        a.wrapping_sub((b - 1) as Self)
     }
 }
 
@@ -47,6 +58,11 @@ impl Integer for u8 {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
         r.end().overflowing_sub(*r.start()).0 as <Self as Integer>::SafeLen + 1
@@ -83,10 +99,10 @@ impl Integer for i32 {
         f as Self::SafeLen
     }
     fn add_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a + (b - 1) as Self
+        a.wrapping_add((b - 1) as Self)
     }
     fn sub_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a - (b - 1) as Self
+        a.wrapping_sub((b - 1) as Self)
     }
 
     #[cfg(feature = "from_slice")]
@@ -94,6 +110,11 @@ impl Integer for i32 {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 }
 
 impl Integer for u32 {
@@ -107,6 +128,11 @@ impl Integer for u32 {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
         r.end().overflowing_sub(*r.start()).0 as <Self as Integer>::SafeLen + 1
@@ -137,6 +163,11 @@ impl Integer for i64 {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
         r.end().overflowing_sub(*r.start()).0 as u64 as <Self as Integer>::SafeLen + 1
@@ -148,10 +179,10 @@ impl Integer for i64 {
         f as Self::SafeLen
     }
     fn add_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a + (b - 1) as Self
+        a.wrapping_add((b - 1) as Self)
     }
     fn sub_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a - (b - 1) as Self
+        a.wrapping_sub((b - 1) as Self)
     }
 }
 
@@ -166,6 +197,11 @@ impl Integer for u64 {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
         r.end().overflowing_sub(*r.start()).0 as <Self as Integer>::SafeLen + 1
@@ -190,10 +226,10 @@ impl Integer for i128 {
     #[cfg(target_pointer_width = "64")]
     type SafeLen = u128;
 
-    #[cfg(feature = "from_slice")]
+    #[cfg(feature = "alloc")]
     #[inline]
     fn from_slice(slice: impl AsRef<[Self]>) -> crate::RangeSetBlaze<Self> {
-        return slice.as_ref().iter().collect();
+        slice.as_ref().iter().collect()
     }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
@@ -210,10 +246,10 @@ impl Integer for i128 {
         f as Self::SafeLen
     }
     fn add_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a + (b - 1) as Self
+        a.wrapping_add((b - 1) as Self)
     }
     fn sub_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a - (b - 1) as Self
+        a.wrapping_sub((b - 1) as Self)
     }
 }
 
@@ -223,10 +259,10 @@ impl Integer for u128 {
     #[cfg(target_pointer_width = "64")]
     type SafeLen = u128;
 
-    #[cfg(feature = "from_slice")]
+    #[cfg(feature = "alloc")]
     #[inline]
     fn from_slice(slice: impl AsRef<[Self]>) -> crate::RangeSetBlaze<Self> {
-        return slice.as_ref().iter().collect();
+        slice.as_ref().iter().collect()
     }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
@@ -260,6 +296,11 @@ impl Integer for isize {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
         r.end().overflowing_sub(*r.start()).0 as usize as <Self as Integer>::SafeLen + 1
@@ -272,10 +313,10 @@ impl Integer for isize {
         f as Self::SafeLen
     }
     fn add_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a + (b - 1) as Self
+        a.wrapping_add((b - 1) as Self)
     }
     fn sub_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a - (b - 1) as Self
+        a.wrapping_sub((b - 1) as Self)
     }
 }
 
@@ -290,6 +331,11 @@ impl Integer for usize {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
         r.end().overflowing_sub(*r.start()).0 as <Self as Integer>::SafeLen + 1
@@ -320,6 +366,11 @@ impl Integer for i16 {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
         r.end().overflowing_sub(*r.start()).0 as u16 as <Self as Integer>::SafeLen + 1
@@ -332,10 +383,10 @@ impl Integer for i16 {
         f as Self::SafeLen
     }
     fn add_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a + (b - 1) as Self
+        a.wrapping_add((b - 1) as Self)
     }
     fn sub_len_less_one(a: Self, b: Self::SafeLen) -> Self {
-        a - (b - 1) as Self
+        a.wrapping_sub((b - 1) as Self)
     }
 }
 
@@ -350,6 +401,11 @@ impl Integer for u16 {
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
         FromSliceIter::<Self, LANES>::new(slice.as_ref()).collect()
     }
+    #[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+    #[inline]
+    fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self> {
+        StableFromSliceIter::new(slice.as_ref()).collect()
+    }
 
     fn safe_len(r: &RangeInclusive<Self>) -> <Self as Integer>::SafeLen {
         r.end().overflowing_sub(*r.start()).0 as <Self as Integer>::SafeLen + 1
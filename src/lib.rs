@@ -20,46 +20,127 @@
 // compile_error!("The 'std' feature is active");
 // #[cfg(feature = "alloc")]
 // compile_error!("The 'alloc' feature is active");
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 // FUTURE: Support serde via optional feature
+mod affine;
+#[cfg(feature = "alloc")]
+mod allocate;
+#[cfg(feature = "alloc")]
+mod convert;
+mod counting;
+#[cfg(feature = "alloc")]
+mod cursor;
+#[cfg(feature = "alloc")]
+mod drain;
+#[cfg(feature = "alloc")]
 mod dyn_sorted_disjoint;
+// Not in the lib unit tests: they also link tests_common, a dev-dependency that builds a
+// second copy of this crate with the same features, so every `#[no_mangle]` function would
+// be defined twice and linking would fail. The ffi tests are in tests/integration_test.rs.
+#[cfg(all(feature = "ffi", not(test)))]
+pub mod ffi;
+#[cfg(feature = "alloc")]
 mod from_slice;
+#[cfg(all(feature = "alloc", not(feature = "from_slice")))]
+mod from_slice_stable;
 mod integer;
+#[cfg(feature = "alloc")]
+mod membership;
 mod merge;
+#[cfg(feature = "alloc")]
+mod minkowski;
+mod morphology;
+#[cfg(feature = "alloc")]
+mod nearest;
 mod not_iter;
+#[cfg(feature = "alloc")]
+mod persistent;
 pub mod prelude;
+#[cfg(feature = "alloc")]
+mod range_allocator;
+#[cfg(feature = "alloc")]
+mod range_count_map;
+#[cfg(feature = "alloc")]
+mod range_set_blaze_2d;
+mod range_set_blaze_array;
+#[cfg(feature = "alloc")]
 mod ranges;
-#[cfg(feature = "rog-experimental")]
+#[cfg(feature = "alloc")]
 mod rog;
+mod similarity;
+#[cfg(feature = "alloc")]
+mod small_map;
 mod sorted_disjoint;
+mod sorted_integers;
+#[cfg(feature = "alloc")]
+mod summary;
 mod tests;
 mod union_iter;
 mod unsorted_disjoint;
-pub use crate::ranges::{IntoRangesIter, RangesIter};
-use alloc::{collections::BTreeMap, vec::Vec};
+pub use affine::{QuotientIter, ScaleIter, ShiftIter};
+#[cfg(feature = "alloc")]
+pub use crate::ranges::{ChangedRanges, IntoRangesIter, RangesIter};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
 use core::{
-    cmp::{max, Ordering},
+    cmp::{max, min, Ordering},
     convert::From,
-    fmt,
-    iter::FusedIterator,
-    ops::{BitOr, BitOrAssign, Bound, RangeBounds, RangeInclusive},
-    str::FromStr,
+    ops::{BitOr, BitOrAssign, Bound, RangeBounds},
 };
+use core::{fmt, iter::FusedIterator, ops::RangeInclusive, str::FromStr};
+#[cfg(feature = "alloc")]
+pub use convert::ConversionError;
+pub use counting::{SkipLenIter, TakeLenIter, WithRunningLenIter};
+#[cfg(feature = "alloc")]
+pub use cursor::{Cursor, CursorMut};
+#[cfg(feature = "alloc")]
+pub use drain::ExtractIfRanges;
+#[cfg(feature = "alloc")]
 pub use dyn_sorted_disjoint::DynSortedDisjoint;
+#[cfg(feature = "alloc")]
 use gen_ops::gen_ops_ex;
+#[cfg(feature = "alloc")]
 use itertools::Tee;
-pub use merge::{KMerge, Merge};
+#[cfg(feature = "alloc")]
+pub use membership::FilterMembers;
+#[cfg(feature = "alloc")]
+pub use merge::KMerge;
+pub use merge::Merge;
+pub use morphology::{CloseIter, DilateIter, ErodeIter, OpenIter};
 pub use not_iter::NotIter;
 use num_traits::{ops::overflowing::OverflowingSub, CheckedAdd, One, WrappingSub, Zero};
-#[cfg(feature = "rog-experimental")]
+#[cfg(feature = "alloc")]
+pub use persistent::{PersistentRangeSetBlaze, PersistentRangesIter};
+#[cfg(feature = "alloc")]
+pub use range_allocator::RangeAllocator;
+#[cfg(feature = "alloc")]
+pub use range_count_map::{RangeCountMap, RangesWithCountAtLeast, SegmentsIter};
+#[cfg(feature = "alloc")]
+pub use range_set_blaze_2d::{RangeSetBlaze2D, RectanglesIter, SlabsIter};
+pub use range_set_blaze_array::{ArrayRangesIter, CapacityError, RangeSetBlazeArray};
+#[cfg(feature = "alloc")]
 pub use rog::{Rog, RogsIter};
 pub use sorted_disjoint::{CheckSortedDisjoint, SortedDisjoint, SortedStarts};
+pub use sorted_integers::SortedIntegersIter;
+#[cfg(feature = "alloc")]
+pub use summary::RangeSetSummary;
 pub use union_iter::UnionIter;
 pub use unsorted_disjoint::AssumeSortedStarts;
+#[cfg(feature = "alloc")]
 use unsorted_disjoint::SortedDisjointWithLenSoFar;
+#[cfg(feature = "alloc")]
 use unsorted_disjoint::UnsortedDisjoint;
 
+// With the `small-set` feature, the ranges live in a `SmallMap`, which keeps a few ranges
+// inline and only spills into a `BTreeMap` when needed. Otherwise, they live in a `BTreeMap`.
+#[cfg(all(feature = "alloc", not(feature = "small-set")))]
+pub(crate) use alloc::collections::{btree_map as range_map, BTreeMap as RangeMap};
+#[cfg(all(feature = "alloc", feature = "small-set"))]
+pub(crate) use small_map::{self as range_map, SmallMap as RangeMap};
+
 /// The element trait of the [`RangeSetBlaze`] and [`SortedDisjoint`], specifically `u8` to `u128` (including `usize`) and `i8` to `i128` (including `isize`).
 pub trait Integer:
     num_integer::Integer
@@ -77,7 +158,7 @@ pub trait Integer:
     + CheckedAdd
     + WrappingSub
 {
-    #[cfg(feature = "from_slice")]
+    #[cfg(feature = "alloc")]
     /// A definition of [`RangeSetBlaze::from_slice()`] specific to this integer type.
     fn from_slice(slice: impl AsRef<[Self]>) -> RangeSetBlaze<Self>;
 
@@ -146,8 +227,6 @@ pub trait Integer:
         Self::max_value()
     }
 
-    // FUTURE define .len() SortedDisjoint
-
     /// Converts a `f64` to [`Integer::SafeLen`] using the formula `f as Self::SafeLen`. For large integer types, this will result in a loss of precision.
     fn f64_to_safe_len(f: f64) -> Self::SafeLen;
 
@@ -190,7 +269,7 @@ pub trait Integer:
 /// | [`new`]/[`default`]                         |                              |                          |
 /// | [`from_iter`][1]/[`collect`][1]             | integer iterator             |                          |
 /// | [`from_iter`][2]/[`collect`][2]             | ranges iterator              |                          |
-/// | [`from_slice`][5]                           | slice of integers            | Fast, but nightly-only  |
+/// | [`from_slice`][5]                           | slice of integers            | Fast                     |
 /// | [`from_sorted_disjoint`][3]/[`into_range_set_blaze`][3] | [`SortedDisjoint`] iterator |               |
 /// | [`from`][4] /[`into`][4]                    | array of integers            |                          |
 ///
@@ -233,9 +312,10 @@ pub trait Integer:
 /// a quadratic improvement an O(*n₁*) implementation that ignores the clumps.
 ///
 /// The [`from_slice`][5] constructor typically provides a constant-time speed up for array-like collections of clumpy integers.
-/// On a representative benchmark, the speed up was 7×.
+/// On the `ingest_clumps_iter_v_slice` benchmark (a million `u32`s), the stable SIMD path was 6–8× faster than `from_iter`
+/// when clumps averaged 1,000 or more integers, about 1.5× faster at 100, and slightly slower on unclumpy data.
 /// The method works by scanning the input for blocks of consecutive integers, and then using `from_iter` on the results.
-/// Where available, it uses SIMD instructions. It is nightly only and enabled by the `from_slice` feature.
+/// Where available, it uses SIMD instructions, picked at run time from the CPU's features.
 ///
 /// ## Constructor Examples
 ///
@@ -262,10 +342,8 @@ pub trait Integer:
 /// assert!(a0 == a1 && a0.to_string() == "-10..=-5, 1..=2");
 ///
 /// // 'from_slice': From any array-like collection of integers.
-/// // Nightly-only, but faster than 'from_iter'/'collect' on integers.
-/// #[cfg(feature = "from_slice")]
+/// // Faster than 'from_iter'/'collect' on integers.
 /// let a0 = RangeSetBlaze::from_slice(vec![3, 2, 1, 100, 1]);
-/// #[cfg(feature = "from_slice")]
 /// assert!(a0.to_string() == "1..=3, 100..=100");
 ///
 /// // If we know the ranges are already sorted and disjoint,
@@ -382,24 +460,28 @@ pub trait Integer:
 /// See the [module-level documentation] for additional examples.
 ///
 /// [module-level documentation]: index.html
+#[cfg(feature = "alloc")]
 pub struct RangeSetBlaze<T: Integer> {
     len: <T as Integer>::SafeLen,
-    btree_map: BTreeMap<T, T>,
+    btree_map: RangeMap<T, T>,
 }
 
 // FUTURE: Make all RangeSetBlaze iterators DoubleEndedIterator and ExactSizeIterator.
+#[cfg(feature = "alloc")]
 impl<T: Integer> fmt::Debug for RangeSetBlaze<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.ranges().to_string())
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> fmt::Display for RangeSetBlaze<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.ranges().to_string())
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> RangeSetBlaze<T> {
     /// Gets an (double-ended) iterator that visits the integer elements in the [`RangeSetBlaze`] in
     /// ascending and/or descending order.
@@ -435,11 +517,7 @@ impl<T: Integer> RangeSetBlaze<T> {
     pub fn iter(&self) -> Iter<T, RangesIter<T>> {
         // If the user asks for an iter, we give them a RangesIter iterator
         // and we iterate that one integer at a time.
-        Iter {
-            option_range_front: None,
-            option_range_back: None,
-            iter: self.ranges(),
-        }
+        Iter::new(self.ranges(), Some(self.len))
     }
 
     /// Returns the first element in the set, if any.
@@ -524,33 +602,33 @@ impl<T: Integer> RangeSetBlaze<T> {
         I: SortedDisjoint<T>,
     {
         let mut iter_with_len = SortedDisjointWithLenSoFar::from(iter);
-        let btree_map = BTreeMap::from_iter(&mut iter_with_len);
+        let btree_map = RangeMap::from_iter(&mut iter_with_len);
         RangeSetBlaze {
             btree_map,
             len: iter_with_len.len_so_far(),
         }
     }
 
-    /// Creates a [`RangeSetBlaze`] from a collection of integers. It is typically many
-    /// times faster than [`from_iter`][1]/[`collect`][1].
-    /// On a representative benchmark, the speed up was 7×.
+    /// Creates a [`RangeSetBlaze`] from a collection of integers. For clumpy integers, it is
+    /// typically many times faster than [`from_iter`][1]/[`collect`][1].
+    /// On the `ingest_clumps_iter_v_slice` benchmark (a million `u32`s), the stable SIMD path was 6–8× faster
+    /// when clumps averaged 1,000 or more integers, about 1.5× faster at 100, and slightly slower on unclumpy data.
     ///
-    /// **Warning: Requires the nightly compiler. Also, you must enable the `from_slice`
-    /// feature in your `Cargo.toml`. For example, with the command:**
-    /// ```bash
-    ///  cargo add range-set-blaze --features "from_slice"
-    /// ```
     /// The function accepts any type that can be referenced as a slice of integers,
     /// including slices, arrays, and vectors. Duplicates and out-of-order elements are fine.
     ///
-    /// Where available, this function leverages SIMD (Single Instruction, Multiple Data) instructions
-    /// for performance optimization. To enable SIMD optimizations, compile with the Rust compiler
-    /// (rustc) flag `-C target-cpu=native`. This instructs rustc to use the native instruction set
-    /// of the CPU on the machine compiling the code, potentially enabling more SIMD optimizations.
+    /// The function scans the slice 64 bytes at a time for blocks of consecutive integers.
+    /// On stable Rust, it checks each block with SIMD (Single Instruction, Multiple Data)
+    /// instructions picked at run time: AVX-512, AVX2, or SSE2 on x86_64 and NEON on aarch64,
+    /// with a scalar fallback elsewhere. Without the `std` feature, run-time detection isn't
+    /// available, so it uses the instructions enabled at compile time, for example, with the
+    /// Rust compiler (rustc) flag `-C target-cpu=native`.
     ///
-    /// **Caution**: Compiling with `-C target-cpu=native` optimizes the binary for your current CPU architecture,
-    /// which may lead to compatibility issues on other machines with different architectures.
-    /// This is particularly important for distributing the binary or running it in varied environments.
+    /// On the nightly compiler, you can instead enable the `from_slice` feature to use the
+    /// `portable_simd` implementation:
+    /// ```bash
+    ///  cargo add range-set-blaze --features "from_slice"
+    /// ```
     ///
     /// *For more about constructors and performance, see [`RangeSetBlaze` Constructors](struct.RangeSetBlaze.html#rangesetblaze-constructors).*
     ///
@@ -565,7 +643,6 @@ impl<T: Integer> RangeSetBlaze<T> {
     /// assert!(a0 == a1 && a1 == a2 && a0.to_string() == "1..=3, 100..=100");
     /// ```
     /// [1]: struct.RangeSetBlaze.html#impl-FromIterator<T>-for-RangeSetBlaze<T>
-    #[cfg(feature = "from_slice")]
     #[inline]
     pub fn from_slice(slice: impl AsRef<[T]>) -> Self {
         T::from_slice(slice)
@@ -741,7 +818,14 @@ impl<T: Integer> RangeSetBlaze<T> {
         self.ranges().is_disjoint(other.ranges())
     }
 
-    fn delete_extra(&mut self, internal_range: &RangeInclusive<T>) {
+    // `gap_start` is the first value of the range that was not already in the set, if any.
+    // When `added` is given, the parts of the range that were not already in the set are pushed to it.
+    fn delete_extra(
+        &mut self,
+        internal_range: &RangeInclusive<T>,
+        mut gap_start: Option<T>,
+        mut added: Option<&mut Vec<RangeInclusive<T>>>,
+    ) {
         let (start, end) = internal_range.clone().into_inner();
         let mut after = self.btree_map.range_mut(start..);
         let (start_after, end_after) = after.next().unwrap(); // there will always be a next
@@ -752,6 +836,12 @@ impl<T: Integer> RangeSetBlaze<T> {
             .map_while(|(start_delete, end_delete)| {
                 // must check this in two parts to avoid overflow
                 if *start_delete <= end || *start_delete <= end + T::one() {
+                    if let (Some(gap), Some(added)) = (gap_start, added.as_deref_mut()) {
+                        if gap < *start_delete {
+                            added.push(gap..=*start_delete - T::one());
+                        }
+                    }
+                    gap_start = (*end_delete < end).then(|| *end_delete + T::one());
                     end_new = max(end_new, *end_delete);
                     self.len -= T::safe_len(&(*start_delete..=*end_delete));
                     Some(*start_delete)
@@ -760,6 +850,9 @@ impl<T: Integer> RangeSetBlaze<T> {
                 }
             })
             .collect::<Vec<_>>();
+        if let (Some(gap), Some(added)) = (gap_start, added) {
+            added.push(gap..=end);
+        }
         if end_new > end {
             self.len += T::safe_len(&(end..=end_new - T::one()));
             *end_after = end_new;
@@ -884,6 +977,109 @@ impl<T: Integer> RangeSetBlaze<T> {
         self.len != len_before
     }
 
+    /// Adds a range to the set and returns the parts of it that were newly inserted,
+    /// as a [`SortedDisjoint`] iterator.
+    ///
+    /// It does the same work as [`ranges_insert`], plus collecting the new parts.
+    ///
+    /// [`ranges_insert`]: RangeSetBlaze::ranges_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// let added = set.ranges_insert_diff(0..=100);
+    /// assert_eq!(added.to_string(), "0..=9, 21..=29, 41..=100");
+    /// assert_eq!(set.to_string(), "0..=100");
+    ///
+    /// let added = set.ranges_insert_diff(50..=60);
+    /// assert_eq!(added.len(), 0);
+    /// ```
+    pub fn ranges_insert_diff(&mut self, range: RangeInclusive<T>) -> ChangedRanges<T> {
+        let mut added = Vec::new();
+        self.internal_add_diff(range, Some(&mut added));
+        ChangedRanges {
+            iter: added.into_iter(),
+        }
+    }
+
+    /// Removes a range from the set and returns the parts of it that were in the set,
+    /// as a [`SortedDisjoint`] iterator.
+    ///
+    /// # Performance
+    ///
+    /// Removing a range takes O(*k* log *n*) time, where *k* is the number of ranges it touches and
+    /// *n* is the number of ranges in `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range's end is greater than `T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// let removed = set.ranges_remove_diff(15..=35);
+    /// assert_eq!(removed.to_string(), "15..=20, 30..=35");
+    /// assert_eq!(set.to_string(), "10..=14, 36..=40");
+    ///
+    /// let removed = set.ranges_remove_diff(20..=30);
+    /// assert_eq!(removed.len(), 0);
+    /// ```
+    pub fn ranges_remove_diff(&mut self, range: RangeInclusive<T>) -> ChangedRanges<T> {
+        let mut removed = Vec::new();
+        self.internal_remove_diff(range, Some(&mut removed));
+        ChangedRanges {
+            iter: removed.into_iter(),
+        }
+    }
+
+    // Removes a range from the set. When `removed` is given, pushes the parts of the range that were in the set.
+    pub(crate) fn internal_remove_diff(
+        &mut self,
+        range: RangeInclusive<T>,
+        mut removed: Option<&mut Vec<RangeInclusive<T>>>,
+    ) {
+        let (start, end) = range.into_inner();
+        assert!(
+            end <= T::safe_max_value(),
+            "end must be <= T::safe_max_value()"
+        );
+        if start > end {
+            return;
+        }
+        let mut remove_piece = |len: &mut T::SafeLen, piece: RangeInclusive<T>| {
+            *len -= T::safe_len(&piece);
+            if let Some(removed) = removed.as_deref_mut() {
+                removed.push(piece);
+            }
+        };
+        // A range that starts before `start` may stick into (or past) the removed range.
+        if let Some((&start_before, end_before)) = self.btree_map.range_mut(..start).next_back() {
+            let end_before_old = *end_before;
+            if start <= end_before_old {
+                *end_before = start - T::one();
+                remove_piece(&mut self.len, start..=min(end, end_before_old));
+                if end < end_before_old {
+                    self.btree_map.insert(end + T::one(), end_before_old);
+                }
+            }
+            debug_assert!(start_before < start);
+        }
+        // Every range that starts inside is removed, and the last one may stick out past `end`.
+        while let Some((&start_after, &end_after)) = self.btree_map.range(start..=end).next() {
+            self.btree_map.remove(&start_after);
+            remove_piece(&mut self.len, start_after..=min(end, end_after));
+            if end < end_after {
+                self.btree_map.insert(end + T::one(), end_after);
+            }
+        }
+    }
+
     /// If the set contains an element equal to the value, removes it from the
     /// set and drops it. Returns whether such an element was present.
     ///
@@ -962,9 +1158,8 @@ impl<T: Integer> RangeSetBlaze<T> {
         );
         let old_len = self.len;
         let mut b = self.btree_map.split_off(&value);
-        if let Some(mut last_entry) = self.btree_map.last_entry() {
+        if let Some((_, end_ref)) = self.btree_map.iter_mut().next_back() {
             // Can assume start strictly less than value
-            let end_ref = last_entry.get_mut();
             if value <= *end_ref {
                 b.insert(value, *end_ref);
                 *end_ref = value - T::one();
@@ -986,7 +1181,7 @@ impl<T: Integer> RangeSetBlaze<T> {
         }
     }
 
-    fn btree_map_len(btree_map: &BTreeMap<T, T>) -> T::SafeLen {
+    fn btree_map_len(btree_map: &RangeMap<T, T>) -> T::SafeLen {
         btree_map
             .iter()
             .fold(<T as Integer>::SafeLen::zero(), |acc, (start, end)| {
@@ -1070,6 +1265,15 @@ impl<T: Integer> RangeSetBlaze<T> {
     // https://stackoverflow.com/questions/49599833/how-to-find-next-smaller-key-in-btreemap-btreeset
     // https://stackoverflow.com/questions/35663342/how-to-modify-partially-remove-a-range-from-a-btreemap
     fn internal_add(&mut self, range: RangeInclusive<T>) {
+        self.internal_add_diff(range, None);
+    }
+
+    // Like `internal_add`, but when `added` is given, pushes the parts of the range that were not already in the set.
+    fn internal_add_diff(
+        &mut self,
+        range: RangeInclusive<T>,
+        added: Option<&mut Vec<RangeInclusive<T>>>,
+    ) {
         let (start, end) = range.clone().into_inner();
         assert!(
             end <= T::safe_max_value(),
@@ -1086,25 +1290,30 @@ impl<T: Integer> RangeSetBlaze<T> {
                 Some(end_before_succ) => end_before_succ < start,
                 None => false,
             } {
-                self.internal_add2(&range);
+                self.internal_add2(&range, added);
             } else if *end_before < end {
+                let gap_start = *end_before + T::one();
                 self.len += T::safe_len(&(*end_before..=end - T::one()));
                 *end_before = end;
                 let start_before = *start_before;
-                self.delete_extra(&(start_before..=end));
+                self.delete_extra(&(start_before..=end), Some(gap_start), added);
             } else {
                 // completely contained, so do nothing
             }
         } else {
-            self.internal_add2(&range);
+            self.internal_add2(&range, added);
         }
     }
 
-    fn internal_add2(&mut self, internal_range: &RangeInclusive<T>) {
+    fn internal_add2(
+        &mut self,
+        internal_range: &RangeInclusive<T>,
+        added: Option<&mut Vec<RangeInclusive<T>>>,
+    ) {
         let (start, end) = internal_range.clone().into_inner();
         let was_there = self.btree_map.insert(start, end);
         debug_assert!(was_there.is_none()); // real assert
-        self.delete_extra(internal_range);
+        self.delete_extra(internal_range, Some(start), added);
         self.len += T::safe_len(internal_range);
     }
 
@@ -1149,7 +1358,7 @@ impl<T: Integer> RangeSetBlaze<T> {
     #[must_use]
     pub fn new() -> Self {
         RangeSetBlaze {
-            btree_map: BTreeMap::new(),
+            btree_map: RangeMap::new(),
             len: <T as Integer>::SafeLen::zero(),
         }
     }
@@ -1171,8 +1380,7 @@ impl<T: Integer> RangeSetBlaze<T> {
     /// assert!(set.is_empty());
     /// ```
     pub fn pop_first(&mut self) -> Option<T> {
-        if let Some(entry) = self.btree_map.first_entry() {
-            let (start, end) = entry.remove_entry();
+        if let Some((start, end)) = self.btree_map.pop_first() {
             self.len -= T::safe_len(&(start..=end));
             if start != end {
                 let start = start + T::one();
@@ -1202,16 +1410,14 @@ impl<T: Integer> RangeSetBlaze<T> {
     /// assert!(set.is_empty());
     /// ```
     pub fn pop_last(&mut self) -> Option<T> {
-        let Some(mut entry) = self.btree_map.last_entry() else {
+        let Some((&start, end)) = self.btree_map.iter_mut().next_back() else {
             return None;
         };
 
-        let start = *entry.key();
-        let end = entry.get_mut();
         let result = *end;
         self.len -= T::safe_len(&(start..=*end));
         if start == *end {
-            entry.remove_entry();
+            self.btree_map.pop_last();
         } else {
             *end -= T::one();
             self.len += T::safe_len(&(start..=*end));
@@ -1310,6 +1516,18 @@ impl<T: Integer> RangeSetBlaze<T> {
     /// In other words, remove all integers `e` for which `f(&e)` returns `false`.
     /// The integer elements are visited in ascending order.
     ///
+    /// # Performance
+    ///
+    /// This calls `f` once for every integer in the set, not once per range, so it takes
+    /// O([`len`]) time, which may be effectively forever. For example, on a set of `0..=u64::MAX
+    /// / 2`, it never finishes. If you can decide by range, use [`retain_ranges`] or
+    /// [`map_ranges_in_place`] instead. If the integers to keep form ranges, intersect with
+    /// them, for example `set & RangeSetBlaze::from_iter([10..=20])`.
+    ///
+    /// [`len`]: RangeSetBlaze::len
+    /// [`retain_ranges`]: RangeSetBlaze::retain_ranges
+    /// [`map_ranges_in_place`]: RangeSetBlaze::map_ranges_in_place
+    ///
     /// # Examples
     ///
     /// ```
@@ -1326,11 +1544,127 @@ impl<T: Integer> RangeSetBlaze<T> {
     {
         *self = self.iter().filter(|v| f(v)).collect();
     }
+
+    /// Retains only the ranges specified by the predicate.
+    ///
+    /// In other words, remove all ranges `r` for which `f(&r)` returns `false`.
+    /// The ranges are visited in ascending order.
+    ///
+    /// # Performance
+    ///
+    /// O(*n*), where *n* is the number of ranges. Unlike [`retain`], the time doesn't depend
+    /// on the number of integers in the set.
+    ///
+    /// [`retain`]: RangeSetBlaze::retain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([1..=1, 10..=20, 30..=u64::MAX / 2]);
+    /// // Keep only the ranges of at least 5 integers.
+    /// set.retain_ranges(|range| range.end() - range.start() >= 4);
+    /// assert_eq!(set, RangeSetBlaze::from_iter([10..=20, 30..=u64::MAX / 2]));
+    /// ```
+    pub fn retain_ranges<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&RangeInclusive<T>) -> bool,
+    {
+        let len = &mut self.len;
+        self.btree_map.retain(|start, end| {
+            let range = *start..=*end;
+            let keep = f(&range);
+            if !keep {
+                *len -= T::safe_len(&range);
+            }
+            keep
+        });
+    }
+
+    /// Replaces each range `r` with `f(r)`. If `f` returns `None` or an empty range, the range
+    /// is removed. The ranges are visited in ascending order.
+    ///
+    /// The new ranges may overlap, touch, or be out of order. Ranges that keep their start and
+    /// stay clear of the next range are updated in place; only the others are removed and
+    /// re-inserted, merging as needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a new range's end is greater than `T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *k* log *n*), where *n* is the number of ranges and *k* is the number of
+    /// ranges that must be re-inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let mut set = RangeSetBlaze::from_iter([0u32..=9, 20..=29, 40..=u32::MAX / 2]);
+    /// // Trim two integers off each end, dropping ranges that disappear.
+    /// set.map_ranges_in_place(|range| {
+    ///     let (start, end) = range.into_inner();
+    ///     (end - start >= 4).then(|| start + 2..=end - 2)
+    /// });
+    /// assert_eq!(set.to_string(), "2..=7, 22..=27, 42..=2147483645");
+    ///
+    /// // Grow each range by 15 on the right, which merges them.
+    /// set.map_ranges_in_place(|range| Some(*range.start()..=range.end() + 15));
+    /// assert_eq!(set.to_string(), "2..=2147483660");
+    /// ```
+    pub fn map_ranges_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(RangeInclusive<T>) -> Option<RangeInclusive<T>>,
+    {
+        let mut removed_starts = Vec::new();
+        let mut pending = Vec::new();
+        let mut iter = self.btree_map.iter_mut().peekable();
+        while let Some((&start, end)) = iter.next() {
+            let old = start..=*end;
+            let Some(new) = f(old.clone()) else {
+                removed_starts.push(start);
+                self.len -= T::safe_len(&old);
+                continue;
+            };
+            assert!(
+                *new.end() <= T::safe_max_value(),
+                "end must be <= T::safe_max_value()"
+            );
+            if new == old {
+                continue;
+            }
+            // Must check this in two parts to avoid overflow
+            let clear_of_next = match iter.peek() {
+                Some((&next_start, _)) => {
+                    *new.end() < next_start && *new.end() + T::one() < next_start
+                }
+                None => true,
+            };
+            self.len -= T::safe_len(&old);
+            if *new.start() == start && start <= *new.end() && clear_of_next {
+                *end = *new.end();
+                self.len += T::safe_len(&new);
+            } else {
+                removed_starts.push(start);
+                pending.push(new);
+            }
+        }
+        for start in removed_starts {
+            self.btree_map.remove(&start);
+        }
+        for range in pending {
+            self.internal_add(range);
+        }
+    }
 }
 
 // We create a RangeSetBlaze from an iterator of integers or integer ranges by
 // 1. turning them into a UnionIter (internally, it collects into intervals and sorts by start).
 // 2. Turning the SortedDisjoint into a BTreeMap.
+#[cfg(feature = "alloc")]
 impl<T: Integer> FromIterator<T> for RangeSetBlaze<T> {
     /// Create a [`RangeSetBlaze`] from an iterator of integers. Duplicates and out-of-order elements are fine.
     ///
@@ -1353,6 +1687,7 @@ impl<T: Integer> FromIterator<T> for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T: Integer> FromIterator<&'a T> for RangeSetBlaze<T> {
     /// Create a [`RangeSetBlaze`] from an iterator of integers references. Duplicates and out-of-order elements are fine.
     ///
@@ -1375,6 +1710,7 @@ impl<'a, T: Integer> FromIterator<&'a T> for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> FromIterator<RangeInclusive<T>> for RangeSetBlaze<T> {
     /// Create a [`RangeSetBlaze`] from an iterator of inclusive ranges, `start..=end`.
     /// Overlapping, out-of-order, and empty ranges are fine.
@@ -1401,6 +1737,7 @@ impl<T: Integer> FromIterator<RangeInclusive<T>> for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T: Integer + 'a> FromIterator<&'a RangeInclusive<T>> for RangeSetBlaze<T> {
     /// Create a [`RangeSetBlaze`] from an iterator of inclusive ranges, `start..=end`.
     /// Overlapping, out-of-order, and empty ranges are fine.
@@ -1426,6 +1763,7 @@ impl<'a, T: Integer + 'a> FromIterator<&'a RangeInclusive<T>> for RangeSetBlaze<
         RangeSetBlaze::from_sorted_disjoint(union_iter)
     }
 }
+#[cfg(feature = "alloc")]
 impl<T: Integer, const N: usize> From<[T; N]> for RangeSetBlaze<T> {
     /// For compatibility with [`BTreeSet`] you may create a [`RangeSetBlaze`] from an array of integers.
     ///
@@ -1442,11 +1780,6 @@ impl<T: Integer, const N: usize> From<[T; N]> for RangeSetBlaze<T> {
     /// let a1: RangeSetBlaze<i32> = [3, 2, 1, 100, 1].into();
     /// assert!(a0 == a1 && a0.to_string() == "1..=3, 100..=100")
     /// ```
-    #[cfg(not(feature = "from_slice"))]
-    fn from(arr: [T; N]) -> Self {
-        arr.into_iter().collect()
-    }
-    #[cfg(feature = "from_slice")]
     fn from(arr: [T; N]) -> Self {
         RangeSetBlaze::from_slice(arr)
     }
@@ -1454,25 +1787,31 @@ impl<T: Integer, const N: usize> From<[T; N]> for RangeSetBlaze<T> {
 
 #[doc(hidden)]
 pub type BitOrMerge<T, L, R> = UnionIter<T, Merge<T, L, R>>;
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
 pub type BitOrKMerge<T, I> = UnionIter<T, KMerge<T, I>>;
 #[doc(hidden)]
 pub type BitAndMerge<T, L, R> = NotIter<T, BitNandMerge<T, L, R>>;
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
 pub type BitAndKMerge<T, I> = NotIter<T, BitNandKMerge<T, I>>;
 #[doc(hidden)]
 pub type BitNandMerge<T, L, R> = BitOrMerge<T, NotIter<T, L>, NotIter<T, R>>;
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
 pub type BitNandKMerge<T, I> = BitOrKMerge<T, NotIter<T, I>>;
 #[doc(hidden)]
 pub type BitNorMerge<T, L, R> = NotIter<T, BitOrMerge<T, L, R>>;
 #[doc(hidden)]
 pub type BitSubMerge<T, L, R> = NotIter<T, BitOrMerge<T, NotIter<T, L>, R>>;
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
 pub type BitXOrTee<T, L, R> =
     BitOrMerge<T, BitSubMerge<T, Tee<L>, Tee<R>>, BitSubMerge<T, Tee<R>, Tee<L>>>;
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
 pub type BitXOr<T, L, R> = BitOrMerge<T, BitSubMerge<T, L, Tee<R>>, BitSubMerge<T, Tee<R>, L>>;
+#[cfg(feature = "alloc")]
 #[doc(hidden)]
 pub type BitEq<T, L, R> = BitOrMerge<
     T,
@@ -1480,6 +1819,7 @@ pub type BitEq<T, L, R> = BitOrMerge<
     NotIter<T, BitOrMerge<T, Tee<L>, Tee<R>>>,
 >;
 
+#[cfg(feature = "alloc")]
 impl<T, I> MultiwayRangeSetBlazeRef<T> for I
 where
     T: Integer,
@@ -1494,6 +1834,7 @@ where
 ///
 /// [`union`]: MultiwayRangeSetBlazeRef::union
 /// [`intersection`]: MultiwayRangeSetBlazeRef::intersection
+#[cfg(feature = "alloc")]
 pub trait MultiwayRangeSetBlazeRef<T: Integer>:
     IntoIterator<Item = RangeSetBlaze<T>> + Sized
 {
@@ -1558,6 +1899,7 @@ pub trait MultiwayRangeSetBlazeRef<T: Integer>:
             .into_range_set_blaze()
     }
 }
+#[cfg(feature = "alloc")]
 impl<'a, T, I> MultiwayRangeSetBlaze<'a, T> for I
 where
     T: Integer + 'a,
@@ -1571,6 +1913,7 @@ where
 ///
 /// [`union`]: MultiwayRangeSetBlaze::union
 /// [`intersection`]: MultiwayRangeSetBlaze::intersection
+#[cfg(feature = "alloc")]
 pub trait MultiwayRangeSetBlaze<'a, T: Integer + 'a>:
     IntoIterator<Item = &'a RangeSetBlaze<T>> + Sized
 {
@@ -1639,6 +1982,7 @@ pub trait MultiwayRangeSetBlaze<'a, T: Integer + 'a>:
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, II, I> MultiwaySortedDisjoint<T, I> for II
 where
     T: Integer,
@@ -1652,6 +1996,7 @@ where
 ///
 /// [`union`]: crate::MultiwaySortedDisjoint::union
 /// [`intersection`]: crate::MultiwaySortedDisjoint::intersection
+#[cfg(feature = "alloc")]
 pub trait MultiwaySortedDisjoint<T: Integer, I>: IntoIterator<Item = I> + Sized
 where
     I: SortedDisjoint<T>,
@@ -1716,6 +2061,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 gen_ops_ex!(
     <T>;
     types ref RangeSetBlaze<T>, ref RangeSetBlaze<T> => RangeSetBlaze<T>;
@@ -1779,6 +2125,7 @@ gen_ops_ex!(
     where T: Integer //Where clause for all impl's
 );
 
+#[cfg(feature = "alloc")]
 gen_ops_ex!(
     <T>;
     types ref RangeSetBlaze<T> => RangeSetBlaze<T>;
@@ -1805,6 +2152,7 @@ gen_ops_ex!(
     where T: Integer //Where clause for all impl's
 );
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> IntoIterator for RangeSetBlaze<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
@@ -1830,16 +2178,30 @@ impl<T: Integer> IntoIterator for RangeSetBlaze<T> {
             option_range_front: None,
             option_range_back: None,
             into_iter: self.btree_map.into_iter(),
+            len: self.len,
         }
     }
 }
 
-/// A (double-ended) iterator over the integer elements of a [`RangeSetBlaze`].
+// The size hint for a known number of elements, which may not fit in a usize.
+fn exact_size_hint<L: num_traits::NumCast>(len: L) -> (usize, Option<usize>) {
+    <usize as num_traits::NumCast>::from(len).map_or((usize::MAX, None), |len| (len, Some(len)))
+}
+
+/// An iterator over the integer elements of a [`RangeSetBlaze`] or of any [`SortedDisjoint`]
+/// iterator. It is double-ended when the ranges are.
 ///
-/// This `struct` is created by the [`iter`] method on [`RangeSetBlaze`]. See its
-/// documentation for more.
+/// This `struct` is created by the [`iter`] method on [`RangeSetBlaze`] and by the [`elements`]
+/// method on [`SortedDisjoint`]. See their documentation for more.
+///
+/// [`nth`] and [`nth_back`] skip whole ranges at a time. When the number of elements is known
+/// up front, as it is for [`RangeSetBlaze::iter`], [`size_hint`] is exact.
 ///
 /// [`iter`]: RangeSetBlaze::iter
+/// [`elements`]: SortedDisjoint::elements
+/// [`nth`]: Iterator::nth
+/// [`nth_back`]: DoubleEndedIterator::nth_back
+/// [`size_hint`]: Iterator::size_hint
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 #[derive(Clone, Debug)]
 pub struct Iter<T, I>
@@ -1850,6 +2212,95 @@ where
     iter: I,
     option_range_front: Option<RangeInclusive<T>>,
     option_range_back: Option<RangeInclusive<T>>,
+    // The number of elements left, if known.
+    len: Option<<T as Integer>::SafeLen>,
+}
+
+impl<T, I> Iter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, len: Option<<T as Integer>::SafeLen>) -> Self {
+        Iter {
+            iter,
+            option_range_front: None,
+            option_range_back: None,
+            len,
+        }
+    }
+
+    #[inline]
+    fn consumed(&mut self, count: <T as Integer>::SafeLen) {
+        if let Some(len) = &mut self.len {
+            *len -= count;
+        }
+    }
+
+    // Skips `n` elements from the front and returns the next one.
+    fn nth_from_front(&mut self, n: <T as Integer>::SafeLen) -> Option<T> {
+        let mut n = n;
+        loop {
+            let Some(range) = self
+                .option_range_front
+                .take()
+                .or_else(|| self.iter.next())
+                .or_else(|| self.option_range_back.take())
+            else {
+                self.len = Some(<T as Integer>::SafeLen::zero());
+                return None;
+            };
+            let len = T::safe_len(&range);
+            if n >= len {
+                self.consumed(len);
+                n -= len;
+                continue;
+            }
+            // n < len, so n + 1 can't overflow
+            let n_plus_one = n + <T as Integer>::SafeLen::one();
+            let (start, end) = range.into_inner();
+            let value = T::add_len_less_one(start, n_plus_one);
+            if value < end {
+                self.option_range_front = Some(value + T::one()..=end);
+            }
+            self.consumed(n_plus_one);
+            return Some(value);
+        }
+    }
+
+    // Skips `n` elements from the back and returns the next one.
+    fn nth_from_back(&mut self, n: <T as Integer>::SafeLen) -> Option<T>
+    where
+        I: DoubleEndedIterator,
+    {
+        let mut n = n;
+        loop {
+            let Some(range) = self
+                .option_range_back
+                .take()
+                .or_else(|| self.iter.next_back())
+                .or_else(|| self.option_range_front.take())
+            else {
+                self.len = Some(<T as Integer>::SafeLen::zero());
+                return None;
+            };
+            let len = T::safe_len(&range);
+            if n >= len {
+                self.consumed(len);
+                n -= len;
+                continue;
+            }
+            // n < len, so n + 1 can't overflow
+            let n_plus_one = n + <T as Integer>::SafeLen::one();
+            let (start, end) = range.into_inner();
+            let value = T::sub_len_less_one(end, n_plus_one);
+            if start < value {
+                self.option_range_back = Some(start..=value - T::one());
+            }
+            self.consumed(n_plus_one);
+            return Some(value);
+        }
+    }
 }
 
 impl<T: Integer, I> FusedIterator for Iter<T, I> where I: SortedDisjoint<T> + FusedIterator {}
@@ -1871,14 +2322,26 @@ where
         if start < end {
             self.option_range_front = Some(start + T::one()..=end);
         }
+        self.consumed(<T as Integer>::SafeLen::one());
         Some(start)
     }
 
-    // We'll have at least as many integers as intervals. There could be more that usize MAX
-    // The option_range field could increase the number of integers, but we can ignore that.
+    fn nth(&mut self, n: usize) -> Option<T> {
+        let n = <<T as Integer>::SafeLen as num_traits::NumCast>::from(n)
+            .expect("real assert: usize fits in SafeLen");
+        self.nth_from_front(n)
+    }
+
+    // When the length isn't known, we'll have at least as many integers as intervals.
+    // There could be more than usize MAX.
     fn size_hint(&self) -> (usize, Option<usize>) {
+        if let Some(len) = self.len {
+            return exact_size_hint(len);
+        }
         let (low, _high) = self.iter.size_hint();
-        (low, None)
+        let partial = usize::from(self.option_range_front.is_some())
+            + usize::from(self.option_range_back.is_some());
+        (low.saturating_add(partial), None)
     }
 }
 
@@ -1897,9 +2360,15 @@ where
         if start < end {
             self.option_range_back = Some(start..=end - T::one());
         }
-
+        self.consumed(<T as Integer>::SafeLen::one());
         Some(end)
     }
+
+    fn nth_back(&mut self, n: usize) -> Option<T> {
+        let n = <<T as Integer>::SafeLen as num_traits::NumCast>::from(n)
+            .expect("real assert: usize fits in SafeLen");
+        self.nth_from_back(n)
+    }
 }
 
 #[must_use = "iterators are lazy and do nothing unless consumed"]
@@ -1910,14 +2379,19 @@ where
 /// documentation for more.
 ///
 /// [`into_iter`]: RangeSetBlaze::into_iter
+#[cfg(feature = "alloc")]
 pub struct IntoIter<T: Integer> {
     option_range_front: Option<RangeInclusive<T>>,
     option_range_back: Option<RangeInclusive<T>>,
-    into_iter: alloc::collections::btree_map::IntoIter<T, T>,
+    into_iter: range_map::IntoIter<T, T>,
+    // The number of elements left.
+    len: <T as Integer>::SafeLen,
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> FusedIterator for IntoIter<T> {}
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> Iterator for IntoIter<T> {
     type Item = T;
 
@@ -1933,17 +2407,16 @@ impl<T: Integer> Iterator for IntoIter<T> {
         if start < end {
             self.option_range_front = Some(start + T::one()..=end);
         }
+        self.len -= <T as Integer>::SafeLen::one();
         Some(start)
     }
 
-    // We'll have at least as many integers as intervals. There could be more that usize MAX
-    // the option_range field could increase the number of integers, but we can ignore that.
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (low, _high) = self.into_iter.size_hint();
-        (low, None)
+        exact_size_hint(self.len)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> DoubleEndedIterator for IntoIter<T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         let range = self
@@ -1957,11 +2430,12 @@ impl<T: Integer> DoubleEndedIterator for IntoIter<T> {
         if start < end {
             self.option_range_back = Some(start..=end - T::one());
         }
-
+        self.len -= <T as Integer>::SafeLen::one();
         Some(end)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> Extend<T> for RangeSetBlaze<T> {
     /// Extends the [`RangeSetBlaze`] with the contents of an Integer iterator.
     ///
@@ -1995,6 +2469,7 @@ impl<T: Integer> Extend<T> for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> BitOrAssign<&RangeSetBlaze<T>> for RangeSetBlaze<T> {
     /// Adds the contents of another [`RangeSetBlaze`] to this one.
     ///
@@ -2028,6 +2503,7 @@ impl<T: Integer> BitOrAssign<&RangeSetBlaze<T>> for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> BitOrAssign<RangeSetBlaze<T>> for RangeSetBlaze<T> {
     /// Adds the contents of another [`RangeSetBlaze`] to this one.
     ///
@@ -2059,6 +2535,7 @@ impl<T: Integer> BitOrAssign<RangeSetBlaze<T>> for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> BitOr<RangeSetBlaze<T>> for RangeSetBlaze<T> {
     /// Unions the contents of two [`RangeSetBlaze`]'s.
     ///
@@ -2080,6 +2557,7 @@ impl<T: Integer> BitOr<RangeSetBlaze<T>> for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> BitOr<&RangeSetBlaze<T>> for RangeSetBlaze<T> {
     /// Unions the contents of two [`RangeSetBlaze`]'s.
     ///
@@ -2101,6 +2579,7 @@ impl<T: Integer> BitOr<&RangeSetBlaze<T>> for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> BitOr<RangeSetBlaze<T>> for &RangeSetBlaze<T> {
     type Output = RangeSetBlaze<T>;
     /// Unions the contents of two [`RangeSetBlaze`]'s.
@@ -2122,6 +2601,7 @@ impl<T: Integer> BitOr<RangeSetBlaze<T>> for &RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> BitOr<&RangeSetBlaze<T>> for &RangeSetBlaze<T> {
     type Output = RangeSetBlaze<T>;
     /// Unions the contents of two [`RangeSetBlaze`]'s.
@@ -2142,6 +2622,7 @@ impl<T: Integer> BitOr<&RangeSetBlaze<T>> for &RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> Extend<RangeInclusive<T>> for RangeSetBlaze<T> {
     /// Extends the [`RangeSetBlaze`] with the contents of a
     /// range iterator.
@@ -2176,6 +2657,7 @@ impl<T: Integer> Extend<RangeInclusive<T>> for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> Ord for RangeSetBlaze<T> {
     /// We define a total ordering on RangeSetBlaze. Following the convention of
     /// [`BTreeSet`], the ordering is lexicographic, *not* by subset/superset.
@@ -2241,6 +2723,7 @@ impl<T: Integer> Ord for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> PartialOrd for RangeSetBlaze<T> {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -2248,6 +2731,7 @@ impl<T: Integer> PartialOrd for RangeSetBlaze<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> Eq for RangeSetBlaze<T> {}
 
 // If the iterator inside a BitOrIter is SortedStart, the output will be SortedDisjoint
@@ -2257,7 +2741,9 @@ impl<T: Integer, I: SortedStarts<T>> SortedDisjoint<T> for UnionIter<T, I> {}
 impl<T: Integer, I: SortedDisjoint<T>> SortedStarts<T> for NotIter<T, I> {}
 impl<T: Integer, I: SortedDisjoint<T>> SortedDisjoint<T> for NotIter<T, I> {}
 // If the iterator inside Tee is SortedDisjoint, the output will be SortedDisjoint
+#[cfg(feature = "alloc")]
 impl<T: Integer, I: SortedDisjoint<T>> SortedStarts<T> for Tee<I> {}
+#[cfg(feature = "alloc")]
 impl<T: Integer, I: SortedDisjoint<T>> SortedDisjoint<T> for Tee<I> {}
 
 #[cfg(feature = "std")]
@@ -0,0 +1,209 @@
+use core::{iter::FusedIterator, ops::RangeInclusive};
+
+use alloc::vec::Vec;
+
+use crate::{Integer, RangeSetBlaze, RangesIter};
+
+// Returns the number of items in `sorted` that are `<= probe`. The loop's only branch is on
+// the length, so its speed doesn't depend on how predictable the probes are.
+fn count_at_or_below<T: Ord>(sorted: &[T], probe: &T) -> usize {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let mut base = 0;
+    let mut size = sorted.len();
+    while size > 1 {
+        let half = size / 2;
+        let mid = base + half;
+        base = if sorted[mid] <= *probe { mid } else { base };
+        size -= half;
+    }
+    base + usize::from(sorted[base] <= *probe)
+}
+
+// Advances `ranges` to the first range whose end is at least `probe` and reports whether it
+// contains `probe`. `probe` must be at least every probe already given to `current`.
+fn walk_contains<T: Integer>(
+    ranges: &mut RangesIter<'_, T>,
+    current: &mut Option<RangeInclusive<T>>,
+    probe: T,
+) -> bool {
+    while let Some(range) = current {
+        if probe <= *range.end() {
+            return *range.start() <= probe;
+        }
+        *current = ranges.next();
+    }
+    false
+}
+
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Returns, for each probe, whether it is in the set. This is faster than calling
+    /// [`contains`] once per probe.
+    ///
+    /// If the probes are sorted (duplicates are fine), the answers come from one merge walk
+    /// over the ranges. Otherwise, the starts and ends of the ranges are copied into two flat
+    /// vectors that are searched with a branch-free binary search. When there are far fewer
+    /// probes than ranges, it calls [`contains`] for each probe instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a probe is greater than `T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// For sorted probes, O(*n* + *m*), where *n* is the number of ranges and *m* is the number
+    /// of probes. Otherwise, O(*n* + *m* log *n*), or O(*m* log *n*) when *m* is much smaller
+    /// than *n*.
+    ///
+    /// [`contains`]: RangeSetBlaze::contains
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([1u8..=3, 10..=12]);
+    /// assert_eq!(set.contains_many([0, 2, 3, 10, 13]), [false, true, true, true, false]);
+    /// assert_eq!(set.contains_many(&[12, 1, 5]), [true, true, false]);
+    /// ```
+    #[must_use]
+    pub fn contains_many(&self, probes: impl AsRef<[T]>) -> Vec<bool> {
+        let probes = probes.as_ref();
+        assert!(
+            probes.iter().all(|probe| *probe <= T::safe_max_value()),
+            "value must be <= T::safe_max_value()"
+        );
+        if probes.windows(2).all(|pair| pair[0] <= pair[1]) {
+            let mut ranges = self.ranges();
+            let mut current = ranges.next();
+            return probes
+                .iter()
+                .map(|&probe| walk_contains(&mut ranges, &mut current, probe))
+                .collect();
+        }
+        if probes.len().saturating_mul(16) < self.ranges_len() {
+            return probes.iter().map(|&probe| self.contains(probe)).collect();
+        }
+        let (starts, ends): (Vec<T>, Vec<T>) = self
+            .ranges()
+            .map(|range| (*range.start(), *range.end()))
+            .unzip();
+        probes
+            .iter()
+            .map(|probe| {
+                let count = count_at_or_below(&starts, probe);
+                count > 0 && *probe <= ends[count - 1]
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the items of `iter` that are in the set, in the order given.
+    ///
+    /// While the items are non-decreasing, membership comes from a merge walk over the
+    /// ranges. An item smaller than an earlier one falls back to [`contains`], and the walk
+    /// resumes with the next item that is at least as large as every earlier one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an item is greater than `T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// For sorted items, O(*n* + *m*), where *n* is the number of ranges and *m* is the
+    /// number of items. Each out-of-order item costs O(log *n*).
+    ///
+    /// [`contains`]: RangeSetBlaze::contains
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([1u8..=3, 10..=12]);
+    /// let members: Vec<u8> = set.filter_members([0, 2, 3, 11, 13, 1]).collect();
+    /// assert_eq!(members, [2, 3, 11, 1]);
+    /// ```
+    pub fn filter_members<I>(&self, iter: I) -> FilterMembers<'_, T, I::IntoIter>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        FilterMembers::new(self, iter.into_iter())
+    }
+}
+
+/// An iterator over the items of an iterator that are in a [`RangeSetBlaze`].
+///
+/// This `struct` is created by the [`filter_members`] method on [`RangeSetBlaze`]. See
+/// [`filter_members`]'s documentation for more.
+///
+/// [`filter_members`]: RangeSetBlaze::filter_members
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct FilterMembers<'a, T, I>
+where
+    T: Integer,
+    I: Iterator<Item = T>,
+{
+    set: &'a RangeSetBlaze<T>,
+    ranges: RangesIter<'a, T>,
+    current: Option<RangeInclusive<T>>,
+    high_water: Option<T>,
+    iter: I,
+}
+
+impl<'a, T, I> FilterMembers<'a, T, I>
+where
+    T: Integer,
+    I: Iterator<Item = T>,
+{
+    fn new(set: &'a RangeSetBlaze<T>, iter: I) -> Self {
+        let mut ranges = set.ranges();
+        let current = ranges.next();
+        FilterMembers {
+            set,
+            ranges,
+            current,
+            high_water: None,
+            iter,
+        }
+    }
+}
+
+impl<T, I> FusedIterator for FilterMembers<'_, T, I>
+where
+    T: Integer,
+    I: Iterator<Item = T> + FusedIterator,
+{
+}
+
+impl<T, I> Iterator for FilterMembers<'_, T, I>
+where
+    T: Integer,
+    I: Iterator<Item = T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        for item in self.iter.by_ref() {
+            assert!(
+                item <= T::safe_max_value(),
+                "value must be <= T::safe_max_value()"
+            );
+            let is_member = if self.high_water.is_some_and(|high_water| item < high_water) {
+                self.set.contains(item)
+            } else {
+                self.high_water = Some(item);
+                walk_contains(&mut self.ranges, &mut self.current, item)
+            };
+            if is_member {
+                return Some(item);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
@@ -1,6 +1,8 @@
 use core::{iter::FusedIterator, ops::RangeInclusive};
 
-use itertools::{Itertools, KMergeBy, MergeBy};
+#[cfg(feature = "alloc")]
+use itertools::KMergeBy;
+use itertools::{Itertools, MergeBy};
 
 use crate::{Integer, SortedDisjoint, SortedStarts};
 
@@ -115,6 +117,7 @@ where
 /// let union = [a, b, c].union();
 /// assert_eq!(union.to_string(), "-1..=-1, 1..=100");
 /// ```
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug)]
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct KMerge<T, I>
@@ -126,6 +129,7 @@ where
     iter: KMergeBy<I, fn(&RangeInclusive<T>, &RangeInclusive<T>) -> bool>,
 }
 
+#[cfg(feature = "alloc")]
 impl<T, I> KMerge<T, I>
 where
     T: Integer,
@@ -142,6 +146,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, I> FusedIterator for KMerge<T, I>
 where
     T: Integer,
@@ -149,6 +154,7 @@ where
 {
 }
 
+#[cfg(feature = "alloc")]
 impl<T, I> Iterator for KMerge<T, I>
 where
     T: Integer,
@@ -165,6 +171,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, I> SortedStarts<T> for KMerge<T, I>
 where
     T: Integer,
@@ -0,0 +1,111 @@
+use core::ops::RangeInclusive;
+
+use alloc::vec::Vec;
+
+use crate::{affine::saturating_add, CheckSortedDisjoint, Integer, RangeSetBlaze, UnionIter};
+
+// Computes `value - delta`. If the result is outside `T::min_value()..=T::safe_max_value()`,
+// returns `Err(true)` when it is below the domain and `Err(false)` when it is above it.
+fn checked_sub<T: Integer>(value: T, delta: T) -> Result<T, bool> {
+    let (difference, overflowed) = value.overflowing_sub(&delta);
+    if overflowed || difference > T::safe_max_value() {
+        // Subtracting a positive number can only go down, and vice versa.
+        Err(delta > T::zero())
+    } else {
+        Ok(difference)
+    }
+}
+
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Returns the Minkowski sum of two sets, that is, the set of every `a + b` where `a` is in
+    /// `self` and `b` is in `other`. Sums saturate at `T::min_value()` and `T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// The sum is computed one pair of ranges at a time, so the cost is O(*nm* log *nm*), where
+    /// *n* and *m* are the number of ranges in the two sets. It doesn't depend on the number of
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([0i8..=2, 10..=10]);
+    /// let b = RangeSetBlaze::from_iter([0i8..=1, 100..=100]);
+    /// assert_eq!(a.minkowski_sum(&b).to_string(), "0..=3, 10..=11, 100..=102, 110..=110");
+    ///
+    /// // Sums saturate.
+    /// let c = RangeSetBlaze::from_iter([100i8..=110]);
+    /// let d = RangeSetBlaze::from_iter([20i8..=30]);
+    /// assert_eq!(c.minkowski_sum(&d).to_string(), "120..=127");
+    /// ```
+    #[must_use]
+    pub fn minkowski_sum(&self, other: &RangeSetBlaze<T>) -> RangeSetBlaze<T> {
+        let ranges: Vec<RangeInclusive<T>> = self
+            .ranges()
+            .flat_map(|a| {
+                other.ranges().map(move |b| {
+                    saturating_add(*a.start(), *b.start())..=saturating_add(*a.end(), *b.end())
+                })
+            })
+            .collect();
+        RangeSetBlaze::from_sorted_disjoint(UnionIter::from_iter(ranges))
+    }
+
+    /// Returns the Minkowski difference of two sets, that is, the set of every `x` such that
+    /// `x + b` is in `self` for every `b` in `other`. This is the morphological definition, so,
+    /// for example, if `self` is a set of free time slots and `other` is a task's duration
+    /// `0..=d-1`, the result is the set of feasible start times.
+    ///
+    /// Every `x` in the result is in `T::min_value()..=T::safe_max_value()`, and no
+    /// intermediate value overflows. If `other` is empty, every `x` qualifies, so the result
+    /// is the whole domain.
+    ///
+    /// # Performance
+    ///
+    /// O(*nm*), where *n* and *m* are the number of ranges in the two sets. It doesn't depend
+    /// on the number of elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let free = RangeSetBlaze::from_iter([0u8..=9, 20..=22, 40..=49]);
+    /// let duration = RangeSetBlaze::from_iter([0u8..=3]);
+    /// assert_eq!(free.minkowski_difference(&duration).to_string(), "0..=6, 40..=46");
+    ///
+    /// // Every `x` with both `x` and `x + 20` in `free`.
+    /// let pair = RangeSetBlaze::from_iter([0u8..=0, 20..=20]);
+    /// assert_eq!(free.minkowski_difference(&pair).to_string(), "0..=2, 20..=22");
+    /// ```
+    #[must_use]
+    pub fn minkowski_difference(&self, other: &RangeSetBlaze<T>) -> RangeSetBlaze<T> {
+        let mut result = RangeSetBlaze::from_iter([T::min_value()..=T::safe_max_value()]);
+        for b in other.ranges() {
+            // The `x` with `x + b.start()..=x + b.end()` inside some range `a` of `self`.
+            // They keep the order and the gaps of `self`'s ranges, so they are sorted & disjoint.
+            let eroded = self.ranges().filter_map(|a| {
+                let start = match checked_sub(*a.start(), *b.start()) {
+                    Ok(start) => start,
+                    Err(true) => T::min_value(),
+                    Err(false) => return None,
+                };
+                let end = match checked_sub(*a.end(), *b.end()) {
+                    Ok(end) => end,
+                    Err(true) => return None,
+                    Err(false) => T::safe_max_value(),
+                };
+                (start <= end).then_some(start..=end)
+            });
+            result = RangeSetBlaze::from_sorted_disjoint(
+                result.ranges() & CheckSortedDisjoint::new(eroded),
+            );
+            if result.is_empty() {
+                break;
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,359 @@
+use core::ops::RangeInclusive;
+
+use num_traits::One;
+
+#[cfg(feature = "alloc")]
+use crate::RangeSetBlaze;
+use crate::{sorted_disjoint::impl_sorted_disjoint_and_ops, Integer, SortedDisjoint};
+
+// Computes `a - k`, saturating at `T::min_value()`.
+fn saturating_sub_len<T: Integer>(a: T, k: <T as Integer>::SafeLen) -> T {
+    let room = T::safe_len(&(T::min_value()..=a)) - <T as Integer>::SafeLen::one();
+    if k >= room {
+        T::min_value()
+    } else {
+        // k < room, so k + 1 can't overflow
+        T::sub_len_less_one(a, k + <T as Integer>::SafeLen::one())
+    }
+}
+
+// Computes `a + k`, saturating at `T::safe_max_value()`.
+fn saturating_add_len<T: Integer>(a: T, k: <T as Integer>::SafeLen) -> T {
+    let room = T::safe_len(&(a..=T::safe_max_value())) - <T as Integer>::SafeLen::one();
+    if k >= room {
+        T::safe_max_value()
+    } else {
+        // k < room, so k + 1 can't overflow
+        T::add_len_less_one(a, k + <T as Integer>::SafeLen::one())
+    }
+}
+
+/// A [`SortedDisjoint`] iterator that pads each range by a radius, merging ranges that come to
+/// overlap or touch.
+///
+/// This `struct` is created by the [`dilate`] method on [`SortedDisjoint`]. See [`dilate`]'s
+/// documentation for more.
+///
+/// [`dilate`]: SortedDisjoint::dilate
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct DilateIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    k: <T as Integer>::SafeLen,
+    pending: Option<RangeInclusive<T>>,
+}
+
+impl<T, I> DilateIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, k: <T as Integer>::SafeLen) -> Self {
+        DilateIter {
+            iter,
+            k,
+            pending: None,
+        }
+    }
+}
+
+impl<T, I> Iterator for DilateIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        loop {
+            let Some(range) = self.iter.next() else {
+                return self.pending.take();
+            };
+            let (start, end) = range.into_inner();
+            let (start, end) = (
+                saturating_sub_len(start, self.k),
+                saturating_add_len(end, self.k),
+            );
+            let Some(pending) = self.pending.take() else {
+                self.pending = Some(start..=end);
+                continue;
+            };
+            let (pending_start, pending_end) = pending.into_inner();
+            // The starts stay sorted, so only the pending range can overlap or touch.
+            if pending_end == T::safe_max_value() || start <= pending_end + T::one() {
+                self.pending = Some(pending_start..=pending_end.max(end));
+            } else {
+                self.pending = Some(start..=end);
+                return Some(pending_start..=pending_end);
+            }
+        }
+    }
+
+    // We could merge all the ranges into one.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        let pending = usize::from(self.pending.is_some());
+        let low = if low + pending > 0 { 1 } else { 0 };
+        let high = high.and_then(|high| high.checked_add(pending));
+        (low, high)
+    }
+}
+
+/// A [`SortedDisjoint`] iterator that shrinks each range by a radius at both ends, dropping
+/// ranges that vanish.
+///
+/// This `struct` is created by the [`erode`] method on [`SortedDisjoint`]. See [`erode`]'s
+/// documentation for more.
+///
+/// [`erode`]: SortedDisjoint::erode
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ErodeIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    k: <T as Integer>::SafeLen,
+}
+
+impl<T, I> ErodeIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, k: <T as Integer>::SafeLen) -> Self {
+        ErodeIter { iter, k }
+    }
+}
+
+impl<T, I> Iterator for ErodeIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        for range in self.iter.by_ref() {
+            let len = T::safe_len(&range);
+            // Keep the range if len > 2k, written so that nothing overflows.
+            if self.k < len && len - self.k > self.k {
+                let (start, end) = range.into_inner();
+                let k_plus_one = self.k + <T as Integer>::SafeLen::one();
+                return Some(
+                    T::add_len_less_one(start, k_plus_one)..=T::sub_len_less_one(end, k_plus_one),
+                );
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// A [`SortedDisjoint`] iterator that fills the gaps between ranges that are no longer than a
+/// given length.
+///
+/// This `struct` is created by the [`close`] method on [`SortedDisjoint`]. See [`close`]'s
+/// documentation for more.
+///
+/// [`close`]: SortedDisjoint::close
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct CloseIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    k: <T as Integer>::SafeLen,
+    pending: Option<RangeInclusive<T>>,
+}
+
+impl<T, I> CloseIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, k: <T as Integer>::SafeLen) -> Self {
+        CloseIter {
+            iter,
+            k,
+            pending: None,
+        }
+    }
+}
+
+impl<T, I> Iterator for CloseIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        loop {
+            let Some(range) = self.iter.next() else {
+                return self.pending.take();
+            };
+            let Some(pending) = self.pending.take() else {
+                self.pending = Some(range);
+                continue;
+            };
+            let (pending_start, pending_end) = pending.into_inner();
+            let (start, end) = range.into_inner();
+            // The gap is pending_end+1..=start-1. Its length is the length of
+            // pending_end..=start less two, which can't overflow.
+            let two = <T as Integer>::SafeLen::one() + <T as Integer>::SafeLen::one();
+            if T::safe_len(&(pending_end..=start)) - two <= self.k {
+                self.pending = Some(pending_start..=end);
+            } else {
+                self.pending = Some(start..=end);
+                return Some(pending_start..=pending_end);
+            }
+        }
+    }
+
+    // We could merge all the ranges into one.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        let pending = usize::from(self.pending.is_some());
+        let low = if low + pending > 0 { 1 } else { 0 };
+        let high = high.and_then(|high| high.checked_add(pending));
+        (low, high)
+    }
+}
+
+/// A [`SortedDisjoint`] iterator that removes the ranges shorter than a given length.
+///
+/// This `struct` is created by the [`open`] method on [`SortedDisjoint`]. See [`open`]'s
+/// documentation for more.
+///
+/// [`open`]: SortedDisjoint::open
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct OpenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    iter: I,
+    k: <T as Integer>::SafeLen,
+}
+
+impl<T, I> OpenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    pub(crate) fn new(iter: I, k: <T as Integer>::SafeLen) -> Self {
+        OpenIter { iter, k }
+    }
+}
+
+impl<T, I> Iterator for OpenIter<T, I>
+where
+    T: Integer,
+    I: SortedDisjoint<T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        let k = self.k;
+        self.iter.find(|range| T::safe_len(range) >= k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl_sorted_disjoint_and_ops!(DilateIter, ErodeIter, CloseIter, OpenIter);
+
+#[cfg(feature = "alloc")]
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Returns a new set with each range padded by `k` at both ends. Ranges that come to overlap
+    /// or touch are merged. The padding saturates at `T::min_value()` and `T::safe_max_value()`.
+    ///
+    /// See [`SortedDisjoint::dilate`] for a lazy version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=2, 10..=20, 250..=250]);
+    /// assert_eq!(a.dilate(3).to_string(), "0..=5, 7..=23, 247..=253");
+    /// assert_eq!(a.dilate(4).to_string(), "0..=24, 246..=254");
+    /// assert_eq!(a.dilate(255).to_string(), "0..=255");
+    /// ```
+    pub fn dilate(&self, k: <T as Integer>::SafeLen) -> Self {
+        RangeSetBlaze::from_sorted_disjoint(self.ranges().dilate(k))
+    }
+
+    /// Returns a new set with each range shrunk by `k` at both ends. Ranges with `2 * k` or
+    /// fewer elements are removed.
+    ///
+    /// The ends of the domain are treated like any other value, so a range that starts at
+    /// `T::min_value()` is shrunk at its start, too. This means that `erode` doesn't undo
+    /// [`dilate`] when `dilate` saturates.
+    ///
+    /// See [`SortedDisjoint::erode`] for a lazy version.
+    ///
+    /// [`dilate`]: RangeSetBlaze::dilate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([0u8..=5, 10..=20, 250..=255]);
+    /// assert_eq!(a.erode(2).to_string(), "2..=3, 12..=18, 252..=253");
+    /// assert_eq!(a.erode(3).to_string(), "13..=17");
+    /// ```
+    pub fn erode(&self, k: <T as Integer>::SafeLen) -> Self {
+        RangeSetBlaze::from_sorted_disjoint(self.ranges().erode(k))
+    }
+
+    /// Returns a new set with each gap of `k` or fewer elements between two ranges filled in.
+    /// The gaps before the first range and after the last range are never filled.
+    ///
+    /// See [`SortedDisjoint::close`] for a lazy version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=2, 5..=6, 10..=20]);
+    /// assert_eq!(a.close(2).to_string(), "1..=6, 10..=20");
+    /// assert_eq!(a.close(3).to_string(), "1..=20");
+    /// ```
+    pub fn close(&self, k: <T as Integer>::SafeLen) -> Self {
+        RangeSetBlaze::from_sorted_disjoint(self.ranges().close(k))
+    }
+
+    /// Returns a new set without the ranges that have fewer than `k` elements.
+    ///
+    /// See [`SortedDisjoint::open`] for a lazy version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=2, 5..=7, 10..=20]);
+    /// assert_eq!(a.open(3).to_string(), "5..=7, 10..=20");
+    /// assert_eq!(a.open(4).to_string(), "10..=20");
+    /// ```
+    pub fn open(&self, k: <T as Integer>::SafeLen) -> Self {
+        RangeSetBlaze::from_sorted_disjoint(self.ranges().open(k))
+    }
+}
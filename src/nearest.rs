@@ -0,0 +1,236 @@
+use core::ops::{Bound, RangeInclusive};
+
+use num_traits::One;
+
+use crate::{Integer, RangeSetBlaze};
+
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Returns the smallest element in the set that is greater than or equal to `value`, if any.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// assert_eq!(set.next_at_or_after(15), Some(15));
+    /// assert_eq!(set.next_at_or_after(25), Some(30));
+    /// assert_eq!(set.next_at_or_after(45), None);
+    /// ```
+    #[must_use]
+    pub fn next_at_or_after(&self, value: T) -> Option<T> {
+        self.next_range_at_or_after(value)
+            .map(|range| value.max(*range.start()))
+    }
+
+    /// Returns the largest element in the set that is less than or equal to `value`, if any.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// assert_eq!(set.prev_at_or_before(15), Some(15));
+    /// assert_eq!(set.prev_at_or_before(25), Some(20));
+    /// assert_eq!(set.prev_at_or_before(5), None);
+    /// ```
+    #[must_use]
+    pub fn prev_at_or_before(&self, value: T) -> Option<T> {
+        self.prev_range_at_or_before(value)
+            .map(|range| value.min(*range.end()))
+    }
+
+    /// Returns the element in the set that is closest to `value`, if any. On a tie, returns the
+    /// smaller element.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// assert_eq!(set.nearest(15), Some(15));
+    /// assert_eq!(set.nearest(24), Some(20));
+    /// assert_eq!(set.nearest(25), Some(20));
+    /// assert_eq!(set.nearest(26), Some(30));
+    /// assert_eq!(set.nearest(i32::MIN), Some(10));
+    /// assert_eq!(RangeSetBlaze::<i32>::new().nearest(0), None);
+    /// ```
+    #[must_use]
+    pub fn nearest(&self, value: T) -> Option<T> {
+        self.nearest_range(value)
+            .map(|range| value.clamp(*range.start(), *range.end()))
+    }
+
+    /// Returns the distance from `value` to the closest element in the set, or `None` if the
+    /// set is empty. The distance is zero if the set contains `value`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10u8..=20, 30..=40]);
+    /// assert_eq!(set.distance_to(15), Some(0));
+    /// assert_eq!(set.distance_to(24), Some(4));
+    /// assert_eq!(set.distance_to(255), Some(215));
+    /// assert_eq!(RangeSetBlaze::<u8>::new().distance_to(0), None);
+    /// ```
+    #[must_use]
+    pub fn distance_to(&self, value: T) -> Option<<T as Integer>::SafeLen> {
+        let nearest = self.nearest(value)?;
+        let (a, b) = if nearest <= value {
+            (nearest, value)
+        } else {
+            (value, nearest)
+        };
+        Some(T::safe_len(&(a..=b)) - <T as Integer>::SafeLen::one())
+    }
+
+    /// Returns the range in the set that contains `value` or, if there is none, the first range
+    /// after `value`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// assert_eq!(set.next_range_at_or_after(15), Some(10..=20));
+    /// assert_eq!(set.next_range_at_or_after(25), Some(30..=40));
+    /// assert_eq!(set.next_range_at_or_after(45), None);
+    /// ```
+    #[must_use]
+    pub fn next_range_at_or_after(&self, value: T) -> Option<RangeInclusive<T>> {
+        if let Some(range) = self.prev_range_at_or_before(value) {
+            if value <= *range.end() {
+                return Some(range);
+            }
+        }
+        self.btree_map
+            .range((Bound::Excluded(value), Bound::Unbounded))
+            .next()
+            .map(|(start, end)| *start..=*end)
+    }
+
+    /// Returns the range in the set that contains `value` or, if there is none, the last range
+    /// before `value`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// assert_eq!(set.prev_range_at_or_before(15), Some(10..=20));
+    /// assert_eq!(set.prev_range_at_or_before(25), Some(10..=20));
+    /// assert_eq!(set.prev_range_at_or_before(5), None);
+    /// ```
+    #[must_use]
+    pub fn prev_range_at_or_before(&self, value: T) -> Option<RangeInclusive<T>> {
+        assert!(
+            value <= T::safe_max_value(),
+            "value must be <= T::safe_max_value()"
+        );
+        self.btree_map
+            .range(..=value)
+            .next_back()
+            .map(|(start, end)| *start..=*end)
+    }
+
+    /// Returns the range in the set that contains `value` or, if there is none, the range
+    /// closest to `value`. On a tie, returns the lower range.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of ranges in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let set = RangeSetBlaze::from_iter([10..=20, 30..=40]);
+    /// assert_eq!(set.nearest_range(15), Some(10..=20));
+    /// assert_eq!(set.nearest_range(25), Some(10..=20));
+    /// assert_eq!(set.nearest_range(26), Some(30..=40));
+    /// ```
+    #[must_use]
+    pub fn nearest_range(&self, value: T) -> Option<RangeInclusive<T>> {
+        let before = self.prev_range_at_or_before(value);
+        if let Some(before) = &before {
+            if value <= *before.end() {
+                return Some(before.clone());
+            }
+        }
+        let after = self
+            .btree_map
+            .range((Bound::Excluded(value), Bound::Unbounded))
+            .next()
+            .map(|(start, end)| *start..=*end);
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                // safe_len, unlike subtraction, can't overflow
+                if T::safe_len(&(*before.end()..=value)) <= T::safe_len(&(value..=*after.start())) {
+                    Some(before)
+                } else {
+                    Some(after)
+                }
+            }
+            (before, after) => before.or(after),
+        }
+    }
+}
@@ -3,7 +3,9 @@ use core::{
     ops::{self, RangeInclusive},
 };
 
-use crate::{BitAndMerge, BitOrMerge, BitSubMerge, BitXOrTee, Integer, SortedDisjoint};
+#[cfg(feature = "alloc")]
+use crate::BitXOrTee;
+use crate::{BitAndMerge, BitOrMerge, BitSubMerge, Integer, SortedDisjoint};
 
 /// Turns a [`SortedDisjoint`] iterator into a [`SortedDisjoint`] iterator of its complement,
 /// i.e., all the integers not in the original iterator, as sorted & disjoint ranges.
@@ -150,6 +152,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer, R, L> ops::BitXor<R> for NotIter<T, L>
 where
     L: SortedDisjoint<T>,
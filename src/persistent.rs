@@ -0,0 +1,1049 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    cmp::{max, Ordering},
+    fmt,
+    iter::FusedIterator,
+    ops::{self, RangeInclusive},
+};
+
+use gen_ops::gen_ops_ex;
+use num_traits::Zero;
+
+use crate::{
+    BitAndMerge, BitOrMerge, BitSubMerge, BitXOrTee, ChangedRanges, Integer, Iter, NotIter,
+    SortedDisjoint, SortedStarts, UnionIter,
+};
+
+// The ranges live in an AVL tree keyed by `start`. Nodes are shared between versions through `Arc`.
+// Every change goes through `Arc::make_mut`, so it copies just the nodes on the path to the change
+// and only when another version still holds them.
+type Link<T> = Option<Arc<Node<T>>>;
+
+#[derive(Clone)]
+struct Node<T: Integer> {
+    start: T,
+    end: T,
+    height: u8,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T: Integer> Node<T> {
+    fn fix_height(&mut self) {
+        self.height = 1 + max(height(&self.left), height(&self.right));
+    }
+}
+
+fn height<T: Integer>(link: &Link<T>) -> u8 {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+fn rotate_right<T: Integer>(link: &mut Link<T>) {
+    let mut node_arc = link.take().expect("real assert: rotate needs a node");
+    let node = Arc::make_mut(&mut node_arc);
+    let mut left_arc = node.left.take().expect("real assert: rotate needs a child");
+    let left = Arc::make_mut(&mut left_arc);
+    node.left = left.right.take();
+    node.fix_height();
+    left.right = Some(node_arc);
+    left.fix_height();
+    *link = Some(left_arc);
+}
+
+fn rotate_left<T: Integer>(link: &mut Link<T>) {
+    let mut node_arc = link.take().expect("real assert: rotate needs a node");
+    let node = Arc::make_mut(&mut node_arc);
+    let mut right_arc = node
+        .right
+        .take()
+        .expect("real assert: rotate needs a child");
+    let right = Arc::make_mut(&mut right_arc);
+    node.right = right.left.take();
+    node.fix_height();
+    right.left = Some(node_arc);
+    right.fix_height();
+    *link = Some(right_arc);
+}
+
+fn rebalance<T: Integer>(link: &mut Link<T>) {
+    let Some(node_arc) = link.as_mut() else {
+        return;
+    };
+    let node = Arc::make_mut(node_arc);
+    node.fix_height();
+    let (left_height, right_height) = (height(&node.left), height(&node.right));
+    if left_height > right_height + 1 {
+        let left = node.left.as_ref().expect("real assert: left is taller");
+        if height(&left.right) > height(&left.left) {
+            rotate_left(&mut node.left);
+        }
+        rotate_right(link);
+    } else if right_height > left_height + 1 {
+        let right = node.right.as_ref().expect("real assert: right is taller");
+        if height(&right.left) > height(&right.right) {
+            rotate_right(&mut node.right);
+        }
+        rotate_left(link);
+    }
+}
+
+// Inserts a range whose start is not yet in the tree.
+fn insert<T: Integer>(link: &mut Link<T>, start: T, end: T) {
+    let Some(node_arc) = link.as_mut() else {
+        *link = Some(Arc::new(Node {
+            start,
+            end,
+            height: 1,
+            left: None,
+            right: None,
+        }));
+        return;
+    };
+    let node = Arc::make_mut(node_arc);
+    match start.cmp(&node.start) {
+        Ordering::Less => insert(&mut node.left, start, end),
+        Ordering::Greater => insert(&mut node.right, start, end),
+        Ordering::Equal => unreachable!("real assert: start must not be in the tree"),
+    }
+    rebalance(link);
+}
+
+// Removes the range with the given start, which must be in the tree, and returns its end.
+fn remove<T: Integer>(link: &mut Link<T>, start: T) -> T {
+    let node = Arc::make_mut(
+        link.as_mut()
+            .expect("real assert: start must be in the tree"),
+    );
+    let end = match start.cmp(&node.start) {
+        Ordering::Less => remove(&mut node.left, start),
+        Ordering::Greater => remove(&mut node.right, start),
+        Ordering::Equal => {
+            let end = node.end;
+            if node.left.is_none() {
+                *link = node.right.take();
+                return end;
+            }
+            if node.right.is_none() {
+                *link = node.left.take();
+                return end;
+            }
+            (node.start, node.end) = remove_first(&mut node.right);
+            end
+        }
+    };
+    rebalance(link);
+    end
+}
+
+fn remove_first<T: Integer>(link: &mut Link<T>) -> (T, T) {
+    let node = Arc::make_mut(link.as_mut().expect("real assert: tree must not be empty"));
+    if node.left.is_none() {
+        let first = (node.start, node.end);
+        *link = node.right.take();
+        return first;
+    }
+    let first = remove_first(&mut node.left);
+    rebalance(link);
+    first
+}
+
+// Builds a balanced tree from sorted & disjoint ranges.
+fn build<T: Integer>(ranges: &[(T, T)]) -> Link<T> {
+    if ranges.is_empty() {
+        return None;
+    }
+    let mid = ranges.len() / 2;
+    let mut node = Node {
+        start: ranges[mid].0,
+        end: ranges[mid].1,
+        height: 1,
+        left: build(&ranges[..mid]),
+        right: build(&ranges[mid + 1..]),
+    };
+    node.fix_height();
+    Some(Arc::new(node))
+}
+
+/// A persistent (immutable) set of integers stored as sorted & disjoint ranges.
+///
+/// Unlike [`RangeSetBlaze`], whose `clone` copies every range, cloning a `PersistentRangeSetBlaze`
+/// is O(1). The ranges live in a balanced tree whose nodes are shared between versions.
+/// Methods such as [`insert`], [`ranges_insert`] and [`remove`] copy only the O(log *n*) nodes on the path
+/// to the change, so each version shares its unchanged subtrees with the versions it came from.
+///
+/// Set operators (`|`, `&`, `-`, `^`) start from a clone of the left-hand input and apply only the needed changes,
+/// so the result shares unchanged subtrees with that input. Complement (`!`) builds a new tree.
+///
+/// [`diff`] compares two versions. It skips any subtree the two versions share, so comparing a version
+/// with a close relative is cheap.
+///
+/// [`RangeSetBlaze`]: crate::RangeSetBlaze
+/// [`insert`]: PersistentRangeSetBlaze::insert
+/// [`ranges_insert`]: PersistentRangeSetBlaze::ranges_insert
+/// [`remove`]: PersistentRangeSetBlaze::remove
+/// [`diff`]: PersistentRangeSetBlaze::diff
+///
+/// # Examples
+///
+/// ```
+/// use range_set_blaze::prelude::*;
+/// use range_set_blaze::PersistentRangeSetBlaze;
+///
+/// let v0 = PersistentRangeSetBlaze::from_iter([1..=10, 20..=30]);
+/// let mut v1 = v0.clone(); // O(1)
+/// v1.ranges_insert(11..=15);
+/// v1.remove(25);
+/// assert_eq!(v0.to_string(), "1..=10, 20..=30");
+/// assert_eq!(v1.to_string(), "1..=15, 20..=24, 26..=30");
+///
+/// let (added, removed) = PersistentRangeSetBlaze::diff(&v0, &v1);
+/// assert_eq!(added.to_string(), "11..=15");
+/// assert_eq!(removed.to_string(), "25..=25");
+/// ```
+#[derive(Clone)]
+pub struct PersistentRangeSetBlaze<T: Integer> {
+    len: <T as Integer>::SafeLen,
+    ranges_len: usize,
+    root: Link<T>,
+}
+
+impl<T: Integer> PersistentRangeSetBlaze<T> {
+    /// Makes a new, empty `PersistentRangeSetBlaze`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let set = PersistentRangeSetBlaze::<i32>::new();
+    /// assert!(set.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        PersistentRangeSetBlaze {
+            len: <T as Integer>::SafeLen::zero(),
+            ranges_len: 0,
+            root: None,
+        }
+    }
+
+    /// Returns the number of elements in the set.
+    ///
+    /// The number is allowed to be very, very large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let set = PersistentRangeSetBlaze::from_iter([0u8..=255]);
+    /// assert_eq!(set.len(), 256usize);
+    /// ```
+    #[must_use]
+    pub const fn len(&self) -> <T as Integer>::SafeLen {
+        self.len
+    }
+
+    /// Returns the number of sorted & disjoint ranges in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let set = PersistentRangeSetBlaze::from_iter([10..=20, 30..=40, 15..=35]);
+    /// assert_eq!(set.ranges_len(), 1);
+    /// ```
+    #[must_use]
+    pub const fn ranges_len(&self) -> usize {
+        self.ranges_len
+    }
+
+    /// Returns `true` if the set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let mut set = PersistentRangeSetBlaze::new();
+    /// assert!(set.is_empty());
+    /// set.insert(1);
+    /// assert!(!set.is_empty());
+    /// ```
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ranges_len == 0
+    }
+
+    /// Returns `true` if the two sets are the same version or share the same tree.
+    /// This is O(1). It may return `false` for sets that are equal but were built separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let a = PersistentRangeSetBlaze::from_iter([1..=3]);
+    /// let mut b = a.clone();
+    /// assert!(PersistentRangeSetBlaze::ptr_eq(&a, &b));
+    /// b.insert(4);
+    /// assert!(!PersistentRangeSetBlaze::ptr_eq(&a, &b));
+    /// ```
+    #[must_use]
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        match (&a.root, &b.root) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the first element in the set, if any.
+    /// This element is always the minimum of all elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let set = PersistentRangeSetBlaze::from_iter([3..=5, 10..=12]);
+    /// assert_eq!(set.first(), Some(3));
+    /// ```
+    #[must_use]
+    pub fn first(&self) -> Option<T> {
+        self.ranges().next().map(|range| *range.start())
+    }
+
+    /// Returns the last element in the set, if any.
+    /// This element is always the maximum of all elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let set = PersistentRangeSetBlaze::from_iter([3..=5, 10..=12]);
+    /// assert_eq!(set.last(), Some(12));
+    /// ```
+    #[must_use]
+    pub fn last(&self) -> Option<T> {
+        self.ranges().next_back().map(|range| *range.end())
+    }
+
+    /// Returns `true` if the set contains an element equal to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let set = PersistentRangeSetBlaze::from_iter([1, 2, 3]);
+    /// assert!(set.contains(1));
+    /// assert!(!set.contains(4));
+    /// ```
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        assert!(
+            value <= T::safe_max_value(),
+            "value must be <= T::safe_max_value()"
+        );
+        self.range_at_or_before(value)
+            .is_some_and(|(_, end)| value <= end)
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted. That is:
+    ///
+    /// - If the set did not previously contain an equal value, `true` is
+    ///   returned.
+    /// - If the set already contained an equal value, `false` is returned, and
+    ///   the entry is not updated.
+    ///
+    /// Other versions of the set are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let v0 = PersistentRangeSetBlaze::from_iter([1..=3]);
+    /// let mut v1 = v0.clone();
+    /// assert!(v1.insert(4));
+    /// assert!(!v1.insert(4));
+    /// assert_eq!(v0.to_string(), "1..=3");
+    /// assert_eq!(v1.to_string(), "1..=4");
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        self.ranges_insert(value..=value)
+    }
+
+    /// Adds a range to the set.
+    ///
+    /// Returns whether any values where newly inserted.
+    ///
+    /// Other versions of the set are not affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range's end is greater than `T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let mut set = PersistentRangeSetBlaze::new();
+    /// assert!(set.ranges_insert(2..=5));
+    /// assert!(set.ranges_insert(5..=6));
+    /// assert!(!set.ranges_insert(3..=4));
+    /// assert_eq!(set.len(), 5usize);
+    /// ```
+    pub fn ranges_insert(&mut self, range: RangeInclusive<T>) -> bool {
+        let (mut start, mut end) = range.into_inner();
+        assert!(
+            end <= T::safe_max_value(),
+            "end must be <= T::safe_max_value()"
+        );
+        if end < start {
+            return false;
+        }
+        let len_before = self.len;
+
+        // Absorb the range before, if it touches, and then every range that starts inside.
+        if let Some((start_before, end_before)) = self.range_at_or_before(start) {
+            if end_before >= end {
+                return false;
+            }
+            if end_before >= start || end_before + T::one() == start {
+                self.tree_remove(start_before);
+                start = start_before;
+            }
+        }
+        while let Some((start_after, end_after)) = self.range_after(start) {
+            if start_after > end && start_after - T::one() > end {
+                break;
+            }
+            self.tree_remove(start_after);
+            end = max(end, end_after);
+        }
+        self.tree_insert(start, end);
+        self.len != len_before
+    }
+
+    /// If the set contains an element equal to the value, removes it from the
+    /// set. Returns whether such an element was present.
+    ///
+    /// Other versions of the set are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let v0 = PersistentRangeSetBlaze::from_iter([1..=3]);
+    /// let mut v1 = v0.clone();
+    /// assert!(v1.remove(2));
+    /// assert!(!v1.remove(2));
+    /// assert_eq!(v0.to_string(), "1..=3");
+    /// assert_eq!(v1.to_string(), "1..=1, 3..=3");
+    /// ```
+    pub fn remove(&mut self, value: T) -> bool {
+        assert!(
+            value <= T::safe_max_value(),
+            "value must be <= T::safe_max_value()"
+        );
+        let len_before = self.len;
+        self.internal_remove(value..=value);
+        self.len != len_before
+    }
+
+    /// Create a [`PersistentRangeSetBlaze`] from a [`SortedDisjoint`] iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let a = PersistentRangeSetBlaze::from_sorted_disjoint(CheckSortedDisjoint::from([-10..=-5, 1..=2]));
+    /// assert_eq!(a.to_string(), "-10..=-5, 1..=2");
+    /// ```
+    pub fn from_sorted_disjoint<I>(iter: I) -> Self
+    where
+        I: SortedDisjoint<T>,
+    {
+        let mut len = <T as Integer>::SafeLen::zero();
+        let ranges: Vec<(T, T)> = iter
+            .map(|range| {
+                len += T::safe_len(&range);
+                range.into_inner()
+            })
+            .collect();
+        PersistentRangeSetBlaze {
+            len,
+            ranges_len: ranges.len(),
+            root: build(&ranges),
+        }
+    }
+
+    /// Gets a [`SortedDisjoint`] iterator over the ranges in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let set = PersistentRangeSetBlaze::from_iter([10..=20, 15..=25, 30..=40]);
+    /// let mut ranges = set.ranges();
+    /// assert_eq!(ranges.next(), Some(10..=25));
+    /// assert_eq!(ranges.next(), Some(30..=40));
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    pub fn ranges(&self) -> PersistentRangesIter<'_, T> {
+        let mut iter = PersistentRangesIter {
+            front: Vec::new(),
+            back: Vec::new(),
+            len: self.ranges_len,
+        };
+        push_left_spine(&mut iter.front, &self.root);
+        push_right_spine(&mut iter.back, &self.root);
+        iter
+    }
+
+    /// Gets an iterator that visits the integer elements in the set in ascending and/or descending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let set = PersistentRangeSetBlaze::from_iter([1..=3]);
+    /// let mut set_iter = set.iter();
+    /// assert_eq!(set_iter.next(), Some(1));
+    /// assert_eq!(set_iter.next_back(), Some(3));
+    /// assert_eq!(set_iter.next(), Some(2));
+    /// assert_eq!(set_iter.next_back(), None);
+    /// ```
+    pub fn iter(&self) -> Iter<T, PersistentRangesIter<'_, T>> {
+        Iter::new(self.ranges(), Some(self.len))
+    }
+
+    /// Compares two versions of a set, returning the integers `new` added and the integers it removed,
+    /// each as a [`SortedDisjoint`] iterator.
+    ///
+    /// Subtrees the two versions share are skipped without being visited, so when `new` was derived
+    /// from `old` by a few changes, the cost is about O(*k* log *n*), where *k* is the number of changed ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let old = PersistentRangeSetBlaze::from_iter((0..1000).map(|i| i * 10..=i * 10 + 5));
+    /// let mut new = old.clone();
+    /// new.ranges_insert(6..=8);
+    /// new.remove(5000);
+    ///
+    /// let (added, removed) = PersistentRangeSetBlaze::diff(&old, &new);
+    /// assert_eq!(added.to_string(), "6..=8");
+    /// assert_eq!(removed.to_string(), "5000..=5000");
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn diff(
+        old: &Self,
+        new: &Self,
+    ) -> (
+        BitSubMerge<T, ChangedRanges<T>, ChangedRanges<T>>,
+        BitSubMerge<T, ChangedRanges<T>, ChangedRanges<T>>,
+    ) {
+        let (old_only, new_only) = changed_ranges(&old.root, &new.root);
+        let added = ChangedRanges {
+            iter: new_only.clone().into_iter(),
+        } - ChangedRanges {
+            iter: old_only.clone().into_iter(),
+        };
+        let removed = ChangedRanges {
+            iter: old_only.into_iter(),
+        } - ChangedRanges {
+            iter: new_only.into_iter(),
+        };
+        (added, removed)
+    }
+
+    // Removes every element of the range, splitting or trimming the ranges it touches.
+    fn internal_remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if end < start {
+            return;
+        }
+        if let Some((start_before, end_before)) = self.range_at_or_before(start) {
+            if start <= end_before {
+                self.tree_remove(start_before);
+                if start_before < start {
+                    self.tree_insert(start_before, start - T::one());
+                }
+                if end < end_before {
+                    self.tree_insert(end + T::one(), end_before);
+                    return;
+                }
+            }
+        }
+        while let Some((start_after, end_after)) = self.range_after(start) {
+            if end < start_after {
+                break;
+            }
+            self.tree_remove(start_after);
+            if end < end_after {
+                self.tree_insert(end + T::one(), end_after);
+                break;
+            }
+        }
+    }
+
+    fn tree_insert(&mut self, start: T, end: T) {
+        insert(&mut self.root, start, end);
+        self.len += T::safe_len(&(start..=end));
+        self.ranges_len += 1;
+    }
+
+    fn tree_remove(&mut self, start: T) {
+        let end = remove(&mut self.root, start);
+        self.len -= T::safe_len(&(start..=end));
+        self.ranges_len -= 1;
+    }
+
+    // The range with the largest start <= value, if any.
+    fn range_at_or_before(&self, value: T) -> Option<(T, T)> {
+        let mut link = &self.root;
+        let mut result = None;
+        while let Some(node) = link {
+            if node.start <= value {
+                result = Some((node.start, node.end));
+                link = &node.right;
+            } else {
+                link = &node.left;
+            }
+        }
+        result
+    }
+
+    // The range with the smallest start > value, if any.
+    fn range_after(&self, value: T) -> Option<(T, T)> {
+        let mut link = &self.root;
+        let mut result = None;
+        while let Some(node) = link {
+            if node.start > value {
+                result = Some((node.start, node.end));
+                link = &node.left;
+            } else {
+                link = &node.right;
+            }
+        }
+        result
+    }
+}
+
+// Walks both trees in order, skipping subtrees they share, and returns the ranges found only in `a` and only in `b`.
+#[allow(clippy::type_complexity)]
+fn changed_ranges<T: Integer>(
+    a: &Link<T>,
+    b: &Link<T>,
+) -> (Vec<RangeInclusive<T>>, Vec<RangeInclusive<T>>) {
+    enum Pending<'a, T: Integer> {
+        Tree(&'a Arc<Node<T>>),
+        Range(T, T),
+    }
+
+    fn expand<'a, T: Integer>(stack: &mut Vec<Pending<'a, T>>) {
+        if let Some(Pending::Tree(node)) = stack.pop() {
+            // Pushed in reverse, so the left subtree comes off the stack first.
+            if let Some(right) = &node.right {
+                stack.push(Pending::Tree(right));
+            }
+            stack.push(Pending::Range(node.start, node.end));
+            if let Some(left) = &node.left {
+                stack.push(Pending::Tree(left));
+            }
+        }
+    }
+
+    let mut a_stack: Vec<Pending<T>> = a.iter().map(Pending::Tree).collect();
+    let mut b_stack: Vec<Pending<T>> = b.iter().map(Pending::Tree).collect();
+    let (mut a_only, mut b_only) = (Vec::new(), Vec::new());
+    loop {
+        match (a_stack.last(), b_stack.last()) {
+            (None, None) => break,
+            (Some(Pending::Tree(x)), Some(Pending::Tree(y))) => {
+                if Arc::ptr_eq(x, y) {
+                    a_stack.pop();
+                    b_stack.pop();
+                } else if x.height >= y.height {
+                    expand(&mut a_stack);
+                } else {
+                    expand(&mut b_stack);
+                }
+            }
+            (Some(Pending::Tree(_)), _) => expand(&mut a_stack),
+            (_, Some(Pending::Tree(_))) => expand(&mut b_stack),
+            (Some(&Pending::Range(a_start, a_end)), Some(&Pending::Range(b_start, b_end))) => {
+                if (a_start, a_end) == (b_start, b_end) {
+                    a_stack.pop();
+                    b_stack.pop();
+                } else if a_start <= b_start {
+                    a_only.push(a_start..=a_end);
+                    a_stack.pop();
+                } else {
+                    b_only.push(b_start..=b_end);
+                    b_stack.pop();
+                }
+            }
+            (Some(&Pending::Range(a_start, a_end)), None) => {
+                a_only.push(a_start..=a_end);
+                a_stack.pop();
+            }
+            (None, Some(&Pending::Range(b_start, b_end))) => {
+                b_only.push(b_start..=b_end);
+                b_stack.pop();
+            }
+        }
+    }
+    (a_only, b_only)
+}
+
+impl<T: Integer> Default for PersistentRangeSetBlaze<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer> PartialEq for PersistentRangeSetBlaze<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other)
+            || (self.len == other.len
+                && self.ranges_len == other.ranges_len
+                && self.ranges().eq(other.ranges()))
+    }
+}
+
+impl<T: Integer> Eq for PersistentRangeSetBlaze<T> {}
+
+impl<T: Integer> fmt::Debug for PersistentRangeSetBlaze<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ranges().to_string())
+    }
+}
+
+impl<T: Integer> fmt::Display for PersistentRangeSetBlaze<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ranges().to_string())
+    }
+}
+
+impl<T: Integer> FromIterator<T> for PersistentRangeSetBlaze<T> {
+    /// Create a [`PersistentRangeSetBlaze`] from an iterator of integers. Duplicates and out-of-order elements are fine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let a0 = PersistentRangeSetBlaze::from_iter([3, 2, 1, 100, 1]);
+    /// let a1: PersistentRangeSetBlaze<i32> = [3, 2, 1, 100, 1].into_iter().collect();
+    /// assert!(a0 == a1 && a0.to_string() == "1..=3, 100..=100");
+    /// ```
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().map(|x| x..=x).collect()
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for PersistentRangeSetBlaze<T> {
+    /// Create a [`PersistentRangeSetBlaze`] from an iterator of inclusive ranges, `start..=end`.
+    /// Overlapping, out-of-order, and empty ranges are fine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// #[allow(clippy::reversed_empty_ranges)]
+    /// let a = PersistentRangeSetBlaze::from_iter([1..=2, 2..=2, -10..=-5, 1..=0]);
+    /// assert_eq!(a.to_string(), "-10..=-5, 1..=2");
+    /// ```
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = RangeInclusive<T>>,
+    {
+        let union_iter: UnionIter<T, _> = iter.into_iter().collect();
+        PersistentRangeSetBlaze::from_sorted_disjoint(union_iter)
+    }
+}
+
+impl<'a, T: Integer> IntoIterator for &'a PersistentRangeSetBlaze<T> {
+    type Item = T;
+    type IntoIter = Iter<T, PersistentRangesIter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+gen_ops_ex!(
+    <T>;
+    types ref PersistentRangeSetBlaze<T>, ref PersistentRangeSetBlaze<T> => PersistentRangeSetBlaze<T>;
+
+    /// Unions the contents of two [`PersistentRangeSetBlaze`]'s.
+    /// The result shares unchanged subtrees with the left-hand input.
+    ///
+    /// Either, neither, or both inputs may be borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let a = PersistentRangeSetBlaze::from_iter([1..=2, 5..=100]);
+    /// let b = PersistentRangeSetBlaze::from_iter([2..=6]);
+    /// let result = &a | &b; // Alternatively, 'a | b'.
+    /// assert_eq!(result.to_string(), "1..=100");
+    /// ```
+    for | call |a: &PersistentRangeSetBlaze<T>, b: &PersistentRangeSetBlaze<T>| {
+        let mut result = a.clone();
+        for range in b.ranges() - a.ranges() {
+            result.ranges_insert(range);
+        }
+        result
+    };
+
+    /// Intersects the contents of two [`PersistentRangeSetBlaze`]'s.
+    /// The result shares unchanged subtrees with the left-hand input.
+    ///
+    /// Either, neither, or both inputs may be borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let a = PersistentRangeSetBlaze::from_iter([1..=2, 5..=100]);
+    /// let b = PersistentRangeSetBlaze::from_iter([2..=6]);
+    /// let result = &a & &b; // Alternatively, 'a & b'.
+    /// assert_eq!(result.to_string(), "2..=2, 5..=6");
+    /// ```
+    for & call |a: &PersistentRangeSetBlaze<T>, b: &PersistentRangeSetBlaze<T>| {
+        let mut result = a.clone();
+        for range in a.ranges() - b.ranges() {
+            result.internal_remove(range);
+        }
+        result
+    };
+
+    /// Symmetric difference the contents of two [`PersistentRangeSetBlaze`]'s.
+    /// The result shares unchanged subtrees with the left-hand input.
+    ///
+    /// Either, neither, or both inputs may be borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let a = PersistentRangeSetBlaze::from_iter([1..=2, 5..=100]);
+    /// let b = PersistentRangeSetBlaze::from_iter([2..=6]);
+    /// let result = &a ^ &b; // Alternatively, 'a ^ b'.
+    /// assert_eq!(result.to_string(), "1..=1, 3..=4, 7..=100");
+    /// ```
+    for ^ call |a: &PersistentRangeSetBlaze<T>, b: &PersistentRangeSetBlaze<T>| {
+        let mut result = a.clone();
+        for range in a.ranges() & b.ranges() {
+            result.internal_remove(range);
+        }
+        for range in b.ranges() - a.ranges() {
+            result.ranges_insert(range);
+        }
+        result
+    };
+
+    /// Difference the contents of two [`PersistentRangeSetBlaze`]'s.
+    /// The result shares unchanged subtrees with the left-hand input.
+    ///
+    /// Either, neither, or both inputs may be borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let a = PersistentRangeSetBlaze::from_iter([1..=2, 5..=100]);
+    /// let b = PersistentRangeSetBlaze::from_iter([2..=6]);
+    /// let result = &a - &b; // Alternatively, 'a - b'.
+    /// assert_eq!(result.to_string(), "1..=1, 7..=100");
+    /// ```
+    for - call |a: &PersistentRangeSetBlaze<T>, b: &PersistentRangeSetBlaze<T>| {
+        let mut result = a.clone();
+        for range in a.ranges() & b.ranges() {
+            result.internal_remove(range);
+        }
+        result
+    };
+    where T: Integer //Where clause for all impl's
+);
+
+gen_ops_ex!(
+    <T>;
+    types ref PersistentRangeSetBlaze<T> => PersistentRangeSetBlaze<T>;
+
+    /// Complement the contents of a [`PersistentRangeSetBlaze`].
+    ///
+    /// The input may be borrowed or not.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::PersistentRangeSetBlaze;
+    ///
+    /// let a = PersistentRangeSetBlaze::from_iter([1..=2, 5..=100]);
+    /// let result = !&a; // Alternatively, '!a'.
+    /// assert_eq!(
+    ///     result.to_string(),
+    ///     "-2147483648..=0, 3..=4, 101..=2147483647"
+    /// );
+    /// ```
+    for ! call |a: &PersistentRangeSetBlaze<T>| {
+        PersistentRangeSetBlaze::from_sorted_disjoint(!a.ranges())
+    };
+
+    where T: Integer //Where clause for all impl's
+);
+
+fn push_left_spine<'a, T: Integer>(stack: &mut Vec<&'a Node<T>>, mut link: &'a Link<T>) {
+    while let Some(node) = link {
+        stack.push(node);
+        link = &node.left;
+    }
+}
+
+fn push_right_spine<'a, T: Integer>(stack: &mut Vec<&'a Node<T>>, mut link: &'a Link<T>) {
+    while let Some(node) = link {
+        stack.push(node);
+        link = &node.right;
+    }
+}
+
+/// An iterator that visits the ranges in the [`PersistentRangeSetBlaze`],
+/// i.e., the integers as sorted & disjoint ranges.
+///
+/// This `struct` is created by the [`ranges`] method on [`PersistentRangeSetBlaze`]. See [`ranges`]'s
+/// documentation for more.
+///
+/// [`ranges`]: PersistentRangeSetBlaze::ranges
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PersistentRangesIter<'a, T: Integer> {
+    front: Vec<&'a Node<T>>,
+    back: Vec<&'a Node<T>>,
+    // The two stacks walk toward each other; `len` stops them from passing.
+    len: usize,
+}
+
+impl<T: Integer> fmt::Debug for PersistentRangesIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T: Integer> SortedStarts<T> for PersistentRangesIter<'_, T> {}
+impl<T: Integer> SortedDisjoint<T> for PersistentRangesIter<'_, T> {}
+
+impl<T: Integer> ExactSizeIterator for PersistentRangesIter<'_, T> {}
+
+impl<T: Integer> FusedIterator for PersistentRangesIter<'_, T> {}
+
+impl<T: Integer> Iterator for PersistentRangesIter<'_, T> {
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let node = self
+            .front
+            .pop()
+            .expect("real assert: len counts the nodes left");
+        push_left_spine(&mut self.front, &node.right);
+        Some(node.start..=node.end)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T: Integer> DoubleEndedIterator for PersistentRangesIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let node = self
+            .back
+            .pop()
+            .expect("real assert: len counts the nodes left");
+        push_right_spine(&mut self.back, &node.left);
+        Some(node.start..=node.end)
+    }
+}
+
+impl<T: Integer> ops::Not for PersistentRangesIter<'_, T> {
+    type Output = NotIter<T, Self>;
+
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+impl<T: Integer, I> ops::BitOr<I> for PersistentRangesIter<'_, T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitOrMerge<T, Self, I>;
+
+    fn bitor(self, other: I) -> Self::Output {
+        SortedDisjoint::union(self, other)
+    }
+}
+
+impl<T: Integer, I> ops::Sub<I> for PersistentRangesIter<'_, T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitSubMerge<T, Self, I>;
+
+    fn sub(self, other: I) -> Self::Output {
+        SortedDisjoint::difference(self, other)
+    }
+}
+
+impl<T: Integer, I> ops::BitXor<I> for PersistentRangesIter<'_, T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitXOrTee<T, Self, I>;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn bitxor(self, other: I) -> Self::Output {
+        SortedDisjoint::symmetric_difference(self, other)
+    }
+}
+
+impl<T: Integer, I> ops::BitAnd<I> for PersistentRangesIter<'_, T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitAndMerge<T, Self, I>;
+
+    fn bitand(self, other: I) -> Self::Output {
+        SortedDisjoint::intersection(self, other)
+    }
+}
@@ -4,7 +4,10 @@
 //! ```
 //! use range_set_blaze::prelude::*;
 //! ```
+pub use crate::{CheckSortedDisjoint, SortedDisjoint};
+
+#[cfg(feature = "alloc")]
 pub use crate::{
-    intersection_dyn, union_dyn, CheckSortedDisjoint, DynSortedDisjoint, MultiwayRangeSetBlaze,
-    MultiwayRangeSetBlazeRef, MultiwaySortedDisjoint, RangeSetBlaze, SortedDisjoint,
+    intersection_dyn, union_dyn, DynSortedDisjoint, MultiwayRangeSetBlaze,
+    MultiwayRangeSetBlazeRef, MultiwaySortedDisjoint, RangeSetBlaze,
 };
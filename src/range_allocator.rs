@@ -0,0 +1,523 @@
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+use core::{
+    cmp::{max, Ordering},
+    fmt,
+    ops::RangeInclusive,
+};
+
+use num_traits::Zero;
+
+use crate::{Integer, RangeSetBlaze};
+
+// The free ranges also live in an AVL tree keyed by `start`, where each node records the
+// length of the longest range in its subtree. A first-fit search skips any subtree whose
+// longest range is too short, so it visits O(log *n*) nodes.
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Clone)]
+struct Node<T: Integer> {
+    start: T,
+    end: T,
+    len: <T as Integer>::SafeLen,
+    max_len: <T as Integer>::SafeLen,
+    height: u8,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T: Integer> Node<T> {
+    fn new(start: T, end: T) -> Self {
+        let len = T::safe_len(&(start..=end));
+        Node {
+            start,
+            end,
+            len,
+            max_len: len,
+            height: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    fn fix(&mut self) {
+        self.height = 1 + max(height(&self.left), height(&self.right));
+        self.max_len = max(self.len, max(max_len(&self.left), max_len(&self.right)));
+    }
+}
+
+fn height<T: Integer>(link: &Link<T>) -> u8 {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+fn max_len<T: Integer>(link: &Link<T>) -> <T as Integer>::SafeLen {
+    link.as_ref()
+        .map_or(<T as Integer>::SafeLen::zero(), |node| node.max_len)
+}
+
+fn rotate_right<T: Integer>(link: &mut Link<T>) {
+    let mut node = link.take().expect("real assert: rotate needs a node");
+    let mut left = node.left.take().expect("real assert: rotate needs a child");
+    node.left = left.right.take();
+    node.fix();
+    left.right = Some(node);
+    left.fix();
+    *link = Some(left);
+}
+
+fn rotate_left<T: Integer>(link: &mut Link<T>) {
+    let mut node = link.take().expect("real assert: rotate needs a node");
+    let mut right = node
+        .right
+        .take()
+        .expect("real assert: rotate needs a child");
+    node.right = right.left.take();
+    node.fix();
+    right.left = Some(node);
+    right.fix();
+    *link = Some(right);
+}
+
+fn rebalance<T: Integer>(link: &mut Link<T>) {
+    let Some(node) = link.as_mut() else {
+        return;
+    };
+    node.fix();
+    let (left_height, right_height) = (height(&node.left), height(&node.right));
+    if left_height > right_height + 1 {
+        let left = node.left.as_ref().expect("real assert: left is taller");
+        if height(&left.right) > height(&left.left) {
+            rotate_left(&mut node.left);
+        }
+        rotate_right(link);
+    } else if right_height > left_height + 1 {
+        let right = node.right.as_ref().expect("real assert: right is taller");
+        if height(&right.left) > height(&right.right) {
+            rotate_right(&mut node.right);
+        }
+        rotate_left(link);
+    }
+}
+
+// Inserts a range whose start is not yet in the tree.
+fn insert<T: Integer>(link: &mut Link<T>, start: T, end: T) {
+    let Some(node) = link.as_mut() else {
+        *link = Some(Box::new(Node::new(start, end)));
+        return;
+    };
+    match start.cmp(&node.start) {
+        Ordering::Less => insert(&mut node.left, start, end),
+        Ordering::Greater => insert(&mut node.right, start, end),
+        Ordering::Equal => unreachable!("real assert: start must not be in the tree"),
+    }
+    rebalance(link);
+}
+
+// Removes the range with the given start, which must be in the tree.
+fn remove<T: Integer>(link: &mut Link<T>, start: T) {
+    let node = link
+        .as_mut()
+        .expect("real assert: start must be in the tree");
+    match start.cmp(&node.start) {
+        Ordering::Less => remove(&mut node.left, start),
+        Ordering::Greater => remove(&mut node.right, start),
+        Ordering::Equal => {
+            if node.left.is_none() {
+                *link = node.right.take();
+                return;
+            }
+            if node.right.is_none() {
+                *link = node.left.take();
+                return;
+            }
+            let first = remove_first(&mut node.right);
+            (node.start, node.end, node.len) = (first.start, first.end, first.len);
+        }
+    }
+    rebalance(link);
+}
+
+fn remove_first<T: Integer>(link: &mut Link<T>) -> Box<Node<T>> {
+    let node = link.as_mut().expect("real assert: tree must not be empty");
+    if node.left.is_none() {
+        let mut first = link.take().expect("real assert: checked above");
+        *link = first.right.take();
+        return first;
+    }
+    let first = remove_first(&mut node.left);
+    rebalance(link);
+    first
+}
+
+// Returns the range with the smallest start `>= from` that has at least `n` elements.
+fn first_fit<T: Integer>(link: &Link<T>, from: T, n: <T as Integer>::SafeLen) -> Option<(T, T)> {
+    let node = link.as_ref().filter(|node| node.max_len >= n)?;
+    if from <= node.start {
+        if let Some(found) = first_fit(&node.left, from, n) {
+            return Some(found);
+        }
+        if node.len >= n {
+            return Some((node.start, node.end));
+        }
+    }
+    first_fit(&node.right, from, n)
+}
+
+// Builds a balanced tree from sorted & disjoint ranges.
+fn build<T: Integer>(ranges: &[(T, T)]) -> Link<T> {
+    if ranges.is_empty() {
+        return None;
+    }
+    let mid = ranges.len() / 2;
+    let mut node = Node::new(ranges[mid].0, ranges[mid].1);
+    node.left = build(&ranges[..mid]);
+    node.right = build(&ranges[mid + 1..]);
+    node.fix();
+    Some(Box::new(node))
+}
+
+/// A free list of integer IDs, a [`RangeSetBlaze`] plus indexes that make gap searches
+/// O(log *n*).
+///
+/// [`RangeSetBlaze`] has the same allocator-style methods, such as [`find_best_fit`] and
+/// [`allocate_first_fit`], but they search the ranges one by one, so that sets that are never
+/// used as free lists pay nothing. A `RangeAllocator` keeps its free ranges in the set and
+/// also in two indexes, one by start that knows the longest range of each subtree and one by
+/// length. Every search and allocation is then O(log *n*), where *n* is the number of free
+/// ranges, at the cost of about three times the memory and slower edits.
+///
+/// The methods return the same blocks as the [`RangeSetBlaze`] methods of the same names.
+///
+/// [`find_best_fit`]: RangeSetBlaze::find_best_fit
+/// [`allocate_first_fit`]: RangeSetBlaze::allocate_first_fit
+///
+/// # Examples
+///
+/// ```
+/// use range_set_blaze::{RangeAllocator, RangeSetBlaze};
+///
+/// let mut ports = RangeAllocator::from(RangeSetBlaze::from_iter([1024u16..=1030, 2000..=2999]));
+/// assert_eq!(ports.allocate(5), Some(1024..=1028));
+/// assert_eq!(ports.allocate_best_fit(2), Some(1029..=1030));
+/// assert_eq!(ports.allocate_first_fit(10, 2500), Some(2500..=2509));
+/// ports.release(1024..=1028);
+/// assert_eq!(ports.free_set().to_string(), "1024..=1028, 2000..=2499, 2510..=2999");
+/// ```
+#[derive(Clone)]
+pub struct RangeAllocator<T: Integer> {
+    free: RangeSetBlaze<T>,
+    by_start: Link<T>,
+    by_len: BTreeSet<(<T as Integer>::SafeLen, T)>,
+}
+
+impl<T: Integer> RangeAllocator<T> {
+    /// Makes a new `RangeAllocator` with no free IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeAllocator;
+    ///
+    /// let mut ids = RangeAllocator::<u64>::new();
+    /// assert_eq!(ids.allocate(1), None);
+    /// ids.release(0..=99);
+    /// assert_eq!(ids.allocate(1), Some(0..=0));
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        RangeAllocator {
+            free: RangeSetBlaze::new(),
+            by_start: None,
+            by_len: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the free IDs as a [`RangeSetBlaze`].
+    #[must_use]
+    pub const fn free_set(&self) -> &RangeSetBlaze<T> {
+        &self.free
+    }
+
+    /// Returns the free IDs as a [`RangeSetBlaze`], dropping the indexes.
+    #[must_use]
+    pub fn into_free_set(self) -> RangeSetBlaze<T> {
+        self.free
+    }
+
+    /// Returns the first block of `n` consecutive free IDs that starts at or after `hint`. See
+    /// [`RangeSetBlaze::find_free`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// Panics if `hint > T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of free ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{RangeAllocator, RangeSetBlaze};
+    ///
+    /// let ids = RangeAllocator::from(RangeSetBlaze::from_iter([0u64..=3, 10..=19, 30..=99]));
+    /// assert_eq!(ids.find_free(5, 12), Some(12..=16));
+    /// assert_eq!(ids.find_free(5, 17), Some(30..=34));
+    /// ```
+    #[must_use]
+    pub fn find_free(&self, n: <T as Integer>::SafeLen, hint: T) -> Option<RangeInclusive<T>> {
+        assert!(n > <T as Integer>::SafeLen::zero(), "n must be > 0");
+        assert!(
+            hint <= T::safe_max_value(),
+            "hint must be <= T::safe_max_value()"
+        );
+        if let Some((_, &end)) = self.free.btree_map.range(..=hint).next_back() {
+            if hint <= end && T::safe_len(&(hint..=end)) >= n {
+                return Some(hint..=T::add_len_less_one(hint, n));
+            }
+        }
+        let from = hint.checked_add(&T::one())?;
+        first_fit(&self.by_start, from, n).map(|(start, _)| start..=T::add_len_less_one(start, n))
+    }
+
+    /// Returns the first `n` IDs of the smallest free range that has at least `n` IDs. Ties go
+    /// to the lowest range. See [`RangeSetBlaze::find_best_fit`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of free ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{RangeAllocator, RangeSetBlaze};
+    ///
+    /// let ids = RangeAllocator::from(RangeSetBlaze::from_iter([0u64..=3, 10..=19, 30..=35]));
+    /// assert_eq!(ids.find_best_fit(5), Some(30..=34));
+    /// ```
+    #[must_use]
+    pub fn find_best_fit(&self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        assert!(n > <T as Integer>::SafeLen::zero(), "n must be > 0");
+        self.by_len
+            .range((n, T::min_value())..)
+            .next()
+            .map(|&(_, start)| start..=T::add_len_less_one(start, n))
+    }
+
+    /// Returns the first `n` IDs of the largest free range, if it has at least `n` IDs. Ties go
+    /// to the lowest range. See [`RangeSetBlaze::find_worst_fit`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of free ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{RangeAllocator, RangeSetBlaze};
+    ///
+    /// let ids = RangeAllocator::from(RangeSetBlaze::from_iter([0u64..=3, 10..=19, 30..=35]));
+    /// assert_eq!(ids.find_worst_fit(2), Some(10..=11));
+    /// ```
+    #[must_use]
+    pub fn find_worst_fit(&self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        assert!(n > <T as Integer>::SafeLen::zero(), "n must be > 0");
+        let &(longest, _) = self.by_len.last().filter(|&&(len, _)| len >= n)?;
+        self.by_len
+            .range((longest, T::min_value())..)
+            .next()
+            .map(|&(_, start)| start..=T::add_len_less_one(start, n))
+    }
+
+    /// Removes and returns the lowest block of `n` consecutive free IDs. See
+    /// [`RangeSetBlaze::allocate`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of free ranges.
+    pub fn allocate(&mut self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        self.allocate_first_fit(n, T::min_value())
+    }
+
+    /// Removes and returns the block found by [`find_free`](RangeAllocator::find_free).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// Panics if `hint > T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of free ranges.
+    pub fn allocate_first_fit(
+        &mut self,
+        n: <T as Integer>::SafeLen,
+        hint: T,
+    ) -> Option<RangeInclusive<T>> {
+        let block = self.find_free(n, hint)?;
+        self.remove_block(&block, n);
+        Some(block)
+    }
+
+    /// Removes and returns the block found by [`find_best_fit`](RangeAllocator::find_best_fit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of free ranges.
+    pub fn allocate_best_fit(&mut self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        let block = self.find_best_fit(n)?;
+        self.remove_block(&block, n);
+        Some(block)
+    }
+
+    /// Removes and returns the block found by [`find_worst_fit`](RangeAllocator::find_worst_fit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of free ranges.
+    pub fn allocate_worst_fit(&mut self, n: <T as Integer>::SafeLen) -> Option<RangeInclusive<T>> {
+        let block = self.find_worst_fit(n)?;
+        self.remove_block(&block, n);
+        Some(block)
+    }
+
+    /// Adds the IDs in `range` back to the free list. IDs that are already free stay free.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range.end() > T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O((*k* + 1) log *n*), where *n* is the number of free ranges and *k* is the number of
+    /// free ranges that `range` overlaps or touches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{RangeAllocator, RangeSetBlaze};
+    ///
+    /// let mut ids = RangeAllocator::from(RangeSetBlaze::from_iter([0u64..=3, 10..=19]));
+    /// ids.release(4..=9);
+    /// assert_eq!(ids.find_best_fit(20), Some(0..=19));
+    /// ```
+    pub fn release(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.clone().into_inner();
+        assert!(
+            end <= T::safe_max_value(),
+            "end must be <= T::safe_max_value()"
+        );
+        if end < start {
+            return;
+        }
+        // The ranges that `range` overlaps or touches merge with it.
+        let low = if start == T::min_value() {
+            start
+        } else {
+            start - T::one()
+        };
+        let high = end.checked_add(&T::one()).unwrap_or(end);
+        let merged: Vec<(T, T)> = self
+            .free
+            .btree_map
+            .range(..=high)
+            .rev()
+            .take_while(|&(_, &range_end)| low <= range_end)
+            .map(|(&range_start, &range_end)| (range_start, range_end))
+            .collect();
+        for (range_start, range_end) in merged {
+            self.index_remove(range_start, range_end);
+        }
+        self.free.ranges_insert(range);
+        let (&merged_start, &merged_end) = self
+            .free
+            .btree_map
+            .range(..=start)
+            .next_back()
+            .expect("real assert: the range was just inserted");
+        self.index_insert(merged_start, merged_end);
+    }
+
+    // Removes a block of `n` IDs that lies inside a single free range.
+    fn remove_block(&mut self, block: &RangeInclusive<T>, n: <T as Integer>::SafeLen) {
+        let (start, end) = block.clone().into_inner();
+        let (&range_start, &range_end) = self
+            .free
+            .btree_map
+            .range(..=start)
+            .next_back()
+            .expect("real assert: the block is inside a range");
+        self.index_remove(range_start, range_end);
+        self.free.remove_block(block, n);
+        if range_start < start {
+            self.index_insert(range_start, start - T::one());
+        }
+        if end < range_end {
+            self.index_insert(end + T::one(), range_end);
+        }
+    }
+
+    fn index_insert(&mut self, start: T, end: T) {
+        insert(&mut self.by_start, start, end);
+        self.by_len.insert((T::safe_len(&(start..=end)), start));
+    }
+
+    fn index_remove(&mut self, start: T, end: T) {
+        remove(&mut self.by_start, start);
+        self.by_len.remove(&(T::safe_len(&(start..=end)), start));
+    }
+}
+
+impl<T: Integer> Default for RangeAllocator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer> fmt::Debug for RangeAllocator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RangeAllocator").field(&self.free).finish()
+    }
+}
+
+impl<T: Integer> From<RangeSetBlaze<T>> for RangeAllocator<T> {
+    /// Indexes a set of free IDs.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* log *n*), where *n* is the number of ranges in the set.
+    fn from(free: RangeSetBlaze<T>) -> Self {
+        let ranges: Vec<(T, T)> = free.ranges().map(RangeInclusive::into_inner).collect();
+        let by_len = ranges
+            .iter()
+            .map(|&(start, end)| (T::safe_len(&(start..=end)), start))
+            .collect();
+        RangeAllocator {
+            by_start: build(&ranges),
+            by_len,
+            free,
+        }
+    }
+}
@@ -0,0 +1,466 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{
+    fmt,
+    iter::FusedIterator,
+    ops::{Bound, RangeInclusive},
+};
+
+use crate::{sorted_disjoint::impl_sorted_disjoint_and_ops, Integer, RangeSetBlaze};
+
+/// A multiset of integers, stored as sorted & disjoint segments that each carry a count.
+///
+/// Where [`RangeSetBlaze`] collapses duplicates, a `RangeCountMap` remembers how many times
+/// each integer was added, for example, the reference counts of shared pages or the number of
+/// reservations that overlap. Integers with a count of zero aren't stored, and touching
+/// segments with equal counts are always merged, so each segment is maximal.
+///
+/// # Examples
+///
+/// ```
+/// use range_set_blaze::prelude::*;
+/// use range_set_blaze::RangeCountMap;
+///
+/// // Three reservations, two of which overlap on 15..=20.
+/// let mut reservations = RangeCountMap::from_iter([10..=20, 15..=30, 40..=50]);
+/// assert_eq!(reservations.count(17), 2);
+/// assert_eq!(reservations.to_string(), "10..=14: 1, 15..=20: 2, 21..=30: 1, 40..=50: 1");
+///
+/// let overbooked = reservations.ranges_with_count_at_least(2);
+/// assert_eq!(overbooked.to_string(), "15..=20");
+///
+/// reservations.remove_range(15..=30, 1);
+/// assert_eq!(reservations.support().to_string(), "10..=20, 40..=50");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RangeCountMap<T: Integer> {
+    // Maps each segment's start to its end and its count, which is never zero.
+    btree_map: BTreeMap<T, (T, usize)>,
+}
+
+impl<T: Integer> RangeCountMap<T> {
+    /// Makes a new, empty `RangeCountMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeCountMap;
+    ///
+    /// let map = RangeCountMap::<u32>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        RangeCountMap {
+            btree_map: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `true` if every integer has a count of zero.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.btree_map.is_empty()
+    }
+
+    /// Returns the number of maximal segments of equal, non-zero count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeCountMap;
+    ///
+    /// let map = RangeCountMap::from_iter([1..=10, 5..=15]);
+    /// assert_eq!(map.segments_len(), 3);
+    /// ```
+    #[must_use]
+    pub fn segments_len(&self) -> usize {
+        self.btree_map.len()
+    }
+
+    /// Returns the number of times `value` was added, less the number of times it was removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value > T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n*), where *n* is the number of segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeCountMap;
+    ///
+    /// let map = RangeCountMap::from_iter([1..=10, 5..=15]);
+    /// assert_eq!(map.count(0), 0);
+    /// assert_eq!(map.count(4), 1);
+    /// assert_eq!(map.count(5), 2);
+    /// ```
+    #[must_use]
+    pub fn count(&self, value: T) -> usize {
+        assert!(
+            value <= T::safe_max_value(),
+            "value must be <= T::safe_max_value()"
+        );
+        match self.btree_map.range(..=value).next_back() {
+            Some((_, &(end, count))) if value <= end => count,
+            _ => 0,
+        }
+    }
+
+    /// Adds `k` to the count of every integer in `range`. An empty range or a `k` of zero
+    /// changes nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range.end() > T::safe_max_value()` or if a count would overflow `usize`.
+    /// Nothing is changed if it panics.
+    ///
+    /// # Performance
+    ///
+    /// O((*m* + 1) log *n*), where *n* is the number of segments and *m* is the number of
+    /// segments that overlap `range`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeCountMap;
+    ///
+    /// let mut map = RangeCountMap::new();
+    /// map.add_range(1..=10, 2);
+    /// map.add_range(5..=15, 1);
+    /// assert_eq!(map.to_string(), "1..=4: 2, 5..=10: 3, 11..=15: 1");
+    /// ```
+    pub fn add_range(&mut self, range: RangeInclusive<T>, k: usize) {
+        self.update(range, k, |count| {
+            count.checked_add(k).expect("count must not overflow usize")
+        });
+    }
+
+    /// Subtracts `k` from the count of every integer in `range`. An empty range or a `k` of
+    /// zero changes nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range.end() > T::safe_max_value()` or if any integer in `range` has a count
+    /// less than `k`. Nothing is changed if it panics.
+    ///
+    /// # Performance
+    ///
+    /// O((*m* + 1) log *n*), where *n* is the number of segments and *m* is the number of
+    /// segments that overlap `range`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeCountMap;
+    ///
+    /// let mut map = RangeCountMap::from_iter([1..=10, 5..=15]);
+    /// map.remove_range(8..=12, 1);
+    /// assert_eq!(map.to_string(), "1..=4: 1, 5..=7: 2, 8..=10: 1, 13..=15: 1");
+    /// ```
+    pub fn remove_range(&mut self, range: RangeInclusive<T>, k: usize) {
+        self.update(range, k, |count| {
+            count
+                .checked_sub(k)
+                .expect("count must be >= k for every integer in the range")
+        });
+    }
+
+    /// Returns the integers whose count is at least `k`, as a
+    /// [`SortedDisjoint`](crate::SortedDisjoint) iterator of ranges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    /// use range_set_blaze::RangeCountMap;
+    ///
+    /// let map = RangeCountMap::from_iter([1..=10, 5..=15, 8..=20]);
+    /// assert_eq!(map.ranges_with_count_at_least(2).to_string(), "5..=15");
+    /// assert_eq!(map.ranges_with_count_at_least(3).to_string(), "8..=10");
+    /// let small = CheckSortedDisjoint::from([0..=6]);
+    /// assert_eq!((map.ranges_with_count_at_least(1) - small).to_string(), "7..=20");
+    /// ```
+    pub fn ranges_with_count_at_least(&self, k: usize) -> RangesWithCountAtLeast<'_, T> {
+        assert!(k > 0, "k must be > 0");
+        RangesWithCountAtLeast {
+            iter: self.btree_map.iter(),
+            k,
+            pending: None,
+        }
+    }
+
+    /// Returns the integers with a non-zero count as a [`RangeSetBlaze`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeCountMap;
+    ///
+    /// let map = RangeCountMap::from_iter([1..=10, 5..=15, 20..=20]);
+    /// assert_eq!(map.support().to_string(), "1..=15, 20..=20");
+    /// ```
+    #[must_use]
+    pub fn support(&self) -> RangeSetBlaze<T> {
+        RangeSetBlaze::from_sorted_disjoint(self.ranges_with_count_at_least(1))
+    }
+
+    /// Returns an iterator over the maximal segments and their counts, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeCountMap;
+    ///
+    /// let map = RangeCountMap::from_iter([1..=10, 5..=15]);
+    /// let segments: Vec<_> = map.segments().collect();
+    /// assert_eq!(segments, [(1..=4, 1), (5..=10, 2), (11..=15, 1)]);
+    /// ```
+    pub fn segments(&self) -> SegmentsIter<'_, T> {
+        SegmentsIter {
+            iter: self.btree_map.iter(),
+        }
+    }
+
+    // Replaces the count of every integer in `range` with `f(count)`, where `f` is called once
+    // per overlapped segment and gap. All counts are computed before anything changes.
+    fn update(&mut self, range: RangeInclusive<T>, k: usize, f: impl Fn(usize) -> usize) {
+        let (start, end) = range.into_inner();
+        assert!(
+            end <= T::safe_max_value(),
+            "end must be <= T::safe_max_value()"
+        );
+        if end < start || k == 0 {
+            return;
+        }
+
+        // The segments that overlap `range`, unclipped.
+        let before = self
+            .btree_map
+            .range(..start)
+            .next_back()
+            .filter(|(_, &(end_before, _))| start <= end_before);
+        let overlapped: Vec<(T, T, usize)> = before
+            .into_iter()
+            .chain(self.btree_map.range(start..=end))
+            .map(|(&seg_start, &(seg_end, count))| (seg_start, seg_end, count))
+            .collect();
+
+        // Cut `range` into pieces at segment edges and give each its new count.
+        let mut pieces = Vec::with_capacity(overlapped.len() * 2 + 1);
+        let mut next = Some(start);
+        for &(seg_start, seg_end, count) in &overlapped {
+            let Some(piece_start) = next else { break };
+            if piece_start < seg_start {
+                pieces.push((piece_start, seg_start - T::one(), f(0)));
+            }
+            let piece_end = seg_end.min(end);
+            pieces.push((piece_start.max(seg_start), piece_end, f(count)));
+            next = (piece_end < end).then(|| piece_end + T::one());
+        }
+        if let Some(piece_start) = next {
+            pieces.push((piece_start, end, f(0)));
+        }
+
+        // Nothing can panic from here on.
+        for &(seg_start, seg_end, count) in &overlapped {
+            self.btree_map.remove(&seg_start);
+            if seg_start < start {
+                self.btree_map.insert(seg_start, (start - T::one(), count));
+            }
+            if end < seg_end {
+                self.btree_map.insert(end + T::one(), (seg_end, count));
+            }
+        }
+        for (piece_start, piece_end, count) in pieces {
+            if count > 0 {
+                self.btree_map.insert(piece_start, (piece_end, count));
+            }
+        }
+        self.merge_around(start, end);
+    }
+
+    // Merges touching segments of equal count from the segment just before `start` through
+    // the segment just after `end`. Segments outside that span were already maximal.
+    fn merge_around(&mut self, start: T, end: T) {
+        let low = match self.btree_map.range(..start).next_back() {
+            Some((&seg_start, _)) => Bound::Included(seg_start),
+            None => Bound::Unbounded,
+        };
+        let high = match end.checked_add(&T::one()) {
+            Some(after) => Bound::Included(after),
+            None => Bound::Unbounded,
+        };
+        let segments: Vec<(T, T, usize)> = self
+            .btree_map
+            .range((low, high))
+            .map(|(&seg_start, &(seg_end, count))| (seg_start, seg_end, count))
+            .collect();
+        let mut merged: Option<(T, T, usize)> = None;
+        for (seg_start, seg_end, count) in segments {
+            match merged {
+                // Must check this in two parts to avoid overflow
+                Some((merged_start, merged_end, merged_count))
+                    if merged_count == count
+                        && merged_end < seg_start
+                        && merged_end + T::one() == seg_start =>
+                {
+                    self.btree_map.remove(&seg_start);
+                    self.btree_map.insert(merged_start, (seg_end, merged_count));
+                    merged = Some((merged_start, seg_end, count));
+                }
+                _ => merged = Some((seg_start, seg_end, count)),
+            }
+        }
+    }
+}
+
+impl<T: Integer> Default for RangeCountMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer> fmt::Debug for RangeCountMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<T: Integer> fmt::Display for RangeCountMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (range, count)) in self.segments().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{range:?}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for RangeCountMap<T> {
+    /// Create a [`RangeCountMap`] that counts, for each integer, how many of the ranges
+    /// contain it. Overlapping, out-of-order, and empty ranges are fine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeCountMap;
+    ///
+    /// let map = RangeCountMap::from_iter([5..=15, 1..=10]);
+    /// assert_eq!(map.to_string(), "1..=4: 1, 5..=10: 2, 11..=15: 1");
+    /// ```
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = RangeInclusive<T>>,
+    {
+        let mut map = RangeCountMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<T: Integer> Extend<RangeInclusive<T>> for RangeCountMap<T> {
+    /// Adds one to the count of every integer in each range.
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = RangeInclusive<T>>,
+    {
+        for range in iter {
+            self.add_range(range, 1);
+        }
+    }
+}
+
+/// An iterator over the maximal segments of a [`RangeCountMap`] and their counts.
+///
+/// This `struct` is created by the [`segments`] method on [`RangeCountMap`]. See
+/// [`segments`]'s documentation for more.
+///
+/// [`segments`]: RangeCountMap::segments
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SegmentsIter<'a, T: Integer> {
+    iter: alloc::collections::btree_map::Iter<'a, T, (T, usize)>,
+}
+
+impl<T: Integer> Iterator for SegmentsIter<'_, T> {
+    type Item = (RangeInclusive<T>, usize);
+
+    fn next(&mut self) -> Option<(RangeInclusive<T>, usize)> {
+        self.iter
+            .next()
+            .map(|(&start, &(end, count))| (start..=end, count))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: Integer> DoubleEndedIterator for SegmentsIter<'_, T> {
+    fn next_back(&mut self) -> Option<(RangeInclusive<T>, usize)> {
+        self.iter
+            .next_back()
+            .map(|(&start, &(end, count))| (start..=end, count))
+    }
+}
+
+impl<T: Integer> ExactSizeIterator for SegmentsIter<'_, T> {}
+
+impl<T: Integer> FusedIterator for SegmentsIter<'_, T> {}
+
+/// A [`SortedDisjoint`](crate::SortedDisjoint) iterator over the integers of a
+/// [`RangeCountMap`] whose count is at least some `k`.
+///
+/// This `struct` is created by the [`ranges_with_count_at_least`] method on
+/// [`RangeCountMap`]. See [`ranges_with_count_at_least`]'s documentation for more.
+///
+/// [`ranges_with_count_at_least`]: RangeCountMap::ranges_with_count_at_least
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RangesWithCountAtLeast<'a, T: Integer> {
+    iter: alloc::collections::btree_map::Iter<'a, T, (T, usize)>,
+    k: usize,
+    pending: Option<RangeInclusive<T>>,
+}
+
+impl<T: Integer> Iterator for RangesWithCountAtLeast<'_, T> {
+    type Item = RangeInclusive<T>;
+
+    // Touching segments that both reach `k` are joined into one range.
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        for (&start, &(end, count)) in self.iter.by_ref() {
+            if count < self.k {
+                continue;
+            }
+            let Some(pending) = self.pending.as_mut() else {
+                self.pending = Some(start..=end);
+                continue;
+            };
+            // Must check this in two parts to avoid overflow
+            if *pending.end() < start && *pending.end() + T::one() == start {
+                *pending = *pending.start()..=end;
+            } else {
+                return self.pending.replace(start..=end);
+            }
+        }
+        self.pending.take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, high) = self.iter.size_hint();
+        let pending = usize::from(self.pending.is_some());
+        (pending, high.and_then(|high| high.checked_add(pending)))
+    }
+}
+
+impl<T: Integer> FusedIterator for RangesWithCountAtLeast<'_, T> {}
+
+impl_sorted_disjoint_and_ops!(<'a, T> RangesWithCountAtLeast<'a, T>);
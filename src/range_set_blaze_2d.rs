@@ -0,0 +1,660 @@
+use alloc::vec::Vec;
+use core::{fmt, iter::FusedIterator, ops::RangeInclusive, slice};
+
+use gen_ops::gen_ops_ex;
+use num_traits::ToPrimitive;
+
+use crate::{Integer, RangeCountMap, RangeSetBlaze, RangesIter};
+
+/// A set of 2-D integer points `(x, y)`, stored as sorted & disjoint X-slabs that each carry a
+/// [`RangeSetBlaze`] of Y values.
+///
+/// Image masks, spreadsheet selections and (time × ID) grids are all unions of rectangles. A
+/// `RangeSetBlaze2D` cuts such a union into vertical slabs: within a slab every column has the
+/// same Y values. Slabs with empty Y sets aren't stored, and touching slabs with equal Y sets are
+/// always merged, so two sets with the same points have the same slabs.
+///
+/// The set operators `|`, `&`, `-`, `^` and `!` work slab by slab with the 1-D
+/// [`SortedDisjoint`](crate::SortedDisjoint) operators.
+///
+/// # Examples
+///
+/// ```
+/// use range_set_blaze::RangeSetBlaze2D;
+///
+/// // Two overlapping rectangles, given as (x range, y range).
+/// let a = RangeSetBlaze2D::from_iter([(0..=9, 0..=9), (5..=14, 5..=14)]);
+/// assert_eq!(a.to_string(), "0..=4: 0..=9; 5..=9: 0..=14; 10..=14: 5..=14");
+/// assert!(a.contains((12, 12)));
+/// assert!(!a.contains((12, 0)));
+/// assert_eq!(a.area(), Some(175));
+///
+/// let b = RangeSetBlaze2D::from_iter([(3..=6, 3..=6)]);
+/// let holes = &a - &b;
+/// assert_eq!(holes.area(), Some(175 - 16));
+/// assert_eq!((&holes & &b).area(), Some(0));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RangeSetBlaze2D<X: Integer, Y: Integer> {
+    // Sorted & disjoint by X range. No Y set is empty, and touching slabs never have equal Y sets.
+    slabs: Vec<(RangeInclusive<X>, RangeSetBlaze<Y>)>,
+}
+
+impl<X: Integer, Y: Integer> RangeSetBlaze2D<X, Y> {
+    /// Makes a new, empty `RangeSetBlaze2D`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let set = RangeSetBlaze2D::<u32, u8>::new();
+    /// assert!(set.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        RangeSetBlaze2D { slabs: Vec::new() }
+    }
+
+    /// Returns `true` if the set contains no points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slabs.is_empty()
+    }
+
+    /// Returns the number of X-slabs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let set = RangeSetBlaze2D::from_iter([(0..=9, 0..=9), (5..=14, 5..=14)]);
+    /// assert_eq!(set.slabs_len(), 3);
+    /// ```
+    #[must_use]
+    pub fn slabs_len(&self) -> usize {
+        self.slabs.len()
+    }
+
+    /// Returns `true` if the set contains the point `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x > X::safe_max_value()` or `y > Y::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O(log *n* + log *m*), where *n* is the number of slabs and *m* is the number of Y ranges
+    /// in the slab found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let set = RangeSetBlaze2D::from_iter([(0..=9, 0..=9), (20..=29, -5..=5)]);
+    /// assert!(set.contains((25, -5)));
+    /// assert!(!set.contains((15, 0)));
+    /// ```
+    #[must_use]
+    pub fn contains(&self, (x, y): (X, Y)) -> bool {
+        assert!(x <= X::safe_max_value(), "x must be <= X::safe_max_value()");
+        assert!(y <= Y::safe_max_value(), "y must be <= Y::safe_max_value()");
+        let index = self
+            .slabs
+            .partition_point(|(x_range, _)| *x_range.end() < x);
+        self.slabs
+            .get(index)
+            .is_some_and(|(x_range, y_set)| *x_range.start() <= x && y_set.contains(y))
+    }
+
+    /// Returns the number of points in the set, the sum over the slabs of the slab's width
+    /// times the length of its Y set.
+    ///
+    /// Returns `None` if the area doesn't fit in a `u128`. This only happens when both lengths
+    /// can be near 2⁶⁴ or one can be near 2¹²⁸, for example, for the complement of the empty
+    /// `RangeSetBlaze2D<u64, u64>`.
+    ///
+    /// # Performance
+    ///
+    /// O(*n*), where *n* is the number of slabs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let set = RangeSetBlaze2D::from_iter([(0u8..=255, 0u8..=255)]);
+    /// assert_eq!(set.area(), Some(65_536));
+    ///
+    /// let full = !RangeSetBlaze2D::<u64, u64>::new();
+    /// assert_eq!(full.area(), None);
+    /// ```
+    #[must_use]
+    pub fn area(&self) -> Option<u128> {
+        self.slabs.iter().try_fold(0u128, |area, (x_range, y_set)| {
+            let width = X::safe_len(x_range).to_u128()?;
+            let height = y_set.len().to_u128()?;
+            area.checked_add(width.checked_mul(height)?)
+        })
+    }
+
+    /// Adds every point of the rectangle `x_range` × `y_range` to the set. An empty range adds
+    /// nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x_range.end() > X::safe_max_value()` or `y_range.end() > Y::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *r*), where *n* is the number of slabs and *r* is the number of Y ranges in the
+    /// slabs that overlap or touch `x_range`. Only those slabs are rebuilt; the *n* is for
+    /// shifting the later slabs along in a `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let mut set = RangeSetBlaze2D::new();
+    /// set.insert_rectangle(0..=9, 0..=4);
+    /// set.insert_rectangle(0..=9, 5..=9);
+    /// assert_eq!(set.to_string(), "0..=9: 0..=9");
+    /// ```
+    pub fn insert_rectangle(&mut self, x_range: RangeInclusive<X>, y_range: RangeInclusive<Y>) {
+        if !Self::is_nonempty_rectangle(&x_range, &y_range) {
+            return;
+        }
+        let (x_start, x_end) = (*x_range.start(), *x_range.end());
+        // Only the slabs that overlap or touch the rectangle can change or merge.
+        // Must check these in two parts to avoid overflow
+        let lo = self.slabs.partition_point(|(slab_x, _)| {
+            *slab_x.end() < x_start && *slab_x.end() + X::one() < x_start
+        });
+        let hi = self.slabs.partition_point(|(slab_x, _)| {
+            *slab_x.start() <= x_end || *slab_x.start() - X::one() <= x_end
+        });
+        let nearby = Self {
+            slabs: self.slabs.drain(lo..hi).collect(),
+        };
+        let merged = &nearby | &Self::from_rectangle(x_range, y_range);
+        self.slabs.splice(lo..lo, merged.slabs);
+    }
+
+    /// Returns an iterator over the X-slabs in order, each as its X range and its set of Y
+    /// values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let set = RangeSetBlaze2D::from_iter([(0..=9, 0..=9), (5..=14, 5..=14)]);
+    /// let (x_range, y_set) = set.slabs().nth(1).unwrap();
+    /// assert_eq!(x_range, 5..=9);
+    /// assert_eq!(y_set.to_string(), "0..=14");
+    /// ```
+    pub fn slabs(&self) -> SlabsIter<'_, X, Y> {
+        SlabsIter {
+            iter: self.slabs.iter(),
+        }
+    }
+
+    /// Returns an iterator over the set as disjoint rectangles `(x_range, y_range)`, sorted by
+    /// the start of `x_range` and then of `y_range`.
+    ///
+    /// Each rectangle is a Y range of a slab, widened across the touching slabs that have that
+    /// exact Y range too. So no rectangle can grow in X without overlapping another, and no
+    /// rectangle can grow in Y at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// // A plus sign.
+    /// let set = RangeSetBlaze2D::from_iter([(0..=8, 3..=5), (3..=5, 0..=8)]);
+    /// let rectangles: Vec<_> = set.rectangles().collect();
+    /// assert_eq!(
+    ///     rectangles,
+    ///     [(0..=2, 3..=5), (3..=5, 0..=8), (6..=8, 3..=5)]
+    /// );
+    ///
+    /// // Slabs with different Y sets can still share a rectangle.
+    /// let set = RangeSetBlaze2D::from_iter([(0..=9, 0..=0), (5..=9, 5..=5)]);
+    /// let rectangles: Vec<_> = set.rectangles().collect();
+    /// assert_eq!(rectangles, [(0..=9, 0..=0), (5..=9, 5..=5)]);
+    /// ```
+    pub fn rectangles(&self) -> RectanglesIter<'_, X, Y> {
+        RectanglesIter {
+            slabs: &self.slabs,
+            index: 0,
+            y_ranges: None,
+        }
+    }
+
+    fn from_rectangle(x_range: RangeInclusive<X>, y_range: RangeInclusive<Y>) -> Self {
+        let mut set = Self::new();
+        if Self::is_nonempty_rectangle(&x_range, &y_range) {
+            set.slabs
+                .push((x_range, RangeSetBlaze::from_iter([y_range])));
+        }
+        set
+    }
+
+    // Checks that the rectangle is in the domain and returns whether it has any points.
+    fn is_nonempty_rectangle(x_range: &RangeInclusive<X>, y_range: &RangeInclusive<Y>) -> bool {
+        assert!(
+            *x_range.end() <= X::safe_max_value(),
+            "end must be <= X::safe_max_value()"
+        );
+        assert!(
+            *y_range.end() <= Y::safe_max_value(),
+            "end must be <= Y::safe_max_value()"
+        );
+        !x_range.is_empty() && !y_range.is_empty()
+    }
+
+    // Appends a slab, merging it into the last slab if they touch and have equal Y sets.
+    fn push_slab(&mut self, x_range: RangeInclusive<X>, y_set: RangeSetBlaze<Y>) {
+        if y_set.is_empty() {
+            return;
+        }
+        if let Some((last_x_range, last_y_set)) = self.slabs.last_mut() {
+            // Must check this in two parts to avoid overflow
+            if *last_x_range.end() < *x_range.start()
+                && *last_x_range.end() + X::one() == *x_range.start()
+                && *last_y_set == y_set
+            {
+                *last_x_range = *last_x_range.start()..=*x_range.end();
+                return;
+            }
+        }
+        self.slabs.push((x_range, y_set));
+    }
+
+    // Sweeps both slab lists together, calling `op` on the Y sets of each X range where neither
+    // changes and both have a slab. Where only one has a slab, `keep_a_only` and `keep_b_only`
+    // say whether its Y set is copied as is or dropped, so `op` never rebuilds a set from one
+    // side. The X ranges outside both inputs are skipped.
+    fn combine<F>(a: &Self, b: &Self, keep_a_only: bool, keep_b_only: bool, op: F) -> Self
+    where
+        F: Fn(&RangeSetBlaze<Y>, &RangeSetBlaze<Y>) -> RangeSetBlaze<Y>,
+    {
+        let mut result = Self::new();
+        let (mut a_slabs, mut b_slabs) = (a.slabs.as_slice(), b.slabs.as_slice());
+        let mut next_x = Some(X::min_value());
+        while let Some(x) = next_x {
+            let (a_next, b_next) = (a_slabs.first(), b_slabs.first());
+            let a_slab = a_next.filter(|(x_range, _)| *x_range.start() <= x);
+            let b_slab = b_next.filter(|(x_range, _)| *x_range.start() <= x);
+            // The piece ends where a slab ends or where the next slab starts.
+            let end = match (a_slab, b_slab) {
+                (None, None) => {
+                    next_x = match (a_next, b_next) {
+                        (None, None) => break,
+                        (Some((a_x, _)), None) => Some(*a_x.start()),
+                        (None, Some((b_x, _))) => Some(*b_x.start()),
+                        (Some((a_x, _)), Some((b_x, _))) => Some(*a_x.start().min(b_x.start())),
+                    };
+                    continue;
+                }
+                (Some((a_x, _)), None) => b_next.map_or(*a_x.end(), |(b_x, _)| {
+                    *a_x.end().min(&(*b_x.start() - X::one()))
+                }),
+                (None, Some((b_x, _))) => a_next.map_or(*b_x.end(), |(a_x, _)| {
+                    *b_x.end().min(&(*a_x.start() - X::one()))
+                }),
+                (Some((a_x, _)), Some((b_x, _))) => *a_x.end().min(b_x.end()),
+            };
+            let y_set = match (a_slab, b_slab) {
+                (Some((_, a_set)), Some((_, b_set))) => op(a_set, b_set),
+                (Some((_, a_set)), None) if keep_a_only => a_set.clone(),
+                (None, Some((_, b_set))) if keep_b_only => b_set.clone(),
+                _ => RangeSetBlaze::new(),
+            };
+            result.push_slab(x..=end, y_set);
+            if a_slab.is_some_and(|(a_x, _)| *a_x.end() == end) {
+                a_slabs = &a_slabs[1..];
+            }
+            if b_slab.is_some_and(|(b_x, _)| *b_x.end() == end) {
+                b_slabs = &b_slabs[1..];
+            }
+            next_x = end.checked_add(&X::one());
+        }
+        result
+    }
+}
+
+impl<X: Integer, Y: Integer> Default for RangeSetBlaze2D<X, Y> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<X: Integer, Y: Integer> fmt::Debug for RangeSetBlaze2D<X, Y> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<X: Integer, Y: Integer> fmt::Display for RangeSetBlaze2D<X, Y> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (x_range, y_set)) in self.slabs().enumerate() {
+            if index > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{x_range:?}: {y_set}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<X: Integer, Y: Integer> FromIterator<(RangeInclusive<X>, RangeInclusive<Y>)>
+    for RangeSetBlaze2D<X, Y>
+{
+    /// Create a [`RangeSetBlaze2D`] from the union of rectangles, each given as
+    /// `(x_range, y_range)`. Overlapping, out-of-order, and empty rectangles are fine.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a rectangle's `x_range.end() > X::safe_max_value()` or
+    /// `y_range.end() > Y::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// It sorts the X edges of the *n* rectangles and then sweeps them once, so it takes
+    /// O(*n* log *n*) plus the time to build each slab's Y set from the rectangles that cover it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let set = RangeSetBlaze2D::from_iter([(5..=9, 0..=9), (0..=4, 0..=9), (3..=2, 0..=0)]);
+    /// assert_eq!(set.to_string(), "0..=9: 0..=9");
+    /// ```
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (RangeInclusive<X>, RangeInclusive<Y>)>,
+    {
+        // Each rectangle starts at the start of its X range and stops just past its end (or
+        // never, if it reaches the top of the domain). Between two edges the rectangles that
+        // cover a column don't change, so one sweep over the sorted edges builds every slab.
+        let mut edges = Vec::new();
+        for (x_range, y_range) in iter {
+            if !Self::is_nonempty_rectangle(&x_range, &y_range) {
+                continue;
+            }
+            let (x_start, x_end) = x_range.into_inner();
+            if let Some(x_stop) = x_end.checked_add(&X::one()) {
+                edges.push((x_stop, false, y_range.clone()));
+            }
+            edges.push((x_start, true, y_range));
+        }
+        edges.sort_unstable_by_key(|(x, _, _)| *x);
+
+        let mut set = RangeSetBlaze2D::new();
+        let mut active = RangeCountMap::new();
+        let mut edges = edges.into_iter().peekable();
+        while let Some((x, is_start, y_range)) = edges.next() {
+            if is_start {
+                active.add_range(y_range, 1);
+            } else {
+                active.remove_range(y_range, 1);
+            }
+            let end = match edges.peek() {
+                Some((next_x, _, _)) if *next_x == x => continue,
+                Some((next_x, _, _)) => *next_x - X::one(),
+                None => X::safe_max_value(),
+            };
+            set.push_slab(x..=end, active.support());
+        }
+        set
+    }
+}
+
+impl<X: Integer, Y: Integer> Extend<(RangeInclusive<X>, RangeInclusive<Y>)>
+    for RangeSetBlaze2D<X, Y>
+{
+    /// Adds every point of each rectangle, given as `(x_range, y_range)`. The rectangles are
+    /// collected as with [`from_iter`](RangeSetBlaze2D::from_iter) and then unioned into the set
+    /// once.
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (RangeInclusive<X>, RangeInclusive<Y>)>,
+    {
+        *self = &*self | Self::from_iter(iter);
+    }
+}
+
+gen_ops_ex!(
+    <X, Y>;
+    types ref RangeSetBlaze2D<X, Y>, ref RangeSetBlaze2D<X, Y> => RangeSetBlaze2D<X, Y>;
+
+    /// Unions the contents of two [`RangeSetBlaze2D`]'s.
+    ///
+    /// Either, neither, or both inputs may be borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let a = RangeSetBlaze2D::from_iter([(0..=9, 0..=4)]);
+    /// let b = RangeSetBlaze2D::from_iter([(0..=9, 5..=9)]);
+    /// let result = &a | &b; // Alternatively, 'a | b'.
+    /// assert_eq!(result.to_string(), "0..=9: 0..=9");
+    /// ```
+    for | call |a: &RangeSetBlaze2D<X, Y>, b: &RangeSetBlaze2D<X, Y>| {
+        RangeSetBlaze2D::combine(a, b, true, true, |a, b| {
+            RangeSetBlaze::from_sorted_disjoint(a.ranges() | b.ranges())
+        })
+    };
+
+    /// Intersects the contents of two [`RangeSetBlaze2D`]'s.
+    ///
+    /// Either, neither, or both inputs may be borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let a = RangeSetBlaze2D::from_iter([(0..=9, 0..=9)]);
+    /// let b = RangeSetBlaze2D::from_iter([(5..=14, 5..=14)]);
+    /// let result = &a & &b; // Alternatively, 'a & b'.
+    /// assert_eq!(result.to_string(), "5..=9: 5..=9");
+    /// ```
+    for & call |a: &RangeSetBlaze2D<X, Y>, b: &RangeSetBlaze2D<X, Y>| {
+        RangeSetBlaze2D::combine(a, b, false, false, |a, b| {
+            RangeSetBlaze::from_sorted_disjoint(a.ranges() & b.ranges())
+        })
+    };
+
+    /// Symmetric difference the contents of two [`RangeSetBlaze2D`]'s.
+    ///
+    /// Either, neither, or both inputs may be borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let a = RangeSetBlaze2D::from_iter([(0..=9, 0..=9)]);
+    /// let b = RangeSetBlaze2D::from_iter([(5..=14, 0..=9)]);
+    /// let result = &a ^ &b; // Alternatively, 'a ^ b'.
+    /// assert_eq!(result.to_string(), "0..=4: 0..=9; 10..=14: 0..=9");
+    /// ```
+    for ^ call |a: &RangeSetBlaze2D<X, Y>, b: &RangeSetBlaze2D<X, Y>| {
+        RangeSetBlaze2D::combine(a, b, true, true, |a, b| {
+            RangeSetBlaze::from_sorted_disjoint(a.ranges() ^ b.ranges())
+        })
+    };
+
+    /// Difference the contents of two [`RangeSetBlaze2D`]'s.
+    ///
+    /// Either, neither, or both inputs may be borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let a = RangeSetBlaze2D::from_iter([(0..=9, 0..=9)]);
+    /// let b = RangeSetBlaze2D::from_iter([(5..=14, 5..=14)]);
+    /// let result = &a - &b; // Alternatively, 'a - b'.
+    /// assert_eq!(result.to_string(), "0..=4: 0..=9; 5..=9: 0..=4");
+    /// ```
+    for - call |a: &RangeSetBlaze2D<X, Y>, b: &RangeSetBlaze2D<X, Y>| {
+        RangeSetBlaze2D::combine(a, b, true, false, |a, b| {
+            RangeSetBlaze::from_sorted_disjoint(a.ranges() - b.ranges())
+        })
+    };
+    where X: Integer, Y: Integer //Where clause for all impl's
+);
+
+gen_ops_ex!(
+    <X, Y>;
+    types ref RangeSetBlaze2D<X, Y> => RangeSetBlaze2D<X, Y>;
+
+    /// Complement the contents of a [`RangeSetBlaze2D`], over every `x` up to
+    /// `X::safe_max_value()` and every `y` up to `Y::safe_max_value()`.
+    ///
+    /// The input may be borrowed or not.
+    ///
+    /// # Examples
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze2D;
+    ///
+    /// let a = RangeSetBlaze2D::from_iter([(0u8..=9, 10u8..=255)]);
+    /// let result = !&a; // Alternatively, '!a'.
+    /// assert_eq!(result.to_string(), "0..=9: 0..=9; 10..=255: 0..=255");
+    /// ```
+    for ! call |a: &RangeSetBlaze2D<X, Y>| {
+        let full = RangeSetBlaze::from_iter([Y::min_value()..=Y::safe_max_value()]);
+        let mut result = RangeSetBlaze2D::new();
+        let mut next_x = Some(X::min_value());
+        for (x_range, y_set) in &a.slabs {
+            // Must check this in two parts to avoid overflow
+            if let Some(x) = next_x.filter(|x| x < x_range.start()) {
+                result.push_slab(x..=*x_range.start() - X::one(), full.clone());
+            }
+            result.push_slab(x_range.clone(), RangeSetBlaze::from_sorted_disjoint(!y_set.ranges()));
+            next_x = x_range.end().checked_add(&X::one());
+        }
+        if let Some(x) = next_x.filter(|x| *x <= X::safe_max_value()) {
+            result.push_slab(x..=X::safe_max_value(), full);
+        }
+        result
+    };
+
+    where X: Integer, Y: Integer //Where clause for all impl's
+);
+
+/// An iterator over the X-slabs of a [`RangeSetBlaze2D`], each as its X range and its set of Y
+/// values.
+///
+/// This `struct` is created by the [`slabs`] method on [`RangeSetBlaze2D`]. See [`slabs`]'s
+/// documentation for more.
+///
+/// [`slabs`]: RangeSetBlaze2D::slabs
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SlabsIter<'a, X: Integer, Y: Integer> {
+    iter: slice::Iter<'a, (RangeInclusive<X>, RangeSetBlaze<Y>)>,
+}
+
+impl<'a, X: Integer, Y: Integer> Iterator for SlabsIter<'a, X, Y> {
+    type Item = (RangeInclusive<X>, &'a RangeSetBlaze<Y>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(x_range, y_set)| (x_range.clone(), y_set))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<X: Integer, Y: Integer> DoubleEndedIterator for SlabsIter<'_, X, Y> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|(x_range, y_set)| (x_range.clone(), y_set))
+    }
+}
+
+impl<X: Integer, Y: Integer> ExactSizeIterator for SlabsIter<'_, X, Y> {}
+
+impl<X: Integer, Y: Integer> FusedIterator for SlabsIter<'_, X, Y> {}
+
+/// An iterator over a [`RangeSetBlaze2D`] as disjoint rectangles `(x_range, y_range)`.
+///
+/// This `struct` is created by the [`rectangles`] method on [`RangeSetBlaze2D`]. See
+/// [`rectangles`]'s documentation for more.
+///
+/// [`rectangles`]: RangeSetBlaze2D::rectangles
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RectanglesIter<'a, X: Integer, Y: Integer> {
+    slabs: &'a [(RangeInclusive<X>, RangeSetBlaze<Y>)],
+    index: usize,
+    // The Y ranges of `slabs[index]` not yet visited.
+    y_ranges: Option<RangesIter<'a, Y>>,
+}
+
+impl<X: Integer, Y: Integer> RectanglesIter<'_, X, Y> {
+    // Returns `true` if the slabs at `index` and `index + 1` touch and both have `y_range` as
+    // one of their ranges.
+    fn shares(&self, index: usize, y_range: &RangeInclusive<Y>) -> bool {
+        let Some([(x_range, y_set), (next_x_range, next_y_set)]) =
+            self.slabs.get(index..=index + 1)
+        else {
+            return false;
+        };
+        let has_y_range = |y_set: &RangeSetBlaze<Y>| {
+            y_set
+                .btree_map
+                .range(..=*y_range.start())
+                .next_back()
+                .is_some_and(|(start, end)| start == y_range.start() && end == y_range.end())
+        };
+        // Must check this in two parts to avoid overflow
+        *x_range.end() < *next_x_range.start()
+            && *x_range.end() + X::one() == *next_x_range.start()
+            && has_y_range(y_set)
+            && has_y_range(next_y_set)
+    }
+}
+
+impl<X: Integer, Y: Integer> Iterator for RectanglesIter<'_, X, Y> {
+    type Item = (RangeInclusive<X>, RangeInclusive<Y>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((x_range, y_set)) = self.slabs.get(self.index) {
+            let mut y_ranges = self.y_ranges.take().unwrap_or_else(|| y_set.ranges());
+            for y_range in y_ranges.by_ref() {
+                // A rectangle that started in an earlier slab was already returned.
+                let continues = self
+                    .index
+                    .checked_sub(1)
+                    .is_some_and(|previous| self.shares(previous, &y_range));
+                if continues {
+                    continue;
+                }
+                let mut last = self.index;
+                while self.shares(last, &y_range) {
+                    last += 1;
+                }
+                let x_end = *self.slabs[last].0.end();
+                self.y_ranges = Some(y_ranges);
+                return Some((*x_range.start()..=x_end, y_range));
+            }
+            self.index += 1;
+        }
+        None
+    }
+}
+
+impl<X: Integer, Y: Integer> FusedIterator for RectanglesIter<'_, X, Y> {}
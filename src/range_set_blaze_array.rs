@@ -0,0 +1,632 @@
+use core::{
+    cmp::{max, min},
+    fmt,
+    iter::FusedIterator,
+    ops::{self, RangeInclusive},
+    slice,
+};
+
+use num_traits::{One, Zero};
+
+#[cfg(feature = "alloc")]
+use crate::BitXOrTee;
+use crate::{
+    BitAndMerge, BitOrMerge, BitSubMerge, Integer, Iter, NotIter, SortedDisjoint, SortedStarts,
+};
+
+/// The error returned when an operation on a [`RangeSetBlazeArray`] would need more
+/// than its fixed number of ranges.
+///
+/// The set is left unchanged when this error is returned.
+///
+/// # Examples
+///
+/// ```
+/// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+///
+/// let mut set = RangeSetBlazeArray::<u8, 2>::new();
+/// set.insert(1)?;
+/// set.insert(3)?;
+/// assert_eq!(set.insert(5), Err(CapacityError));
+/// assert_eq!(set.insert(2), Ok(true)); // merges 1..=1 and 3..=3, so it fits
+/// # Ok::<(), CapacityError>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CapacityError;
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "insufficient capacity for another range")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+/// A set of integers stored as at most `N` sorted & disjoint ranges, kept in place.
+///
+/// Unlike [`RangeSetBlaze`], a `RangeSetBlazeArray` never allocates, so it works on targets
+/// without a global allocator (build with `default-features = false`). Any operation that would
+/// need more than `N` ranges returns a [`CapacityError`] and leaves the set unchanged.
+///
+/// Its [`ranges`] iterator is [`SortedDisjoint`], so it composes with the rest of the
+/// iterator algebra, e.g., `a.ranges() | b.ranges()`.
+///
+/// # Performance
+///
+/// Lookups are O(log N). Insertions and removals are O(N) because they shift ranges within the array.
+///
+/// # Examples
+///
+/// ```
+/// use range_set_blaze::prelude::*;
+/// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+///
+/// let mut a = RangeSetBlazeArray::<i32, 4>::new();
+/// a.ranges_insert(1..=2)?;
+/// a.ranges_insert(5..=100)?;
+/// let b = RangeSetBlazeArray::<i32, 4>::from_sorted_disjoint(CheckSortedDisjoint::from([2..=6]))?;
+///
+/// // Combine them with the SortedDisjoint operators and collect into a new array.
+/// let union = RangeSetBlazeArray::<i32, 4>::from_sorted_disjoint(a.ranges() | b.ranges())?;
+/// assert_eq!(union.ranges().collect::<Vec<_>>(), vec![1..=100]);
+///
+/// let intersection = a.ranges() & b.ranges();
+/// assert_eq!(intersection.to_string(), "2..=2, 5..=6");
+/// # Ok::<(), CapacityError>(())
+/// ```
+///
+/// [`RangeSetBlaze`]: crate::RangeSetBlaze
+/// [`ranges`]: RangeSetBlazeArray::ranges
+#[derive(Clone, Copy)]
+pub struct RangeSetBlazeArray<T: Integer, const N: usize> {
+    len: <T as Integer>::SafeLen,
+    ranges_len: usize,
+    // Only the first `ranges_len` entries are in use.
+    ranges: [(T, T); N],
+}
+
+impl<T: Integer, const N: usize> RangeSetBlazeArray<T, N> {
+    /// Makes a new, empty `RangeSetBlazeArray`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlazeArray;
+    ///
+    /// let set = RangeSetBlazeArray::<i32, 8>::new();
+    /// assert!(set.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        RangeSetBlazeArray {
+            len: <T as Integer>::SafeLen::zero(),
+            ranges_len: 0,
+            ranges: [(T::min_value(), T::min_value()); N],
+        }
+    }
+
+    /// Returns the maximum number of ranges the set can hold, that is, `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlazeArray;
+    ///
+    /// assert_eq!(RangeSetBlazeArray::<u8, 3>::new().capacity(), 3);
+    /// ```
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements in the set.
+    ///
+    /// The number is allowed to be very, very large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<u8, 2>::new();
+    /// set.ranges_insert(0..=255)?;
+    /// assert_eq!(set.len(), 256usize);
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    #[must_use]
+    pub const fn len(&self) -> <T as Integer>::SafeLen {
+        self.len
+    }
+
+    /// Returns the number of sorted & disjoint ranges in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 4>::new();
+    /// set.ranges_insert(10..=20)?;
+    /// set.ranges_insert(30..=40)?;
+    /// set.ranges_insert(15..=35)?;
+    /// assert_eq!(set.ranges_len(), 1);
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    #[must_use]
+    pub const fn ranges_len(&self) -> usize {
+        self.ranges_len
+    }
+
+    /// Returns `true` if the set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 4>::new();
+    /// assert!(set.is_empty());
+    /// set.insert(1)?;
+    /// assert!(!set.is_empty());
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ranges_len == 0
+    }
+
+    /// Clears the set, removing all elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 4>::new();
+    /// set.insert(1)?;
+    /// set.clear();
+    /// assert!(set.is_empty());
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the first element in the set, if any.
+    /// This element is always the minimum of all elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 4>::new();
+    /// assert_eq!(set.first(), None);
+    /// set.ranges_insert(3..=5)?;
+    /// assert_eq!(set.first(), Some(3));
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    #[must_use]
+    pub fn first(&self) -> Option<T> {
+        self.as_slice().first().map(|&(start, _)| start)
+    }
+
+    /// Returns the last element in the set, if any.
+    /// This element is always the maximum of all elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 4>::new();
+    /// assert_eq!(set.last(), None);
+    /// set.ranges_insert(3..=5)?;
+    /// assert_eq!(set.last(), Some(5));
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    #[must_use]
+    pub fn last(&self) -> Option<T> {
+        self.as_slice().last().map(|&(_, end)| end)
+    }
+
+    /// Returns `true` if the set contains an element equal to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 4>::new();
+    /// set.ranges_insert(1..=3)?;
+    /// assert!(set.contains(1));
+    /// assert!(!set.contains(4));
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        assert!(
+            value <= T::safe_max_value(),
+            "value must be <= T::safe_max_value()"
+        );
+        let slice = self.as_slice();
+        let index = slice.partition_point(|&(start, _)| start <= value);
+        index > 0 && value <= slice[index - 1].1
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns `Ok(true)` if the set did not previously contain the value and `Ok(false)` if it did.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapacityError`] if the value would need an (N+1)th range. The set is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 1>::new();
+    /// assert_eq!(set.insert(2), Ok(true));
+    /// assert_eq!(set.insert(2), Ok(false));
+    /// assert_eq!(set.insert(3), Ok(true));
+    /// assert_eq!(set.insert(5), Err(CapacityError));
+    /// assert_eq!(set.len(), 2usize);
+    /// ```
+    pub fn insert(&mut self, value: T) -> Result<bool, CapacityError> {
+        self.ranges_insert(value..=value)
+    }
+
+    /// Adds a range to the set.
+    ///
+    /// Returns `Ok(true)` if the set did not previously contain all the elements of the range.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapacityError`] if the range would need an (N+1)th range. The set is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range's end is greater than `T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 2>::new();
+    /// assert_eq!(set.ranges_insert(2..=5), Ok(true));
+    /// assert_eq!(set.ranges_insert(5..=6), Ok(true));
+    /// assert_eq!(set.ranges_insert(3..=4), Ok(false));
+    /// assert_eq!(set.ranges_insert(10..=11), Ok(true));
+    /// assert_eq!(set.ranges_insert(20..=21), Err(CapacityError));
+    /// assert_eq!(set.len(), 7usize);
+    /// ```
+    pub fn ranges_insert(&mut self, range: RangeInclusive<T>) -> Result<bool, CapacityError> {
+        let (start, end) = range.into_inner();
+        assert!(
+            end <= T::safe_max_value(),
+            "end must be <= T::safe_max_value()"
+        );
+        if end < start {
+            return Ok(false);
+        }
+
+        // Ranges in lo..hi touch or overlap start..=end, so they merge with it.
+        let slice = self.as_slice();
+        let lo = slice.partition_point(|&(_, end_i)| end_i < start && end_i + T::one() < start);
+        let hi = slice.partition_point(|&(start_i, _)| start_i <= end || start_i - T::one() <= end);
+
+        if lo == hi {
+            if self.ranges_len == N {
+                return Err(CapacityError);
+            }
+            self.ranges.copy_within(lo..self.ranges_len, lo + 1);
+            self.ranges[lo] = (start, end);
+            self.ranges_len += 1;
+            self.len += T::safe_len(&(start..=end));
+            return Ok(true);
+        }
+
+        let merged = (
+            min(start, self.ranges[lo].0),
+            max(end, self.ranges[hi - 1].1),
+        );
+        let len_before = self.len;
+        for &(start_i, end_i) in &self.ranges[lo..hi] {
+            self.len -= T::safe_len(&(start_i..=end_i));
+        }
+        self.len += T::safe_len(&(merged.0..=merged.1));
+        self.ranges[lo] = merged;
+        self.ranges.copy_within(hi..self.ranges_len, lo + 1);
+        self.ranges_len -= hi - lo - 1;
+        Ok(self.len != len_before)
+    }
+
+    /// If the set contains an element equal to the value, removes it from the set.
+    ///
+    /// Returns `Ok(true)` if the element was present.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapacityError`] if removing the value would split a range and the set is
+    /// already full. The set is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 1>::new();
+    /// set.ranges_insert(1..=5)?;
+    /// assert_eq!(set.remove(1), Ok(true));
+    /// assert_eq!(set.remove(1), Ok(false));
+    /// assert_eq!(set.remove(3), Err(CapacityError)); // would split 2..=5 into two ranges
+    /// assert_eq!(set.remove(5), Ok(true));
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    pub fn remove(&mut self, value: T) -> Result<bool, CapacityError> {
+        assert!(
+            value <= T::safe_max_value(),
+            "value must be <= T::safe_max_value()"
+        );
+        let index = self
+            .as_slice()
+            .partition_point(|&(start, _)| start <= value);
+        if index == 0 {
+            return Ok(false);
+        }
+        let index = index - 1;
+        let (start, end) = self.ranges[index];
+        if end < value {
+            return Ok(false);
+        }
+
+        if start == end {
+            self.ranges.copy_within(index + 1..self.ranges_len, index);
+            self.ranges_len -= 1;
+        } else if start == value {
+            self.ranges[index].0 = start + T::one();
+        } else if end == value {
+            self.ranges[index].1 = end - T::one();
+        } else {
+            if self.ranges_len == N {
+                return Err(CapacityError);
+            }
+            self.ranges
+                .copy_within(index + 1..self.ranges_len, index + 2);
+            self.ranges[index] = (start, value - T::one());
+            self.ranges[index + 1] = (value + T::one(), end);
+            self.ranges_len += 1;
+        }
+        self.len -= <T as Integer>::SafeLen::one();
+        Ok(true)
+    }
+
+    /// Create a [`RangeSetBlazeArray`] from a [`SortedDisjoint`] iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CapacityError`] if the iterator yields more than `N` ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let a = RangeSetBlazeArray::<i32, 2>::from_sorted_disjoint(CheckSortedDisjoint::from([-10..=-5, 1..=2]))?;
+    /// assert_eq!(a.ranges().to_string(), "-10..=-5, 1..=2");
+    ///
+    /// let b = RangeSetBlazeArray::<i32, 1>::from_sorted_disjoint(CheckSortedDisjoint::from([-10..=-5, 1..=2]));
+    /// assert_eq!(b.err(), Some(CapacityError));
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    pub fn from_sorted_disjoint<I>(iter: I) -> Result<Self, CapacityError>
+    where
+        I: SortedDisjoint<T>,
+    {
+        let mut result = Self::new();
+        for range in iter {
+            if result.ranges_len == N {
+                return Err(CapacityError);
+            }
+            result.len += T::safe_len(&range);
+            result.ranges[result.ranges_len] = range.into_inner();
+            result.ranges_len += 1;
+        }
+        Ok(result)
+    }
+
+    /// Gets a [`SortedDisjoint`] iterator over the ranges in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 4>::new();
+    /// set.ranges_insert(10..=20)?;
+    /// set.ranges_insert(15..=25)?;
+    /// set.ranges_insert(30..=40)?;
+    ///
+    /// let mut ranges = set.ranges();
+    /// assert_eq!(ranges.next(), Some(10..=25));
+    /// assert_eq!(ranges.next(), Some(30..=40));
+    /// assert_eq!(ranges.next(), None);
+    ///
+    /// assert_eq!((!set.ranges()).to_string(), "-2147483648..=9, 26..=29, 41..=2147483647");
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    pub fn ranges(&self) -> ArrayRangesIter<'_, T> {
+        ArrayRangesIter {
+            iter: self.as_slice().iter(),
+        }
+    }
+
+    /// Gets an iterator that visits the integer elements in the set in ascending and/or descending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{CapacityError, RangeSetBlazeArray};
+    ///
+    /// let mut set = RangeSetBlazeArray::<i32, 4>::new();
+    /// set.ranges_insert(1..=3)?;
+    /// let mut set_iter = set.iter();
+    /// assert_eq!(set_iter.next(), Some(1));
+    /// assert_eq!(set_iter.next_back(), Some(3));
+    /// assert_eq!(set_iter.next(), Some(2));
+    /// assert_eq!(set_iter.next_back(), None);
+    /// # Ok::<(), CapacityError>(())
+    /// ```
+    pub fn iter(&self) -> Iter<T, ArrayRangesIter<'_, T>> {
+        Iter::new(self.ranges(), Some(self.len))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[(T, T)] {
+        &self.ranges[..self.ranges_len]
+    }
+}
+
+impl<T: Integer, const N: usize> Default for RangeSetBlazeArray<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer, const N: usize> PartialEq for RangeSetBlazeArray<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Integer, const N: usize> Eq for RangeSetBlazeArray<T, N> {}
+
+impl<T: Integer, const N: usize> fmt::Debug for RangeSetBlazeArray<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (start, end)) in self.as_slice().iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{start}..={end}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Integer, const N: usize> fmt::Display for RangeSetBlazeArray<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl<'a, T: Integer, const N: usize> IntoIterator for &'a RangeSetBlazeArray<T, N> {
+    type Item = T;
+    type IntoIter = Iter<T, ArrayRangesIter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator that visits the ranges in the [`RangeSetBlazeArray`],
+/// i.e., the integers as sorted & disjoint ranges.
+///
+/// This `struct` is created by the [`ranges`] method on [`RangeSetBlazeArray`]. See [`ranges`]'s
+/// documentation for more.
+///
+/// [`ranges`]: RangeSetBlazeArray::ranges
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ArrayRangesIter<'a, T: Integer> {
+    iter: slice::Iter<'a, (T, T)>,
+}
+
+// ArrayRangesIter walks a sorted & disjoint slice, so it is SortedDisjoint
+impl<T: Integer> SortedStarts<T> for ArrayRangesIter<'_, T> {}
+impl<T: Integer> SortedDisjoint<T> for ArrayRangesIter<'_, T> {}
+
+impl<T: Integer> ExactSizeIterator for ArrayRangesIter<'_, T> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<T: Integer> FusedIterator for ArrayRangesIter<'_, T> {}
+
+impl<T: Integer> Iterator for ArrayRangesIter<'_, T> {
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|&(start, end)| start..=end)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: Integer> DoubleEndedIterator for ArrayRangesIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|&(start, end)| start..=end)
+    }
+}
+
+impl<T: Integer> ops::Not for ArrayRangesIter<'_, T> {
+    type Output = NotIter<T, Self>;
+
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+impl<T: Integer, I> ops::BitOr<I> for ArrayRangesIter<'_, T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitOrMerge<T, Self, I>;
+
+    fn bitor(self, other: I) -> Self::Output {
+        SortedDisjoint::union(self, other)
+    }
+}
+
+impl<T: Integer, I> ops::Sub<I> for ArrayRangesIter<'_, T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitSubMerge<T, Self, I>;
+
+    fn sub(self, other: I) -> Self::Output {
+        SortedDisjoint::difference(self, other)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Integer, I> ops::BitXor<I> for ArrayRangesIter<'_, T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitXOrTee<T, Self, I>;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn bitxor(self, other: I) -> Self::Output {
+        SortedDisjoint::symmetric_difference(self, other)
+    }
+}
+
+impl<T: Integer, I> ops::BitAnd<I> for ArrayRangesIter<'_, T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitAndMerge<T, Self, I>;
+
+    fn bitand(self, other: I) -> Self::Output {
+        SortedDisjoint::intersection(self, other)
+    }
+}
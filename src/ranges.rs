@@ -1,14 +1,14 @@
-use alloc::collections::btree_map;
 use core::{
     iter::FusedIterator,
     ops::{self, RangeInclusive},
 };
 
+use alloc::vec;
 use itertools::Itertools;
 
 use crate::{
-    BitAndMerge, BitOrMerge, BitSubMerge, BitXOr, BitXOrTee, Integer, NotIter, SortedDisjoint,
-    SortedStarts,
+    range_map, BitAndMerge, BitOrMerge, BitSubMerge, BitXOr, BitXOrTee, Integer, NotIter,
+    SortedDisjoint, SortedStarts,
 };
 
 /// An iterator that visits the ranges in the [`RangeSetBlaze`],
@@ -22,7 +22,7 @@ use crate::{
 #[derive(Clone, Debug)]
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct RangesIter<'a, T: Integer> {
-    pub(crate) iter: btree_map::Iter<'a, T, T>,
+    pub(crate) iter: range_map::Iter<'a, T, T>,
 }
 
 impl<'a, T: Integer> AsRef<RangesIter<'a, T>> for RangesIter<'a, T> {
@@ -76,7 +76,7 @@ impl<T: Integer> DoubleEndedIterator for RangesIter<'_, T> {
 /// [`into_ranges`]: crate::RangeSetBlaze::into_ranges
 #[derive(Debug)]
 pub struct IntoRangesIter<T: Integer> {
-    pub(crate) iter: alloc::collections::btree_map::IntoIter<T, T>,
+    pub(crate) iter: range_map::IntoIter<T, T>,
 }
 
 impl<T: Integer> SortedStarts<T> for IntoRangesIter<T> {}
@@ -222,3 +222,96 @@ where
         SortedDisjoint::intersection(self, other)
     }
 }
+
+/// A [`SortedDisjoint`] iterator over ranges that were changed, for example, the ranges
+/// newly added by [`RangeSetBlaze::ranges_insert_diff`].
+///
+/// This `struct` is created by [`RangeSetBlaze::ranges_insert_diff`], [`RangeSetBlaze::ranges_remove_diff`],
+/// and [`PersistentRangeSetBlaze::diff`]. See their documentation for more.
+///
+/// [`RangeSetBlaze::ranges_insert_diff`]: crate::RangeSetBlaze::ranges_insert_diff
+/// [`RangeSetBlaze::ranges_remove_diff`]: crate::RangeSetBlaze::ranges_remove_diff
+/// [`PersistentRangeSetBlaze::diff`]: crate::PersistentRangeSetBlaze::diff
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ChangedRanges<T: Integer> {
+    pub(crate) iter: vec::IntoIter<RangeInclusive<T>>,
+}
+
+impl<T: Integer> SortedStarts<T> for ChangedRanges<T> {}
+impl<T: Integer> SortedDisjoint<T> for ChangedRanges<T> {}
+
+impl<T: Integer> ExactSizeIterator for ChangedRanges<T> {}
+
+impl<T: Integer> FusedIterator for ChangedRanges<T> {}
+
+impl<T: Integer> Iterator for ChangedRanges<T> {
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: Integer> DoubleEndedIterator for ChangedRanges<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T: Integer> ops::Not for ChangedRanges<T> {
+    type Output = NotIter<T, Self>;
+
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+impl<T: Integer, I> ops::BitOr<I> for ChangedRanges<T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitOrMerge<T, Self, I>;
+
+    fn bitor(self, other: I) -> Self::Output {
+        SortedDisjoint::union(self, other)
+    }
+}
+
+impl<T: Integer, I> ops::Sub<I> for ChangedRanges<T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitSubMerge<T, Self, I>;
+
+    fn sub(self, other: I) -> Self::Output {
+        SortedDisjoint::difference(self, other)
+    }
+}
+
+impl<T: Integer, I> ops::BitXor<I> for ChangedRanges<T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitXOrTee<T, Self, I>;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn bitxor(self, other: I) -> Self::Output {
+        SortedDisjoint::symmetric_difference(self, other)
+    }
+}
+
+impl<T: Integer, I> ops::BitAnd<I> for ChangedRanges<T>
+where
+    I: SortedDisjoint<T>,
+{
+    type Output = BitAndMerge<T, Self, I>;
+
+    fn bitand(self, other: I) -> Self::Output {
+        SortedDisjoint::intersection(self, other)
+    }
+}
@@ -1,61 +1,108 @@
-#![cfg(feature = "rog-experimental")]
-#![deprecated(
-    note = "The rog ('range or gap') module is experimental and may be changed or removed in future versions.
-    Changes may not be reflected in the semantic versioning."
-)]
+use core::{
+    iter::FusedIterator,
+    ops::{Bound, RangeBounds, RangeInclusive},
+};
 
-use alloc::collections::btree_map;
-use alloc::vec::Vec;
-use core::ops::{Bound, RangeBounds, RangeInclusive};
+use crate::{range_map, Integer, RangeSetBlaze};
 
-use crate::{Integer, RangeSetBlaze};
-
-/// Experimental: An iterator over [`Rog`]s (ranges or gaps) in a [`RangeSetBlaze`].
+/// An iterator over [`Rog`]s (ranges or gaps) in a [`RangeSetBlaze`].
+///
+/// This `struct` is created by the [`rogs`] and [`rogs_range`] methods on [`RangeSetBlaze`].
+/// See [`rogs_range`]'s documentation for more.
 ///
-/// See [`RangeSetBlaze::rogs_range`] for more information.
+/// [`rogs`]: RangeSetBlaze::rogs
+/// [`rogs_range`]: RangeSetBlaze::rogs_range
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct RogsIter<'a, T: Integer> {
-    end_in: T,
-    next_rog: Option<Rog<T>>,
-    final_gap_start: Option<T>,
-    btree_map_iter: btree_map::Range<'a, T, T>,
+    // the ranges of the set that overlap the requested range, unclipped
+    btree_map_iter: range_map::Range<'a, T, T>,
+    // the requested range, clipped, that is not yet visited
+    front: T,
+    back: T,
+    // a (clipped) range taken from `btree_map_iter` but not yet returned
+    front_range: Option<(T, T)>,
+    back_range: Option<(T, T)>,
+    done: bool,
+}
+
+impl<T: Integer> RogsIter<'_, T> {
+    fn clip(&self, (start, end): (&T, &T)) -> (T, T) {
+        ((*start).max(self.front), (*end).min(self.back))
+    }
 }
 
+impl<T: Integer> FusedIterator for RogsIter<'_, T> {}
+
 impl<T: Integer> Iterator for RogsIter<'_, T> {
     type Item = Rog<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(rog) = self.next_rog.take() {
-            return Some(rog);
+        if self.done {
+            return None;
+        }
+        let range = match self.front_range.take() {
+            Some(range) => Some(range),
+            None => match self.btree_map_iter.next() {
+                Some(range) => Some(self.clip(range)),
+                None => self.back_range.take(),
+            },
         };
-
-        if let Some((start_el, end_el)) = self.btree_map_iter.next() {
-            if self.end_in < *start_el {
-                self.btree_map_iter = btree_map::Range::default();
-            } else {
-                debug_assert!(self.final_gap_start.is_some()); // final_gap_start should be Some if we're in this branch
-                debug_assert!(self.final_gap_start.unwrap() < *start_el); // so -1 is safe
-                let result = Rog::Gap(self.final_gap_start.unwrap()..=*start_el - T::one());
-                if end_el < &self.end_in {
-                    self.next_rog = Some(Rog::Range(*start_el..=*end_el));
-                    debug_assert!(end_el < &self.end_in); // so +1 is safe
-                    self.final_gap_start = Some(*end_el + T::one());
-                } else {
-                    self.next_rog = Some(Rog::Range(*start_el..=self.end_in));
-                    self.final_gap_start = None;
-                }
-                return Some(result);
-            }
+        let Some((start, end)) = range else {
+            self.done = true;
+            return Some(Rog::Gap(self.front..=self.back));
         };
+        if self.front < start {
+            // return the gap before the range and keep the range for later
+            let gap = Rog::Gap(self.front..=start - T::one());
+            self.front = start;
+            self.front_range = Some((start, end));
+            return Some(gap);
+        }
+        debug_assert!(self.front == start); // real assert
+        if end == self.back {
+            self.done = true;
+        } else {
+            self.front = end + T::one(); // end < back, so +1 is safe
+        }
+        Some(Rog::Range(start..=end))
+    }
+}
 
-        if let Some(gap_start) = self.final_gap_start.take() {
-            return Some(Rog::Gap(gap_start..=self.end_in));
+impl<T: Integer> DoubleEndedIterator for RogsIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let range = match self.back_range.take() {
+            Some(range) => Some(range),
+            None => match self.btree_map_iter.next_back() {
+                Some(range) => Some(self.clip(range)),
+                None => self.front_range.take(),
+            },
         };
-
-        None
+        let Some((start, end)) = range else {
+            self.done = true;
+            return Some(Rog::Gap(self.front..=self.back));
+        };
+        if end < self.back {
+            // return the gap after the range and keep the range for later
+            let gap = Rog::Gap(end + T::one()..=self.back);
+            self.back = end;
+            self.back_range = Some((start, end));
+            return Some(gap);
+        }
+        debug_assert!(end == self.back); // real assert
+        if start == self.front {
+            self.done = true;
+        } else {
+            self.back = start - T::one(); // start > front, so -1 is safe
+        }
+        Some(Rog::Range(start..=end))
     }
 }
 
-/// Experimental: Represents an range or gap in a [`RangeSetBlaze`].
+/// Represents an range or gap in a [`RangeSetBlaze`].
 ///
 /// See [`RangeSetBlaze::rogs_range`] and [`RangeSetBlaze::rogs_get`] for more information.
 ///
@@ -68,8 +115,7 @@ impl<T: Integer> Iterator for RogsIter<'_, T> {
 /// assert_eq!(range_set_blaze.rogs_get(2), Rog::Range(1..=3));
 /// assert_eq!(range_set_blaze.rogs_get(4), Rog::Gap(4..=2_147_483_647));
 /// ```
-
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Rog<T: Integer> {
     /// A range of integers in a [`RangeSetBlaze`].
     Range(RangeInclusive<T>),
@@ -108,6 +154,24 @@ impl<T: Integer> Rog<T> {
         }
     }
 
+    /// Returns the number of integers in a [`Rog`] (range or gap).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::Rog;
+    /// assert_eq!(Rog::Gap(1u8..=3).len(), 3usize);
+    /// assert_eq!(Rog::Range(i8::MIN..=i8::MAX).len(), 256usize);
+    /// ```
+    #[must_use]
+    #[allow(clippy::len_without_is_empty)] // a range or gap is never empty
+    pub fn len(&self) -> <T as Integer>::SafeLen {
+        match self {
+            Rog::Range(r) => T::safe_len(r),
+            Rog::Gap(r) => T::safe_len(r),
+        }
+    }
+
     /// Returns `true` if the [`Rog`] (range or gap) contains the given integer.
     ///
     /// # Examples
@@ -126,7 +190,7 @@ impl<T: Integer> Rog<T> {
 }
 
 impl<T: Integer> RangeSetBlaze<T> {
-    /// Experimental: Returns the [`Rog`] (range or gap) containing the given integer. If the
+    /// Returns the [`Rog`] (range or gap) containing the given integer. If the
     /// [`RangeSetBlaze`] contains the integer, returns a [`Rog::Range`]. If the
     /// [`RangeSetBlaze`] does not contain the integer, returns a [`Rog::Gap`].
     ///
@@ -134,13 +198,6 @@ impl<T: Integer> RangeSetBlaze<T> {
     ///
     /// Panics if the `value > T::safe_max_value()`.
     ///
-    /// # Enabling
-    ///
-    /// This method is experimental and must be enabled with the `rog-experimental` feature.
-    /// ```bash
-    /// cargo add range-set-blaze --features "rog-experimental"
-    /// ```
-    ///
     /// # Examples
     ///
     /// ```
@@ -181,33 +238,82 @@ impl<T: Integer> RangeSetBlaze<T> {
         }
     }
 
-    /// Experimental: Constructs an iterator over a sub-range of [`Rog`]'s (ranges and gaps) in the [`RangeSetBlaze`].
+    /// Returns the [`Rog`] (range or gap) containing the given integer, together with the
+    /// [`Rog`]s just before and just after it. Ranges and gaps alternate, so if the middle
+    /// [`Rog`] is a range, its neighbors are gaps, and vice versa. A neighbor is `None` when the
+    /// middle [`Rog`] reaches `T::min_value()` or `T::safe_max_value()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `value > T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{RangeSetBlaze, Rog};
+    ///
+    /// let set = RangeSetBlaze::from_iter([10u8..=20, 30..=40]);
+    /// assert_eq!(
+    ///     set.rogs_get_neighbors(25),
+    ///     (
+    ///         Some(Rog::Range(10..=20)),
+    ///         Rog::Gap(21..=29),
+    ///         Some(Rog::Range(30..=40))
+    ///     )
+    /// );
+    /// assert_eq!(
+    ///     set.rogs_get_neighbors(50),
+    ///     (Some(Rog::Range(30..=40)), Rog::Gap(41..=255), None)
+    /// );
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn rogs_get_neighbors(&self, value: T) -> (Option<Rog<T>>, Rog<T>, Option<Rog<T>>) {
+        let rog = self.rogs_get(value);
+        let before = (rog.start() > T::min_value()).then(|| self.rogs_get(rog.start() - T::one()));
+        let after = (rog.end() < T::safe_max_value()).then(|| self.rogs_get(rog.end() + T::one()));
+        (before, rog, after)
+    }
+
+    /// Returns an iterator over all the [`Rog`]s (ranges and gaps) from `T::min_value()` to
+    /// `T::safe_max_value()`. This is the same as `rogs_range(..)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::{RangeSetBlaze, Rog};
+    ///
+    /// let set = RangeSetBlaze::from_iter([10u8..=20, 30..=255]);
+    /// assert_eq!(
+    ///     set.rogs().collect::<Vec<_>>(),
+    ///     vec![Rog::Gap(0..=9), Rog::Range(10..=20), Rog::Gap(21..=29), Rog::Range(30..=255)]
+    /// );
+    /// assert_eq!(set.rogs().next_back(), Some(Rog::Range(30..=255)));
+    /// ```
+    pub fn rogs(&self) -> RogsIter<'_, T> {
+        self.rogs_range(..)
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of [`Rog`]'s (ranges and gaps) in the [`RangeSetBlaze`].
     /// The simplest way is to use the range syntax `min..=max`, thus `range(min..=max)` will
     /// yield elements from min (inclusive) to max (inclusive).
     /// The range may also be entered as `(Bound<T>, Bound<T>)`, so for example
     /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
     /// range from 4 to 10.
     ///
+    /// An empty range, for example, `4..4` or `(Excluded(255u8), Unbounded)`, yields no [`Rog`]s.
+    ///
     /// # Panics
     ///
     /// Panics if range `start > end`.
     ///
     /// Panics if range `start == end` and both bounds are `Excluded`.
     ///
-    /// Panics if range `end > T::safe_max_value()`.
-    ///
-    /// # Enabling
-    ///
-    /// This method is experimental and must be enabled with the `rog-experimental` feature.
-    /// ```bash
-    /// cargo add range-set-blaze --features "rog-experimental"
-    /// ```
-
+    /// Panics if range inclusive `end > T::safe_max_value()`.
     ///
     /// # Examples
     ///
-    /// ```rangesetblaze::new()//
-    /// use range_set_blaze::{RangeSetBlaze, Rog;};
+    /// ```
+    /// use range_set_blaze::{RangeSetBlaze, Rog};
     /// use core::ops::Bound::Included;
     ///
     /// let mut set = RangeSetBlaze::new();
@@ -225,6 +331,11 @@ impl<T: Integer> RangeSetBlaze<T> {
     ///     a.rogs_range(-5..=8).collect::<Vec<_>>(),
     ///     vec![Rog::Gap(-5..=0), Rog::Range(1..=6), Rog::Gap(7..=8)]
     /// );
+    /// assert_eq!(
+    ///     a.rogs_range(4..13).rev().collect::<Vec<_>>(),
+    ///     vec![Rog::Range(11..=12), Rog::Gap(7..=10), Rog::Range(4..=6)]
+    /// );
+    /// assert_eq!(a.rogs_range(4..4).next(), None);
     ///
     /// let empty = RangeSetBlaze::<u8>::new();
     /// assert_eq!(
@@ -232,117 +343,75 @@ impl<T: Integer> RangeSetBlaze<T> {
     ///     vec![Rog::Gap(0..=255)]
     /// );
     /// ```
-    pub fn rogs_range<R>(&self, range: R) -> RogsIter<T>
+    pub fn rogs_range<R>(&self, range: R) -> RogsIter<'_, T>
     where
         R: RangeBounds<T>,
     {
-        let (start_in, end_in) = extract_range(range);
-
-        let mut before = self.btree_map.range(..=start_in).rev();
-        if let Some((_, end_before)) = before.next() {
-            if end_before < &start_in {
-                // case 1: range doesn't touch the before range
-                RogsIter {
-                    end_in,
-                    next_rog: None,
-                    final_gap_start: Some(start_in),
-                    btree_map_iter: self.btree_map.range(start_in..),
-                }
-            } else if end_before < &end_in {
-                // case 2: the range touches and extends beyond the before range
-                debug_assert!(*end_before < end_in); // so +1 is safe
-                debug_assert!(start_in <= *end_before); // so +1 is safe
-                RogsIter {
-                    end_in,
-                    next_rog: Some(Rog::Range(start_in..=*end_before)),
-                    final_gap_start: Some(*end_before + T::one()),
-                    btree_map_iter: self.btree_map.range(start_in + T::one()..),
-                }
-            } else {
-                // case 3 the range is completely contained in the before range
-                RogsIter {
-                    end_in,
-                    next_rog: Some(Rog::Range(start_in..=end_in)),
-                    final_gap_start: None,
-                    btree_map_iter: btree_map::Range::default(),
-                }
-            }
-        } else {
-            // case 4: there is no before range
-            RogsIter {
-                end_in,
-                next_rog: None,
-                final_gap_start: Some(start_in),
-                btree_map_iter: self.btree_map.range(start_in..),
-            }
-        }
-    }
-
-    /// Used internally to test `rogs_range`.
-    #[doc(hidden)]
-    pub fn _rogs_range_slow<R>(&self, range: R) -> Vec<Rog<T>>
-    where
-        R: RangeBounds<T>,
-    {
-        let (start_in, end_in) = extract_range(range);
-        let rsb_in = RangeSetBlaze::from_iter([start_in..=end_in]);
-        let ranges = &rsb_in & self;
-        let gaps = rsb_in - self;
-        let ranges = ranges.ranges().map(|r| Rog::Range(r));
-        let gaps = gaps.ranges().map(|r| Rog::Gap(r));
-        let mut result = ranges.chain(gaps).collect::<Vec<Rog<T>>>();
-        result.sort_by_key(|a| a.start());
-        result
-    }
+        let Some((start_in, end_in)) = extract_range(range) else {
+            return RogsIter {
+                btree_map_iter: range_map::Range::default(),
+                front: T::min_value(),
+                back: T::min_value(),
+                front_range: None,
+                back_range: None,
+                done: true,
+            };
+        };
 
-    /// Used internally to test `rogs_get`.
-    #[doc(hidden)]
-    pub fn rogs_get_slow(&self, value: T) -> Rog<T> {
-        assert!(
-            value <= T::safe_max_value(),
-            "value must be <= T::safe_max_value()"
-        );
-        let all_rogs = self._rogs_range_slow(..);
-        for rog in all_rogs {
-            if rog.contains(value) {
-                return rog;
-            }
+        // start with the range that contains start_in, if any
+        let first_start = match self.btree_map.range(..=start_in).next_back() {
+            Some((start, end)) if start_in <= *end => *start,
+            _ => start_in,
+        };
+        RogsIter {
+            btree_map_iter: self.btree_map.range(first_start..=end_in),
+            front: start_in,
+            back: end_in,
+            front_range: None,
+            back_range: None,
+            done: false,
         }
-        unreachable!("value must be in something");
     }
 }
 
-fn extract_range<T: Integer, R>(range: R) -> (T, T)
+// Returns the inclusive start and end of the range, or `None` if the range is empty.
+fn extract_range<T: Integer, R>(range: R) -> Option<(T, T)>
 where
     R: RangeBounds<T>,
 {
-    let start = match range.start_bound() {
-        Bound::Included(n) => *n,
-        Bound::Excluded(n) => {
-            assert!(
-                *n < T::safe_max_value(),
-                "inclusive start must be <= T::max_safe_value()"
-            );
-            *n + T::one()
+    match (range.start_bound(), range.end_bound()) {
+        (Bound::Excluded(start), Bound::Excluded(end)) => {
+            assert!(start < end, "start must be < end when both are excluded");
         }
-        Bound::Unbounded => T::min_value(),
-    };
+        (
+            Bound::Included(start) | Bound::Excluded(start),
+            Bound::Included(end) | Bound::Excluded(end),
+        ) => assert!(start <= end, "start must be <= end"),
+        _ => {}
+    }
     let end = match range.end_bound() {
         Bound::Included(n) => *n,
         Bound::Excluded(n) => {
-            assert!(
-                *n > T::min_value(),
-                "inclusive end must be >= T::min_value()"
-            );
+            if *n == T::min_value() {
+                return None;
+            }
             *n - T::one()
         }
         Bound::Unbounded => T::safe_max_value(),
     };
-    assert!(start <= end, "start must be <= end");
     assert!(
         end <= T::safe_max_value(),
         "end must be <= T::safe_max_value()"
     );
-
-    (start, end)
+    let start = match range.start_bound() {
+        Bound::Included(n) => *n,
+        Bound::Excluded(n) => {
+            if *n >= end {
+                return None;
+            }
+            *n + T::one()
+        }
+        Bound::Unbounded => T::min_value(),
+    };
+    (start <= end).then_some((start, end))
 }
@@ -0,0 +1,238 @@
+use core::ops::RangeInclusive;
+
+use num_traits::Zero;
+
+#[cfg(feature = "alloc")]
+use crate::RangeSetBlaze;
+use crate::{Integer, SortedDisjoint};
+
+/// The lengths of two sets and of their intersection, as computed by [`overlap_lens`].
+pub(crate) struct OverlapLens<T: Integer> {
+    pub(crate) left: <T as Integer>::SafeLen,
+    pub(crate) right: <T as Integer>::SafeLen,
+    pub(crate) both: <T as Integer>::SafeLen,
+}
+
+impl<T: Integer> OverlapLens<T> {
+    // `left + right - both`, written so that nothing overflows.
+    pub(crate) fn union(&self) -> <T as Integer>::SafeLen {
+        self.left + (self.right - self.both)
+    }
+
+    pub(crate) fn symmetric_difference(&self) -> <T as Integer>::SafeLen {
+        (self.left - self.both) + (self.right - self.both)
+    }
+
+    // `numerator / denominator`, where 0 / 0 is 1.0 because it comes from a subset or equal sets.
+    pub(crate) fn ratio(
+        numerator: <T as Integer>::SafeLen,
+        denominator: <T as Integer>::SafeLen,
+    ) -> f64 {
+        if denominator.is_zero() {
+            1.0
+        } else {
+            T::safe_len_to_f64(numerator) / T::safe_len_to_f64(denominator)
+        }
+    }
+}
+
+// Returns the next range of `iter`, adding its length to `len`.
+fn next_counting<T, I>(iter: &mut I, len: &mut <T as Integer>::SafeLen) -> Option<RangeInclusive<T>>
+where
+    T: Integer,
+    I: Iterator<Item = RangeInclusive<T>>,
+{
+    let range = iter.next();
+    if let Some(range) = &range {
+        *len += T::safe_len(range);
+    }
+    range
+}
+
+/// Computes the lengths of two [`SortedDisjoint`] iterators and of their intersection in one
+/// merged pass, without allocating.
+pub(crate) fn overlap_lens<T, L, R>(mut left: L, mut right: R) -> OverlapLens<T>
+where
+    T: Integer,
+    L: SortedDisjoint<T>,
+    R: SortedDisjoint<T>,
+{
+    let mut lens = OverlapLens {
+        left: <T as Integer>::SafeLen::zero(),
+        right: <T as Integer>::SafeLen::zero(),
+        both: <T as Integer>::SafeLen::zero(),
+    };
+    let mut left_range = next_counting(&mut left, &mut lens.left);
+    let mut right_range = next_counting(&mut right, &mut lens.right);
+    while let (Some(l), Some(r)) = (&left_range, &right_range) {
+        let start = *l.start().max(r.start());
+        let end = *l.end().min(r.end());
+        if start <= end {
+            lens.both += T::safe_len(&(start..=end));
+        }
+        // The range that ends first can't overlap anything else.
+        if l.end() < r.end() {
+            left_range = next_counting(&mut left, &mut lens.left);
+        } else {
+            right_range = next_counting(&mut right, &mut lens.right);
+        }
+    }
+    while left_range.is_some() {
+        left_range = next_counting(&mut left, &mut lens.left);
+    }
+    while right_range.is_some() {
+        right_range = next_counting(&mut right, &mut lens.right);
+    }
+    lens
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Returns the number of elements in the intersection of two sets, without allocating.
+    ///
+    /// See [`SortedDisjoint::intersection_len`] for the same on lazy set expressions.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two sets. It makes one
+    /// merged pass over the ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=10, 20..=29]);
+    /// let b = RangeSetBlaze::from_iter([5..=24]);
+    /// assert_eq!(a.intersection_len(&b), 11);
+    /// ```
+    #[must_use]
+    pub fn intersection_len(&self, other: &RangeSetBlaze<T>) -> <T as Integer>::SafeLen {
+        self.ranges().intersection_len(other.ranges())
+    }
+
+    /// Returns the number of elements in the union of two sets, without allocating.
+    ///
+    /// See [`SortedDisjoint::union_len`] for the same on lazy set expressions.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two sets. It makes one
+    /// merged pass over the ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=10, 20..=29]);
+    /// let b = RangeSetBlaze::from_iter([5..=24]);
+    /// assert_eq!(a.union_len(&b), 29);
+    /// ```
+    #[must_use]
+    pub fn union_len(&self, other: &RangeSetBlaze<T>) -> <T as Integer>::SafeLen {
+        self.ranges().union_len(other.ranges())
+    }
+
+    /// Returns the number of elements in exactly one of two sets, without allocating.
+    ///
+    /// See [`SortedDisjoint::symmetric_difference_len`] for the same on lazy set expressions.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two sets. It makes one
+    /// merged pass over the ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=10, 20..=29]);
+    /// let b = RangeSetBlaze::from_iter([5..=24]);
+    /// assert_eq!(a.symmetric_difference_len(&b), 18);
+    /// ```
+    #[must_use]
+    pub fn symmetric_difference_len(&self, other: &RangeSetBlaze<T>) -> <T as Integer>::SafeLen {
+        self.ranges().symmetric_difference_len(other.ranges())
+    }
+
+    /// Returns the Jaccard index of two sets, that is, the size of their intersection divided
+    /// by the size of their union. Two empty sets have a Jaccard index of 1.0.
+    ///
+    /// See [`SortedDisjoint::jaccard`] for the same on lazy set expressions.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two sets. It makes one
+    /// merged pass over the ranges and doesn't allocate. For large integer types, the result
+    /// may lose precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=10, 20..=29]);
+    /// let b = RangeSetBlaze::from_iter([5..=24]);
+    /// assert_eq!(a.jaccard(&b), 11.0 / 29.0);
+    /// ```
+    #[must_use]
+    pub fn jaccard(&self, other: &RangeSetBlaze<T>) -> f64 {
+        self.ranges().jaccard(other.ranges())
+    }
+
+    /// Returns the overlap coefficient of two sets, that is, the size of their intersection
+    /// divided by the size of the smaller set. It is 1.0 when one set is a subset of the other,
+    /// including when either set is empty.
+    ///
+    /// See [`SortedDisjoint::overlap_coefficient`] for the same on lazy set expressions.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two sets. It makes one
+    /// merged pass over the ranges and doesn't allocate. For large integer types, the result
+    /// may lose precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=10, 20..=29]);
+    /// let b = RangeSetBlaze::from_iter([5..=24]);
+    /// assert_eq!(a.overlap_coefficient(&b), 11.0 / 20.0);
+    /// ```
+    #[must_use]
+    pub fn overlap_coefficient(&self, other: &RangeSetBlaze<T>) -> f64 {
+        self.ranges().overlap_coefficient(other.ranges())
+    }
+
+    /// Returns the fraction of `self` that is contained in `other`, that is, the size of their
+    /// intersection divided by the size of `self`. An empty set is fully contained, so its
+    /// containment is 1.0.
+    ///
+    /// See [`SortedDisjoint::containment`] for the same on lazy set expressions.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two sets. It makes one
+    /// merged pass over the ranges and doesn't allocate. For large integer types, the result
+    /// may lose precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=10, 20..=29]);
+    /// let b = RangeSetBlaze::from_iter([5..=24]);
+    /// assert_eq!(a.containment(&b), 11.0 / 20.0);
+    /// assert_eq!(b.containment(&a), 11.0 / 20.0);
+    /// assert_eq!(b.containment(&(&a | &b)), 1.0);
+    /// ```
+    #[must_use]
+    pub fn containment(&self, other: &RangeSetBlaze<T>) -> f64 {
+        self.ranges().containment(other.ranges())
+    }
+}
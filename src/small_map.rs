@@ -0,0 +1,414 @@
+#![cfg(feature = "small-set")]
+
+// A drop-in replacement for the parts of `BTreeMap` that `RangeSetBlaze` uses.
+//
+// Sets with only a few ranges keep them inline, in a small sorted array, and so never touch
+// the allocator. When a set outgrows the array, its ranges spill into a regular `BTreeMap`.
+// When a spilled set shrinks back to half the inline capacity (or less), it moves back inline.
+// (The gap between the two thresholds keeps a set that hovers near the limit from
+// allocating and freeing over and over.)
+//
+// The method names and signatures match `BTreeMap`'s so that the rest of the crate
+// can use either type, depending on the `small-set` feature.
+
+use alloc::collections::{btree_map, BTreeMap};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter::{once, FusedIterator, Take},
+    ops::{Bound, RangeBounds},
+    slice,
+};
+
+use crate::Integer;
+
+/// The number of ranges stored without allocating.
+pub(crate) const INLINE_CAPACITY: usize = 4;
+
+#[derive(Clone)]
+pub(crate) enum SmallMap<K, V> {
+    Inline(Inline<K, V>),
+    Heap(BTreeMap<K, V>),
+}
+
+#[derive(Clone)]
+pub(crate) struct Inline<K, V> {
+    len: usize,
+    items: [(K, V); INLINE_CAPACITY],
+}
+
+impl<K: Integer, V: Integer> Inline<K, V> {
+    fn new() -> Self {
+        Inline {
+            len: 0,
+            items: [(K::zero(), V::zero()); INLINE_CAPACITY],
+        }
+    }
+
+    fn as_slice(&self) -> &[(K, V)] {
+        &self.items[..self.len]
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [(K, V)] {
+        &mut self.items[..self.len]
+    }
+
+    fn search(&self, key: &K) -> Result<usize, usize> {
+        self.as_slice().binary_search_by(|(k, _)| k.cmp(key))
+    }
+
+    // Returns the index range of the items whose keys fall in `range`.
+    fn bounds<R: RangeBounds<K>>(&self, range: &R) -> (usize, usize) {
+        let slice = self.as_slice();
+        let start = match range.start_bound() {
+            Bound::Included(s) => slice.partition_point(|(k, _)| k < s),
+            Bound::Excluded(s) => slice.partition_point(|(k, _)| k <= s),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(e) => slice.partition_point(|(k, _)| k <= e),
+            Bound::Excluded(e) => slice.partition_point(|(k, _)| k < e),
+            Bound::Unbounded => slice.len(),
+        };
+        (start, end.max(start))
+    }
+
+    // Inserts into a non-full array. Returns the old value, if any.
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(index) => Some(core::mem::replace(&mut self.items[index].1, value)),
+            Err(index) => {
+                debug_assert!(self.len < INLINE_CAPACITY); // real assert
+                self.items[index..=self.len].rotate_right(1);
+                self.items[index] = (key, value);
+                self.len += 1;
+                None
+            }
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> (K, V) {
+        let item = self.items[index];
+        self.items[index..self.len].rotate_left(1);
+        self.len -= 1;
+        item
+    }
+}
+
+impl<K, V> SmallMap<K, V> {
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            SmallMap::Inline(inline) => inline.len,
+            SmallMap::Heap(btree_map) => btree_map.len(),
+        }
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, K, V> {
+        match self {
+            SmallMap::Inline(inline) => Iter::Inline(inline.items[..inline.len].iter()),
+            SmallMap::Heap(btree_map) => Iter::Heap(btree_map.iter()),
+        }
+    }
+
+    /// Tells if the ranges have spilled out of the inline array and into a `BTreeMap`.
+    #[cfg(test)]
+    pub(crate) fn is_spilled(&self) -> bool {
+        matches!(self, SmallMap::Heap(_))
+    }
+}
+
+impl<K: Integer, V: Integer> SmallMap<K, V> {
+    pub(crate) fn new() -> Self {
+        SmallMap::Inline(Inline::new())
+    }
+
+    pub(crate) fn clear(&mut self) {
+        *self = SmallMap::new();
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        match self {
+            SmallMap::Inline(inline) => IterMut::Inline(inline.as_mut_slice().iter_mut()),
+            SmallMap::Heap(btree_map) => IterMut::Heap(btree_map.iter_mut()),
+        }
+    }
+
+    pub(crate) fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V> {
+        match self {
+            SmallMap::Inline(inline) => {
+                let (start, end) = inline.bounds(&range);
+                Range::Inline(inline.as_slice()[start..end].iter())
+            }
+            SmallMap::Heap(btree_map) => Range::Heap(btree_map.range(range)),
+        }
+    }
+
+    pub(crate) fn range_mut<R: RangeBounds<K>>(&mut self, range: R) -> RangeMut<'_, K, V> {
+        match self {
+            SmallMap::Inline(inline) => {
+                let (start, end) = inline.bounds(&range);
+                RangeMut::Inline(inline.as_mut_slice()[start..end].iter_mut())
+            }
+            SmallMap::Heap(btree_map) => RangeMut::Heap(btree_map.range_mut(range)),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self {
+            SmallMap::Inline(inline) => {
+                if inline.len < INLINE_CAPACITY || inline.search(&key).is_ok() {
+                    return inline.insert(key, value);
+                }
+                let mut btree_map: BTreeMap<K, V> = inline.as_slice().iter().copied().collect();
+                btree_map.insert(key, value);
+                *self = SmallMap::Heap(btree_map);
+                None
+            }
+            SmallMap::Heap(btree_map) => btree_map.insert(key, value),
+        }
+    }
+
+    pub(crate) fn remove(&mut self, key: &K) -> Option<V> {
+        match self {
+            SmallMap::Inline(inline) => {
+                let index = inline.search(key).ok()?;
+                Some(inline.remove_at(index).1)
+            }
+            SmallMap::Heap(btree_map) => {
+                let value = btree_map.remove(key);
+                self.shrink();
+                value
+            }
+        }
+    }
+
+    pub(crate) fn pop_first(&mut self) -> Option<(K, V)> {
+        match self {
+            SmallMap::Inline(inline) => (inline.len > 0).then(|| inline.remove_at(0)),
+            SmallMap::Heap(btree_map) => {
+                let entry = btree_map.pop_first();
+                self.shrink();
+                entry
+            }
+        }
+    }
+
+    pub(crate) fn pop_last(&mut self) -> Option<(K, V)> {
+        match self {
+            SmallMap::Inline(inline) => (inline.len > 0).then(|| inline.remove_at(inline.len - 1)),
+            SmallMap::Heap(btree_map) => {
+                let entry = btree_map.pop_last();
+                self.shrink();
+                entry
+            }
+        }
+    }
+
+    pub(crate) fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        match self {
+            SmallMap::Inline(inline) => {
+                let mut kept = 0;
+                for index in 0..inline.len {
+                    let (key, mut value) = inline.items[index];
+                    if f(&key, &mut value) {
+                        inline.items[kept] = (key, value);
+                        kept += 1;
+                    }
+                }
+                inline.len = kept;
+            }
+            SmallMap::Heap(btree_map) => {
+                btree_map.retain(f);
+                self.shrink();
+            }
+        }
+    }
+
+    pub(crate) fn split_off(&mut self, key: &K) -> Self {
+        match self {
+            SmallMap::Inline(inline) => {
+                let (start, _) = inline.bounds(&(Bound::Included(*key), Bound::Unbounded));
+                let mut other = Inline::new();
+                other.len = inline.len - start;
+                other.items[..other.len].copy_from_slice(&inline.items[start..inline.len]);
+                inline.len = start;
+                SmallMap::Inline(other)
+            }
+            SmallMap::Heap(btree_map) => {
+                let mut other = SmallMap::Heap(btree_map.split_off(key));
+                self.shrink();
+                other.shrink();
+                other
+            }
+        }
+    }
+
+    // Moves a spilled map back inline once it is small enough.
+    fn shrink(&mut self) {
+        if let SmallMap::Heap(btree_map) = self {
+            if btree_map.len() <= INLINE_CAPACITY / 2 {
+                let mut inline = Inline::new();
+                for (index, (key, value)) in btree_map.iter().enumerate() {
+                    inline.items[index] = (*key, *value);
+                }
+                inline.len = btree_map.len();
+                *self = SmallMap::Inline(inline);
+            }
+        }
+    }
+}
+
+impl<K: Integer, V: Integer> Default for SmallMap<K, V> {
+    fn default() -> Self {
+        SmallMap::new()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for SmallMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+// Hashes like a BTreeMap, so that equal maps hash equally, whether inline or spilled.
+impl<K: Hash, V: Hash> Hash for SmallMap<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for (key, value) in self.iter() {
+            key.hash(state);
+            value.hash(state);
+        }
+    }
+}
+
+impl<K: Integer, V: Integer> FromIterator<(K, V)> for SmallMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let mut inline = Inline::new();
+        while let Some((key, value)) = iter.next() {
+            if inline.len == INLINE_CAPACITY && inline.search(&key).is_err() {
+                let btree_map = inline
+                    .as_slice()
+                    .iter()
+                    .copied()
+                    .chain(once((key, value)))
+                    .chain(iter)
+                    .collect();
+                return SmallMap::Heap(btree_map);
+            }
+            inline.insert(key, value);
+        }
+        SmallMap::Inline(inline)
+    }
+}
+
+impl<K: Integer, V: Integer> IntoIterator for SmallMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        match self {
+            SmallMap::Inline(inline) => IntoIter::Inline(inline.items.into_iter().take(inline.len)),
+            SmallMap::Heap(btree_map) => IntoIter::Heap(btree_map.into_iter()),
+        }
+    }
+}
+
+// Like BTreeMap's iterators, the (cloneable) iterators debug-print their remaining items.
+macro_rules! debug_as_list {
+    ($name:ident<$($lt:lifetime,)? K, V>) => {
+        impl<$($lt,)? K: Clone + fmt::Debug, V: Clone + fmt::Debug> fmt::Debug for $name<$($lt,)? K, V> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.clone()).finish()
+            }
+        }
+    };
+}
+
+// Each iterator below is either a slice iterator (inline) or the matching BTreeMap iterator (spilled).
+macro_rules! forward_iterator {
+    ($name:ident<$($lt:lifetime,)? K, V>, $item:ty, |$pair:pat_param| $map:expr) => {
+        impl<$($lt,)? K, V> Iterator for $name<$($lt,)? K, V> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                match self {
+                    $name::Inline(iter) => iter.next().map(|$pair| $map),
+                    $name::Heap(iter) => iter.next(),
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match self {
+                    $name::Inline(iter) => iter.size_hint(),
+                    $name::Heap(iter) => iter.size_hint(),
+                }
+            }
+        }
+
+        impl<$($lt,)? K, V> DoubleEndedIterator for $name<$($lt,)? K, V> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match self {
+                    $name::Inline(iter) => iter.next_back().map(|$pair| $map),
+                    $name::Heap(iter) => iter.next_back(),
+                }
+            }
+        }
+
+        impl<$($lt,)? K, V> FusedIterator for $name<$($lt,)? K, V> {}
+    };
+}
+
+#[derive(Clone)]
+pub(crate) enum Iter<'a, K, V> {
+    Inline(slice::Iter<'a, (K, V)>),
+    Heap(btree_map::Iter<'a, K, V>),
+}
+forward_iterator!(Iter<'a, K, V>, (&'a K, &'a V), |(k, v)| (k, v));
+debug_as_list!(Iter<'a, K, V>);
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+#[derive(Debug)]
+pub(crate) enum IterMut<'a, K, V> {
+    Inline(slice::IterMut<'a, (K, V)>),
+    Heap(btree_map::IterMut<'a, K, V>),
+}
+forward_iterator!(IterMut<'a, K, V>, (&'a K, &'a mut V), |(k, v)| (&*k, v));
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+#[derive(Clone)]
+pub(crate) enum Range<'a, K, V> {
+    Inline(slice::Iter<'a, (K, V)>),
+    Heap(btree_map::Range<'a, K, V>),
+}
+forward_iterator!(Range<'a, K, V>, (&'a K, &'a V), |(k, v)| (k, v));
+debug_as_list!(Range<'a, K, V>);
+
+impl<K, V> Default for Range<'_, K, V> {
+    fn default() -> Self {
+        Range::Inline([].iter())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum RangeMut<'a, K, V> {
+    Inline(slice::IterMut<'a, (K, V)>),
+    Heap(btree_map::RangeMut<'a, K, V>),
+}
+forward_iterator!(RangeMut<'a, K, V>, (&'a K, &'a mut V), |(k, v)| (&*k, v));
+
+pub(crate) enum IntoIter<K, V> {
+    Inline(Take<core::array::IntoIter<(K, V), INLINE_CAPACITY>>),
+    Heap(btree_map::IntoIter<K, V>),
+}
+forward_iterator!(IntoIter<K, V>, (K, V), |pair| pair);
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K: Clone + fmt::Debug, V: Clone + fmt::Debug> fmt::Debug for IntoIter<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntoIter::Inline(iter) => f.debug_list().entries(iter.clone()).finish(),
+            IntoIter::Heap(iter) => iter.fmt(f),
+        }
+    }
+}
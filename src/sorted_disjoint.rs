@@ -1,16 +1,25 @@
+#[cfg(feature = "alloc")]
 use alloc::format;
+#[cfg(feature = "alloc")]
 use alloc::string::String;
 use core::{
     iter::FusedIterator,
     ops::{self, RangeInclusive},
 };
 
+#[cfg(feature = "alloc")]
 use itertools::Itertools;
 
+use crate::similarity::{overlap_lens, OverlapLens};
+use num_traits::Zero;
+
 use crate::{
-    BitAndMerge, BitOrMerge, BitSubMerge, BitXOrTee, Integer, Merge, NotIter, RangeSetBlaze,
-    UnionIter,
+    BitAndMerge, BitOrMerge, BitSubMerge, CloseIter, DilateIter, ErodeIter, Integer, Iter, Merge,
+    NotIter, OpenIter, QuotientIter, ScaleIter, ShiftIter, SkipLenIter, TakeLenIter, UnionIter,
+    WithRunningLenIter,
 };
+#[cfg(feature = "alloc")]
+use crate::{BitXOrTee, RangeSetBlaze};
 
 /// Internally, a trait used to mark iterators that provide ranges sorted by start, but not necessarily by end,
 /// and may overlap.
@@ -319,6 +328,316 @@ pub trait SortedDisjoint<T: Integer>: SortedStarts<T> {
         NotIter::new(self)
     }
 
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator with each range padded
+    /// by `k` at both ends. Ranges that come to overlap or touch are merged. The padding saturates at
+    /// `T::min_value()` and `T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=2, 10..=20, 250..=250]);
+    /// assert_eq!(a.dilate(3).to_string(), "0..=5, 7..=23, 247..=253");
+    /// ```
+    #[inline]
+    fn dilate(self, k: <T as Integer>::SafeLen) -> DilateIter<T, Self>
+    where
+        Self: Sized,
+    {
+        DilateIter::new(self, k)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator with each range shrunk
+    /// by `k` at both ends. Ranges with `2 * k` or fewer elements are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([0u8..=5, 10..=20, 250..=255]);
+    /// assert_eq!(a.erode(2).to_string(), "2..=3, 12..=18, 252..=253");
+    /// ```
+    #[inline]
+    fn erode(self, k: <T as Integer>::SafeLen) -> ErodeIter<T, Self>
+    where
+        Self: Sized,
+    {
+        ErodeIter::new(self, k)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator with each gap of `k`
+    /// or fewer elements between two ranges filled in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=2, 5..=6, 10..=20]);
+    /// assert_eq!(a.close(2).to_string(), "1..=6, 10..=20");
+    /// ```
+    #[inline]
+    fn close(self, k: <T as Integer>::SafeLen) -> CloseIter<T, Self>
+    where
+        Self: Sized,
+    {
+        CloseIter::new(self, k)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator without the ranges
+    /// that have fewer than `k` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=2, 5..=7, 10..=20]);
+    /// assert_eq!(a.open(3).to_string(), "5..=7, 10..=20");
+    /// ```
+    #[inline]
+    fn open(self, k: <T as Integer>::SafeLen) -> OpenIter<T, Self>
+    where
+        Self: Sized,
+    {
+        OpenIter::new(self, k)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator with `delta` added to
+    /// every integer. `delta` may be negative for signed types.
+    ///
+    /// # Panics
+    ///
+    /// Panics, while iterating, if any integer would move outside `T::min_value()..=T::safe_max_value()`.
+    /// Use [`saturating_shift`] to handle overflow.
+    ///
+    /// [`saturating_shift`]: SortedDisjoint::saturating_shift
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([0..=9, 20..=29]);
+    /// assert_eq!(a.shift(-5).to_string(), "-5..=4, 15..=24");
+    /// ```
+    #[inline]
+    fn shift(self, delta: T) -> ShiftIter<T, Self>
+    where
+        Self: Sized,
+    {
+        ShiftIter::new(self, delta, false)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator with `delta` added to
+    /// every integer, saturating at `T::min_value()` and `T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([0u8..=9, 200..=209, 250..=250]);
+    /// assert_eq!(a.saturating_shift(50).to_string(), "50..=59, 250..=255");
+    /// ```
+    #[inline]
+    fn saturating_shift(self, delta: T) -> ShiftIter<T, Self>
+    where
+        Self: Sized,
+    {
+        ShiftIter::new(self, delta, true)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator that turns every
+    /// integer `x` into the block `x * factor ..= x * factor + factor - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor <= 0` or `factor > T::safe_max_value()`.
+    ///
+    /// Panics, while iterating, if any block would fall outside `T::min_value()..=T::safe_max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([0u32..=1, 3..=3]);
+    /// assert_eq!(a.scale(10).to_string(), "0..=19, 30..=39");
+    /// ```
+    #[inline]
+    fn scale(self, factor: T) -> ScaleIter<T, Self>
+    where
+        Self: Sized,
+    {
+        ScaleIter::new(self, factor)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator that maps every
+    /// integer `x` to `x / divisor`, rounding down (toward negative infinity).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor <= 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([-3..=-1, 4..=5, 20..=29]);
+    /// assert_eq!(a.quotient(2).to_string(), "-2..=-1, 2..=2, 10..=14");
+    /// ```
+    #[inline]
+    fn quotient(self, divisor: T) -> QuotientIter<T, Self>
+    where
+        Self: Sized,
+    {
+        QuotientIter::new(self, divisor)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns an iterator over its integer elements.
+    ///
+    /// Unlike `.flat_map(|range| range)`, the result's [`nth`] and [`nth_back`] skip whole ranges
+    /// at a time, it is double-ended when `self` is, and it works all the way up to
+    /// `T::safe_max_value()`.
+    ///
+    /// [`nth`]: Iterator::nth
+    /// [`nth_back`]: DoubleEndedIterator::nth_back
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=2, 10..=20]);
+    /// let b = CheckSortedDisjoint::from([15..=30]);
+    /// let mut elements = (a - b).elements();
+    /// assert_eq!(elements.next(), Some(1));
+    /// assert_eq!(elements.nth(3), Some(12));
+    /// assert_eq!(elements.collect::<Vec<_>>(), vec![13, 14]);
+    ///
+    /// let c = RangeSetBlaze::from_iter([1u8..=2, 10..=20]);
+    /// let mut elements = c.ranges().elements();
+    /// assert_eq!(elements.next_back(), Some(20));
+    /// assert_eq!(elements.nth_back(10), Some(2));
+    /// ```
+    #[inline]
+    fn elements(self) -> Iter<T, Self>
+    where
+        Self: Sized,
+    {
+        Iter::new(self, None)
+    }
+
+    /// Returns the number of elements (not ranges) in a [`SortedDisjoint`] iterator. This method
+    /// takes ownership of the iterator and consumes it.
+    ///
+    /// The count is an [`Integer::SafeLen`], so it can't overflow, even for `u128` and `i128`.
+    ///
+    /// This is the `SortedDisjoint` counterpart of [`RangeSetBlaze::len`]. It isn't named `len`
+    /// because [`RangesIter`] and the other range iterators are [`ExactSizeIterator`]s whose
+    /// `len` counts ranges, and a consuming trait method named `len` would silently take
+    /// precedence over it.
+    ///
+    /// [`RangeSetBlaze::len`]: crate::RangeSetBlaze::len
+    /// [`RangesIter`]: crate::RangesIter
+    ///
+    /// # Performance
+    ///
+    /// O(*n*), where *n* is the number of ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=2, 10..=20]);
+    /// let b = CheckSortedDisjoint::from([15..=30]);
+    /// assert_eq!((a - b).elements_len(), 7);
+    ///
+    /// let c = RangeSetBlaze::from_iter([1u8..=2, 10..=20]);
+    /// assert_eq!(c.ranges().len(), 2);
+    /// assert_eq!(c.ranges().elements_len(), 13);
+    /// ```
+    #[must_use]
+    fn elements_len(self) -> <T as Integer>::SafeLen
+    where
+        Self: Sized,
+    {
+        self.fold(<T as Integer>::SafeLen::zero(), |len, range| {
+            len + T::safe_len(&range)
+        })
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns an iterator of `(range, len_so_far)` pairs,
+    /// where `len_so_far` counts the elements in `range` and in all the ranges before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=2, 10..=20]);
+    /// let pairs: Vec<_> = a.with_running_len().collect();
+    /// assert_eq!(pairs, vec![(1..=2, 2), (10..=20, 13)]);
+    /// ```
+    #[inline]
+    fn with_running_len(self) -> WithRunningLenIter<T, Self>
+    where
+        Self: Sized,
+    {
+        WithRunningLenIter::new(self)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator of its first `n`
+    /// elements. The range that crosses the boundary is shortened.
+    ///
+    /// Together with [`skip_len`], this pages through a lazy set expression.
+    ///
+    /// [`skip_len`]: SortedDisjoint::skip_len
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=2, 10..=20]);
+    /// assert_eq!(a.take_len(5).to_string(), "1..=2, 10..=12");
+    ///
+    /// // Page 2, with 5 elements per page
+    /// let a = CheckSortedDisjoint::from([1u8..=2, 10..=20]);
+    /// assert_eq!(a.skip_len(5).take_len(5).to_string(), "13..=17");
+    /// ```
+    #[inline]
+    fn take_len(self, n: <T as Integer>::SafeLen) -> TakeLenIter<T, Self>
+    where
+        Self: Sized,
+    {
+        TakeLenIter::new(self, n)
+    }
+
+    /// Given a [`SortedDisjoint`] iterator, returns a [`SortedDisjoint`] iterator without its
+    /// first `n` elements. The range that crosses the boundary is shortened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=2, 10..=20]);
+    /// assert_eq!(a.skip_len(5).to_string(), "13..=20");
+    /// ```
+    #[inline]
+    fn skip_len(self, n: <T as Integer>::SafeLen) -> SkipLenIter<T, Self>
+    where
+        Self: Sized,
+    {
+        SkipLenIter::new(self, n)
+    }
+
     /// Given two [`SortedDisjoint`] iterators, efficiently returns a [`SortedDisjoint`] iterator
     /// of their symmetric difference.
     ///
@@ -340,6 +659,7 @@ pub trait SortedDisjoint<T: Integer>: SortedStarts<T> {
     /// assert_eq!(symmetric_difference.to_string(), "1..=1, 3..=3");
     /// ```
     #[inline]
+    #[cfg(feature = "alloc")]
     fn symmetric_difference<R>(self, other: R) -> BitXOrTee<T, Self, R::IntoIter>
     where
         R: IntoIterator<Item = Self::Item>,
@@ -383,6 +703,7 @@ pub trait SortedDisjoint<T: Integer>: SortedStarts<T> {
     /// let a = CheckSortedDisjoint::from([1..=2]);
     /// assert_eq!(a.to_string(), "1..=2");
     /// ```
+    #[cfg(feature = "alloc")]
     fn to_string(self) -> String
     where
         Self: Sized,
@@ -504,6 +825,183 @@ pub trait SortedDisjoint<T: Integer>: SortedStarts<T> {
         self.intersection(other).is_empty()
     }
 
+    /// Returns the number of elements in the intersection of two [`SortedDisjoint`] iterators,
+    /// without allocating. Like [`equal`], this method consumes the iterators.
+    ///
+    /// [`equal`]: SortedDisjoint::equal
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two iterators. It makes
+    /// one merged pass over the ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=10, 20..=29]);
+    /// let b = CheckSortedDisjoint::from([5..=24]);
+    /// assert_eq!(a.intersection_len(b), 11);
+    /// ```
+    #[must_use]
+    fn intersection_len<R>(self, other: R) -> <T as Integer>::SafeLen
+    where
+        R: IntoIterator<Item = Self::Item>,
+        R::IntoIter: SortedDisjoint<T>,
+        Self: Sized,
+    {
+        overlap_lens(self, other.into_iter()).both
+    }
+
+    /// Returns the number of elements in the union of two [`SortedDisjoint`] iterators,
+    /// without allocating. Like [`equal`], this method consumes the iterators.
+    ///
+    /// [`equal`]: SortedDisjoint::equal
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two iterators. It makes
+    /// one merged pass over the ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=10, 20..=29]);
+    /// let b = CheckSortedDisjoint::from([5..=24]);
+    /// assert_eq!(a.union_len(b), 29);
+    /// ```
+    #[must_use]
+    fn union_len<R>(self, other: R) -> <T as Integer>::SafeLen
+    where
+        R: IntoIterator<Item = Self::Item>,
+        R::IntoIter: SortedDisjoint<T>,
+        Self: Sized,
+    {
+        overlap_lens(self, other.into_iter()).union()
+    }
+
+    /// Returns the number of elements in exactly one of two [`SortedDisjoint`] iterators,
+    /// without allocating. Like [`equal`], this method consumes the iterators.
+    ///
+    /// [`equal`]: SortedDisjoint::equal
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two iterators. It makes
+    /// one merged pass over the ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=10, 20..=29]);
+    /// let b = CheckSortedDisjoint::from([5..=24]);
+    /// assert_eq!(a.symmetric_difference_len(b), 18);
+    /// ```
+    #[must_use]
+    fn symmetric_difference_len<R>(self, other: R) -> <T as Integer>::SafeLen
+    where
+        R: IntoIterator<Item = Self::Item>,
+        R::IntoIter: SortedDisjoint<T>,
+        Self: Sized,
+    {
+        overlap_lens(self, other.into_iter()).symmetric_difference()
+    }
+
+    /// Returns the Jaccard index of two [`SortedDisjoint`] iterators, that is, the size of
+    /// their intersection divided by the size of their union. Two empty iterators have a
+    /// Jaccard index of 1.0.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two iterators. It makes
+    /// one merged pass over the ranges and doesn't allocate. For large integer types, the
+    /// result may lose precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=10, 20..=29]);
+    /// let b = CheckSortedDisjoint::from([5..=24]);
+    /// assert_eq!(a.jaccard(b), 11.0 / 29.0);
+    /// ```
+    #[must_use]
+    fn jaccard<R>(self, other: R) -> f64
+    where
+        R: IntoIterator<Item = Self::Item>,
+        R::IntoIter: SortedDisjoint<T>,
+        Self: Sized,
+    {
+        let lens = overlap_lens(self, other.into_iter());
+        OverlapLens::<T>::ratio(lens.both, lens.union())
+    }
+
+    /// Returns the overlap coefficient of two [`SortedDisjoint`] iterators, that is, the size
+    /// of their intersection divided by the size of the smaller one. It is 1.0 when one is a
+    /// subset of the other, including when either is empty.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two iterators. It makes
+    /// one merged pass over the ranges and doesn't allocate. For large integer types, the
+    /// result may lose precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=10, 20..=29]);
+    /// let b = CheckSortedDisjoint::from([5..=24]);
+    /// assert_eq!(a.overlap_coefficient(b), 11.0 / 20.0);
+    /// ```
+    #[must_use]
+    fn overlap_coefficient<R>(self, other: R) -> f64
+    where
+        R: IntoIterator<Item = Self::Item>,
+        R::IntoIter: SortedDisjoint<T>,
+        Self: Sized,
+    {
+        let lens = overlap_lens(self, other.into_iter());
+        OverlapLens::<T>::ratio(lens.both, lens.left.min(lens.right))
+    }
+
+    /// Returns the fraction of `self` that is contained in `other`, that is, the size of their
+    /// intersection divided by the size of `self`. An empty `self` is fully contained, so its
+    /// containment is 1.0.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* + *m*), where *n* and *m* are the number of ranges in the two iterators. It makes
+    /// one merged pass over the ranges and doesn't allocate. For large integer types, the
+    /// result may lose precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from([1u8..=10, 20..=29]);
+    /// let b = CheckSortedDisjoint::from([5..=24]);
+    /// assert_eq!(a.containment(b), 11.0 / 20.0);
+    /// ```
+    #[must_use]
+    fn containment<R>(self, other: R) -> f64
+    where
+        R: IntoIterator<Item = Self::Item>,
+        R::IntoIter: SortedDisjoint<T>,
+        Self: Sized,
+    {
+        let lens = overlap_lens(self, other.into_iter());
+        OverlapLens::<T>::ratio(lens.both, lens.left)
+    }
+
     /// Create a [`RangeSetBlaze`] from a [`SortedDisjoint`] iterator.
     ///
     /// *For more about constructors and performance, see [`RangeSetBlaze` Constructors](struct.RangeSetBlaze.html#constructors).*
@@ -517,6 +1015,7 @@ pub trait SortedDisjoint<T: Integer>: SortedStarts<T> {
     /// let a1: RangeSetBlaze<i32> = CheckSortedDisjoint::from([-10..=-5, 1..=2]).into_range_set_blaze();
     /// assert!(a0 == a1 && a0.to_string() == "-10..=-5, 1..=2");
     /// ```
+    #[cfg(feature = "alloc")]
     fn into_range_set_blaze(self) -> RangeSetBlaze<T>
     where
         Self: Sized,
@@ -714,6 +1213,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer, R, L> ops::BitXor<R> for CheckSortedDisjoint<T, L>
 where
     L: Iterator<Item = RangeInclusive<T>>,
@@ -725,3 +1225,98 @@ where
         SortedDisjoint::symmetric_difference(self, other)
     }
 }
+
+// Marks an adapter `$name<T, I>` over a `SortedDisjoint` `I` as `SortedDisjoint` and gives it the
+// usual set operators.
+//
+// The second form takes any generics and where clause, for example
+// `impl_sorted_disjoint_and_ops!(<'a, T, F> Foo<'a, T, F> where F: Fn(T) -> bool)`. Its integer
+// parameter must be named `T`, and it leaves `FusedIterator` to the caller.
+macro_rules! impl_sorted_disjoint_and_ops {
+    ($($name:ident),*) => {$(
+        impl<T: $crate::Integer, I: $crate::SortedDisjoint<T> + core::iter::FusedIterator> core::iter::FusedIterator for $name<T, I> {}
+        $crate::sorted_disjoint::impl_sorted_disjoint_and_ops!(<T, I> $name<T, I> where I: $crate::SortedDisjoint<T>);
+    )*};
+    (<$($lt:lifetime,)? $($generic:ident),*> $ty:ty $(where $($bound:tt)*)?) => {
+        impl<$($lt,)? $($generic),*> $crate::SortedStarts<T> for $ty
+        where
+            T: $crate::Integer,
+            $($($bound)*)?
+        {
+        }
+
+        impl<$($lt,)? $($generic),*> $crate::SortedDisjoint<T> for $ty
+        where
+            T: $crate::Integer,
+            $($($bound)*)?
+        {
+        }
+
+        impl<$($lt,)? $($generic),*> core::ops::Not for $ty
+        where
+            T: $crate::Integer,
+            $($($bound)*)?
+        {
+            type Output = $crate::NotIter<T, Self>;
+
+            fn not(self) -> Self::Output {
+                $crate::SortedDisjoint::complement(self)
+            }
+        }
+
+        impl<$($lt,)? $($generic,)* R> core::ops::BitOr<R> for $ty
+        where
+            T: $crate::Integer,
+            R: $crate::SortedDisjoint<T>,
+            $($($bound)*)?
+        {
+            type Output = $crate::BitOrMerge<T, Self, R>;
+
+            fn bitor(self, other: R) -> Self::Output {
+                $crate::SortedDisjoint::union(self, other)
+            }
+        }
+
+        impl<$($lt,)? $($generic,)* R> core::ops::Sub<R> for $ty
+        where
+            T: $crate::Integer,
+            R: $crate::SortedDisjoint<T>,
+            $($($bound)*)?
+        {
+            type Output = $crate::BitSubMerge<T, Self, R>;
+
+            fn sub(self, other: R) -> Self::Output {
+                $crate::SortedDisjoint::difference(self, other)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<$($lt,)? $($generic,)* R> core::ops::BitXor<R> for $ty
+        where
+            T: $crate::Integer,
+            R: $crate::SortedDisjoint<T>,
+            $($($bound)*)?
+        {
+            type Output = $crate::BitXOrTee<T, Self, R>;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn bitxor(self, other: R) -> Self::Output {
+                $crate::SortedDisjoint::symmetric_difference(self, other)
+            }
+        }
+
+        impl<$($lt,)? $($generic,)* R> core::ops::BitAnd<R> for $ty
+        where
+            T: $crate::Integer,
+            R: $crate::SortedDisjoint<T>,
+            $($($bound)*)?
+        {
+            type Output = $crate::BitAndMerge<T, Self, R>;
+
+            fn bitand(self, other: R) -> Self::Output {
+                $crate::SortedDisjoint::intersection(self, other)
+            }
+        }
+    };
+}
+pub(crate) use impl_sorted_disjoint_and_ops;
@@ -0,0 +1,117 @@
+use core::{iter::FusedIterator, ops::RangeInclusive};
+
+use crate::{CheckSortedDisjoint, Integer};
+
+/// An iterator that groups sorted integers into ranges, coalescing consecutive and duplicate
+/// integers. It panics if the integers are not sorted.
+///
+/// This `struct` is created by the [`CheckSortedDisjoint::from_sorted_integers`] method. See
+/// its documentation for more.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SortedIntegersIter<T, I>
+where
+    T: Integer,
+    I: Iterator<Item = T>,
+{
+    iter: I,
+    range: Option<RangeInclusive<T>>,
+}
+
+impl<T, I> FusedIterator for SortedIntegersIter<T, I>
+where
+    T: Integer,
+    I: Iterator<Item = T> + FusedIterator,
+{
+}
+
+impl<T, I> Iterator for SortedIntegersIter<T, I>
+where
+    T: Integer,
+    I: Iterator<Item = T>,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<RangeInclusive<T>> {
+        for value in self.iter.by_ref() {
+            let Some(range) = self.range.as_mut() else {
+                self.range = Some(value..=value);
+                continue;
+            };
+            let end = *range.end();
+            assert!(end <= value, "integers must be sorted");
+            if end == value {
+                continue;
+            }
+            // end < value, so end + 1 can't overflow
+            if end + T::one() == value {
+                *range = *range.start()..=value;
+            } else {
+                return self.range.replace(value..=value);
+            }
+        }
+        self.range.take()
+    }
+
+    // Every integer could start a range, or all could merge into the pending one.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        if self.range.is_some() {
+            (1, high.and_then(|high| high.checked_add(1)))
+        } else {
+            (low.min(1), high)
+        }
+    }
+}
+
+impl<T, I> CheckSortedDisjoint<T, SortedIntegersIter<T, I>>
+where
+    T: Integer,
+    I: Iterator<Item = T>,
+{
+    /// Creates a [`CheckSortedDisjoint`] from an iterator of sorted integers, grouping
+    /// consecutive and duplicate integers into ranges as it goes. The result works with the
+    /// [`SortedDisjoint`] operators without first collecting into a [`RangeSetBlaze`].
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if/when it finds that the integers are not sorted, or that an
+    /// integer is greater than `T::safe_max_value()`.
+    ///
+    /// # Performance
+    ///
+    /// It makes one pass over the integers and doesn't allocate.
+    ///
+    /// [`SortedDisjoint`]: crate::SortedDisjoint
+    /// [`RangeSetBlaze`]: crate::RangeSetBlaze
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from_sorted_integers([1, 2, 2, 3, 7, 9, 10]);
+    /// assert_eq!(a.to_string(), "1..=3, 7..=7, 9..=10");
+    ///
+    /// let a = CheckSortedDisjoint::from_sorted_integers([1, 2, 2, 3, 7, 9, 10]);
+    /// let b = CheckSortedDisjoint::from([3..=9]);
+    /// assert_eq!((a - b).to_string(), "1..=2, 10..=10");
+    /// ```
+    ///
+    /// Here the integers are not sorted, so the iterator will panic.
+    /// ```should_panic
+    /// use range_set_blaze::prelude::*;
+    ///
+    /// let a = CheckSortedDisjoint::from_sorted_integers([1, 2, 5, 4]);
+    /// assert_eq!(a.to_string(), "1..=2, 4..=5");
+    /// ```
+    pub fn from_sorted_integers<J>(iter: J) -> Self
+    where
+        J: IntoIterator<Item = T, IntoIter = I>,
+    {
+        CheckSortedDisjoint::new(SortedIntegersIter {
+            iter: iter.into_iter(),
+            range: None,
+        })
+    }
+}
@@ -0,0 +1,192 @@
+use alloc::vec::Vec;
+use num_traits::{One, Zero};
+
+use crate::{Integer, RangeSetBlaze};
+
+/// Summary statistics of a [`RangeSetBlaze`], as returned by [`RangeSetBlaze::summary`].
+///
+/// Lengths are [`Integer::SafeLen`], so they can't overflow, even for `u128` and `i128` sets.
+/// The statistics that don't exist for an empty set (or, for the gap statistics, for a set
+/// with fewer than two ranges) are `None`.
+///
+/// # Examples
+///
+/// ```
+/// use range_set_blaze::RangeSetBlaze;
+///
+/// let summary = RangeSetBlaze::from_iter([1u8..=2, 10..=13, 20..=20]).summary();
+/// assert_eq!(summary.ranges_len, 3);
+/// assert_eq!(summary.len, 7);
+/// assert_eq!(summary.max_gap_len, Some(7));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeSetSummary<T: Integer> {
+    /// The number of ranges.
+    pub ranges_len: usize,
+    /// The number of integers.
+    pub len: <T as Integer>::SafeLen,
+    /// The length of the shortest range.
+    pub min_range_len: Option<<T as Integer>::SafeLen>,
+    /// The length of the longest range.
+    pub max_range_len: Option<<T as Integer>::SafeLen>,
+    /// The mean length of the ranges. For large integer types, this may lose precision.
+    pub mean_range_len: Option<f64>,
+    /// The number of gaps between ranges. The gaps before the first range and after the last
+    /// range aren't counted.
+    pub gaps_len: usize,
+    /// The length of the longest gap between two ranges.
+    pub max_gap_len: Option<<T as Integer>::SafeLen>,
+    /// The fraction of `first..=last` that is in the set. For large integer types, this may
+    /// lose precision.
+    pub density: Option<f64>,
+}
+
+// Returns `floor(log2(len))`, the index of `len`'s log bucket. `len` must be > 0.
+fn log_bucket<L: num_integer::Integer + Copy>(mut len: L) -> usize {
+    let two = L::one() + L::one();
+    let mut bucket = 0;
+    while len >= two {
+        len = len / two;
+        bucket += 1;
+    }
+    bucket
+}
+
+// Counts `len` into its log bucket, growing the histogram as needed.
+fn add_to_histogram<L: num_integer::Integer + Copy>(histogram: &mut Vec<usize>, len: L) {
+    let bucket = log_bucket(len);
+    if histogram.len() <= bucket {
+        histogram.resize(bucket + 1, 0);
+    }
+    histogram[bucket] += 1;
+}
+
+// The length of the gap `end+1..=start-1` between two ranges, computed as the length of
+// `end..=start` less two so that nothing overflows.
+fn gap_len<T: Integer>(end: T, start: T) -> <T as Integer>::SafeLen {
+    let two = <T as Integer>::SafeLen::one() + <T as Integer>::SafeLen::one();
+    let gap_len = T::safe_len(&(end..=start)) - two;
+    debug_assert!(gap_len > <T as Integer>::SafeLen::zero(), "real assert");
+    gap_len
+}
+
+impl<T: Integer> RangeSetBlaze<T> {
+    /// Returns summary statistics of the set: the range count, the element count, the shortest,
+    /// longest, and mean range lengths, the gap count, the longest gap, and the density over
+    /// `first..=last`. See [`RangeSetSummary`] for details.
+    ///
+    /// # Performance
+    ///
+    /// O(*n*), where *n* is the number of ranges. It makes one pass over the ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let summary = RangeSetBlaze::from_iter([1u8..=2, 10..=13, 20..=20]).summary();
+    /// assert_eq!(summary.ranges_len, 3);
+    /// assert_eq!(summary.len, 7);
+    /// assert_eq!(summary.min_range_len, Some(1));
+    /// assert_eq!(summary.max_range_len, Some(4));
+    /// assert_eq!(summary.mean_range_len, Some(7.0 / 3.0));
+    /// assert_eq!(summary.gaps_len, 2);
+    /// assert_eq!(summary.max_gap_len, Some(7));
+    /// assert_eq!(summary.density, Some(0.35));
+    ///
+    /// let summary = RangeSetBlaze::<u8>::new().summary();
+    /// assert_eq!(summary.ranges_len, 0);
+    /// assert_eq!(summary.density, None);
+    /// ```
+    #[must_use]
+    pub fn summary(&self) -> RangeSetSummary<T> {
+        let mut min_range_len: Option<<T as Integer>::SafeLen> = None;
+        let mut max_range_len: Option<<T as Integer>::SafeLen> = None;
+        let mut max_gap_len: Option<<T as Integer>::SafeLen> = None;
+        let mut previous_end: Option<T> = None;
+        for range in self.ranges() {
+            let range_len = T::safe_len(&range);
+            min_range_len = Some(min_range_len.map_or(range_len, |min| min.min(range_len)));
+            max_range_len = Some(max_range_len.map_or(range_len, |max| max.max(range_len)));
+            if let Some(previous_end) = previous_end {
+                let gap_len = gap_len(previous_end, *range.start());
+                max_gap_len = Some(max_gap_len.map_or(gap_len, |max| max.max(gap_len)));
+            }
+            previous_end = Some(*range.end());
+        }
+
+        let ranges_len = self.ranges_len();
+        let len = self.len();
+        let mean_range_len = (ranges_len > 0).then(|| T::safe_len_to_f64(len) / ranges_len as f64);
+        let density = self.first().zip(self.last()).map(|(first, last)| {
+            T::safe_len_to_f64(len) / T::safe_len_to_f64(T::safe_len(&(first..=last)))
+        });
+        RangeSetSummary {
+            ranges_len,
+            len,
+            min_range_len,
+            max_range_len,
+            mean_range_len,
+            gaps_len: ranges_len.saturating_sub(1),
+            max_gap_len,
+            density,
+        }
+    }
+
+    /// Returns a log-bucketed histogram of the range lengths. Bucket `i` counts the ranges with
+    /// `2^i <= length < 2^(i + 1)`. The histogram ends at the last non-empty bucket, so it is
+    /// empty for an empty set.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* log *m*), where *n* is the number of ranges and *m* is the length of the longest
+    /// range. It makes one pass over the ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=1, 3..=3, 10..=13, 20..=29]);
+    /// assert_eq!(a.range_len_histogram(), vec![2, 0, 1, 1]);
+    /// ```
+    #[must_use]
+    pub fn range_len_histogram(&self) -> Vec<usize> {
+        let mut histogram = Vec::new();
+        for range in self.ranges() {
+            add_to_histogram(&mut histogram, T::safe_len(&range));
+        }
+        histogram
+    }
+
+    /// Returns a log-bucketed histogram of the lengths of the gaps between ranges. Bucket `i`
+    /// counts the gaps with `2^i <= length < 2^(i + 1)`. The gaps before the first range and
+    /// after the last range aren't counted. The histogram ends at the last non-empty bucket, so
+    /// it is empty for a set with fewer than two ranges.
+    ///
+    /// # Performance
+    ///
+    /// O(*n* log *m*), where *n* is the number of ranges and *m* is the length of the longest
+    /// gap. It makes one pass over the ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use range_set_blaze::RangeSetBlaze;
+    ///
+    /// let a = RangeSetBlaze::from_iter([1u8..=1, 3..=3, 10..=13, 20..=29]);
+    /// assert_eq!(a.gap_len_histogram(), vec![1, 0, 2]);
+    /// ```
+    #[must_use]
+    pub fn gap_len_histogram(&self) -> Vec<usize> {
+        let mut histogram = Vec::new();
+        let mut previous_end: Option<T> = None;
+        for range in self.ranges() {
+            if let Some(previous_end) = previous_end {
+                add_to_histogram(&mut histogram, gap_len(previous_end, *range.start()));
+            }
+            previous_end = Some(*range.end());
+        }
+        histogram
+    }
+}
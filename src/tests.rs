@@ -998,7 +998,7 @@ fn lib_coverage_0() {
     assert_eq!(i.size_hint(), j.size_hint());
     assert_eq!(
         format!("{:?}", &i),
-        "IntoIter { option_range_front: None, option_range_back: None, into_iter: [(1, 3)] }"
+        "IntoIter { option_range_front: None, option_range_back: None, into_iter: [(1, 3)], len: 3 }"
     );
 
     let mut a = RangeSetBlaze::from_iter([1..=3]);
@@ -1489,3 +1489,826 @@ fn double_end_range() {
     assert_eq!(range.next(), Some(12));
     assert_eq!(range.next(), Some(20));
 }
+
+#[cfg(feature = "small-set")]
+#[test]
+fn small_set_inline_and_spill() {
+    let mut a = RangeSetBlaze::from_iter([1..=2, 10..=12, 20..=20, 30..=31]);
+    assert!(!a.btree_map.is_spilled());
+    a.insert(3);
+    a.ranges_insert(40..=45);
+    assert!(a.btree_map.is_spilled());
+    assert_eq!(a.to_string(), "1..=3, 10..=12, 20..=20, 30..=31, 40..=45");
+
+    a.remove(20);
+    a.remove(10);
+    assert!(a.btree_map.is_spilled());
+    let b = a.split_off(30);
+    assert!(!a.btree_map.is_spilled() && !b.btree_map.is_spilled());
+    assert_eq!(a.to_string(), "1..=3, 11..=12");
+    assert_eq!(b.to_string(), "30..=31, 40..=45");
+    assert_eq!(a.len() + b.len(), 13usize);
+
+    let mut c = RangeSetBlaze::from_iter([0, 2, 4, 6, 8, 10]);
+    assert!(c.btree_map.is_spilled());
+    assert_eq!((c.pop_first(), c.pop_last()), (Some(0), Some(10)));
+    assert_eq!(c.pop_first(), Some(2));
+    assert!(c.btree_map.is_spilled());
+    let d = RangeSetBlaze::from_iter([8, 6, 4]);
+    assert!(!d.btree_map.is_spilled());
+    assert_eq!(c, d);
+
+    use std::hash::Hasher;
+    let mut hasher_c = DefaultHasher::new();
+    c.hash(&mut hasher_c);
+    let mut hasher_d = DefaultHasher::new();
+    d.hash(&mut hasher_d);
+    assert_eq!(hasher_c.finish(), hasher_d.finish());
+
+    c.remove(4);
+    assert!(!c.btree_map.is_spilled());
+    assert_eq!(c.to_string(), "6..=6, 8..=8");
+}
+
+#[cfg(feature = "small-set")]
+#[quickcheck]
+fn small_set_matches_btree_set(ops: Vec<(bool, i8)>) -> bool {
+    let mut expected = BTreeSet::new();
+    let mut actual = RangeSetBlaze::new();
+    for (insert, value) in ops {
+        let (e, a) = if insert {
+            (expected.insert(value), actual.insert(value))
+        } else {
+            (expected.remove(&value), actual.remove(value))
+        };
+        if e != a || !actual.iter().eq(expected.iter().copied()) {
+            return false;
+        }
+    }
+    actual.len() == expected.len()
+}
+
+/// A quickcheck range from `start` through `width % max_width` more integers,
+/// stopping at the largest allowed value.
+fn arb_range<T: Integer>(start: T, width: u8, max_width: u8) -> RangeInclusive<T> {
+    let width: T = num_traits::cast(width % max_width).unwrap();
+    start..=start.checked_add(&width).unwrap_or_else(T::safe_max_value)
+}
+
+/// A quickcheck set made of one [`arb_range`] per `(start, width)` pair.
+fn arb_set<T: Integer>(pairs: Vec<(T, u8)>, max_width: u8) -> RangeSetBlaze<T> {
+    pairs
+        .into_iter()
+        .map(|(start, width)| arb_range(start, width, max_width))
+        .collect()
+}
+
+#[quickcheck]
+fn range_set_blaze_array_matches_range_set_blaze(ops: Vec<(bool, i8, u8)>) -> bool {
+    let mut expected = RangeSetBlaze::new();
+    let mut actual = RangeSetBlazeArray::<i8, 3>::new();
+    for (insert, start, width) in ops {
+        let (start, end) = arb_range(start, width, 8).into_inner();
+        let before = actual;
+        let result = if insert {
+            actual.ranges_insert(start..=end)
+        } else {
+            actual.remove(start)
+        };
+        match result {
+            Ok(changed) => {
+                let e = if insert {
+                    expected.ranges_insert(start..=end)
+                } else {
+                    expected.remove(start)
+                };
+                if e != changed {
+                    return false;
+                }
+            }
+            Err(CapacityError) => {
+                if actual != before || actual.ranges_len() != 3 {
+                    return false;
+                }
+            }
+        }
+        if !actual.ranges().eq(expected.ranges()) || actual.len() != expected.len() {
+            return false;
+        }
+    }
+    true
+}
+
+#[quickcheck]
+fn persistent_matches_range_set_blaze(ops: Vec<(u8, i8, u8)>) -> bool {
+    let mut versions = vec![(PersistentRangeSetBlaze::new(), RangeSetBlaze::new())];
+    for (op, start, width) in ops {
+        let (start, end) = arb_range(start, width, 8).into_inner();
+        let (mut actual, mut expected) = versions[start as usize % versions.len()].clone();
+        let (a, e) = match op % 3 {
+            0 => (
+                actual.ranges_insert(start..=end),
+                expected.ranges_insert(start..=end),
+            ),
+            1 => (actual.remove(start), expected.remove(start)),
+            _ => (actual.insert(end), expected.insert(end)),
+        };
+        if a != e || !actual.ranges().eq(expected.ranges()) || actual.len() != expected.len() {
+            return false;
+        }
+        if !actual.ranges().rev().eq(expected.ranges().rev()) {
+            return false;
+        }
+        versions.push((actual, expected));
+    }
+    // Earlier versions are unchanged and diff/operators agree with RangeSetBlaze.
+    for window in versions.windows(2) {
+        let ((old, old_expected), (new, new_expected)) = (&window[0], &window[1]);
+        if !old.ranges().eq(old_expected.ranges()) {
+            return false;
+        }
+        let (added, removed) = PersistentRangeSetBlaze::diff(old, new);
+        let (added_expected, removed_expected) =
+            (new_expected - old_expected, old_expected - new_expected);
+        if !added.equal(added_expected.ranges()) || !removed.equal(removed_expected.ranges()) {
+            return false;
+        }
+        if !(old | new)
+            .ranges()
+            .eq((old_expected | new_expected).ranges())
+            || !(old & new)
+                .ranges()
+                .eq((old_expected & new_expected).ranges())
+            || !(old - new)
+                .ranges()
+                .eq((old_expected - new_expected).ranges())
+            || !(old ^ new)
+                .ranges()
+                .eq((old_expected ^ new_expected).ranges())
+            || !(!old).ranges().eq((!old_expected).ranges())
+        {
+            return false;
+        }
+    }
+    true
+}
+
+#[quickcheck]
+fn ranges_insert_and_remove_diff(ops: Vec<(bool, i8, u8)>) -> bool {
+    let mut set = RangeSetBlaze::new();
+    for (insert, start, width) in ops {
+        let range = arb_range(start, width, 16);
+        let before = set.clone();
+        let (changed, expected) = if insert {
+            let added = RangeSetBlaze::from_sorted_disjoint(set.ranges_insert_diff(range.clone()));
+            (added, &set - &before)
+        } else {
+            let removed =
+                RangeSetBlaze::from_sorted_disjoint(set.ranges_remove_diff(range.clone()));
+            (removed, &before - &set)
+        };
+        let target = RangeSetBlaze::from_iter([range]);
+        let expected_set = if insert {
+            &before | &target
+        } else {
+            &before - &target
+        };
+        if changed != expected || set != expected_set || set.len() != expected_set.len() {
+            return false;
+        }
+    }
+    true
+}
+
+#[quickcheck]
+fn allocate_matches_linear_scan(ranges: Vec<(u8, u8)>, ops: Vec<(u8, u8, u8)>) -> bool {
+    let mut free = arb_set(ranges, 32);
+    for (kind, n, hint) in ops {
+        let n = (n % 40) as usize + 1;
+        let fits = |r: &RangeInclusive<u8>| u8::safe_len(r) >= n;
+        let expected = match kind % 3 {
+            0 => free
+                .ranges()
+                .map(|r| hint.max(*r.start())..=*r.end())
+                .filter(|r| r.start() <= r.end())
+                .find(fits),
+            1 => free.ranges().filter(fits).min_by_key(u8::safe_len),
+            _ => free.ranges().rev().max_by_key(u8::safe_len).filter(fits),
+        }
+        .map(|r| *r.start()..=u8::add_len_less_one(*r.start(), n));
+        let before = free.clone();
+        let actual = match kind % 3 {
+            0 => free.allocate_first_fit(n, hint),
+            1 => free.allocate_best_fit(n),
+            _ => free.allocate_worst_fit(n),
+        };
+        if actual != expected {
+            return false;
+        }
+        let taken = RangeSetBlaze::from_iter(actual);
+        if free != &before - &taken || free.len() != before.len() - taken.len() {
+            return false;
+        }
+    }
+    true
+}
+
+#[quickcheck]
+fn nearest_matches_linear_scan(ranges: Vec<(u8, u8)>, value: u8) -> bool {
+    let set = arb_set(ranges, 32);
+    let next = set.iter().find(|x| *x >= value);
+    let prev = set.iter().rev().find(|x| *x <= value);
+    let distance = |x: u8| x.abs_diff(value) as usize;
+    let nearest = match (prev, next) {
+        (Some(prev), Some(next)) => Some(if distance(prev) <= distance(next) {
+            prev
+        } else {
+            next
+        }),
+        (prev, next) => prev.or(next),
+    };
+    let range_of = |x: Option<u8>| x.and_then(|x| set.ranges().find(|r| r.contains(&x)));
+    set.next_at_or_after(value) == next
+        && set.prev_at_or_before(value) == prev
+        && set.nearest(value) == nearest
+        && set.distance_to(value) == nearest.map(distance)
+        && set.next_range_at_or_after(value) == range_of(next)
+        && set.prev_range_at_or_before(value) == range_of(prev)
+        && set.nearest_range(value) == range_of(nearest)
+}
+
+#[quickcheck]
+fn morphology_matches_elementwise(ranges: Vec<(u8, u8)>, k: u16) -> bool {
+    let set = arb_set(ranges, 32);
+    let k = (k % 300) as usize;
+    let dilated: RangeSetBlaze<u8> = (0..=255u8)
+        .filter(|&x| set.iter().any(|y| (x.abs_diff(y) as usize) <= k))
+        .collect();
+    let eroded: RangeSetBlaze<u8> = (0..=255u8)
+        .filter(|&x| {
+            let (x, k) = (x as usize, k);
+            x >= k && x + k <= 255 && (x - k..=x + k).all(|y| set.contains(y as u8))
+        })
+        .collect();
+    let closed: RangeSetBlaze<u8> = (0..=255u8)
+        .filter(|&x| {
+            set.contains(x)
+                || match (set.prev_at_or_before(x), set.next_at_or_after(x)) {
+                    (Some(prev), Some(next)) => (next - prev - 1) as usize <= k,
+                    _ => false,
+                }
+        })
+        .collect();
+    let opened: RangeSetBlaze<u8> = set
+        .ranges()
+        .filter(|range| u8::safe_len(range) >= k)
+        .collect();
+    set.dilate(k) == dilated
+        && set.erode(k) == eroded
+        && set.close(k) == closed
+        && set.open(k) == opened
+        && set.ranges().dilate(k).equal(dilated.ranges())
+        && set.ranges().erode(k).equal(eroded.ranges())
+}
+
+#[quickcheck]
+fn affine_matches_elementwise(ranges: Vec<(i8, u8)>, delta: i8, factor: u8) -> bool {
+    let set = arb_set(ranges, 32);
+    let elementwise = |f: &dyn Fn(i8) -> i8| set.iter().map(f).collect::<RangeSetBlaze<i8>>();
+
+    let mut shifted = set.clone();
+    let fits = set.iter().all(|x| x.checked_add(delta).is_some());
+    if shifted.checked_shift(delta) != fits {
+        return false;
+    }
+    if fits && shifted != elementwise(&|x| x + delta) || !fits && shifted != set {
+        return false;
+    }
+    let mut shifted = set.clone();
+    shifted.saturating_shift(delta);
+    if shifted != elementwise(&|x| x.saturating_add(delta))
+        || !set.ranges().saturating_shift(delta).equal(shifted.ranges())
+    {
+        return false;
+    }
+    let mut shifted = set.clone();
+    shifted.wrapping_shift(delta);
+    if shifted != elementwise(&|x| x.wrapping_add(delta)) {
+        return false;
+    }
+
+    let divisor = (factor % 20) as i8 + 1;
+    let mut quotient = set.clone();
+    quotient.quotient(divisor);
+    if quotient != elementwise(&|x| x.div_euclid(divisor)) {
+        return false;
+    }
+    let mut scaled = quotient.clone();
+    let fits = quotient.iter().all(|x| {
+        x.checked_mul(divisor)
+            .is_some_and(|y| y.checked_add(divisor - 1).is_some())
+    });
+    if fits {
+        scaled.scale(divisor);
+        let expected: RangeSetBlaze<i8> = quotient
+            .iter()
+            .map(|x| x * divisor..=x * divisor + (divisor - 1))
+            .collect();
+        if scaled != expected || !(&set - &scaled).is_empty() {
+            return false;
+        }
+    }
+    true
+}
+
+#[quickcheck]
+fn convert_matches_elementwise(ranges: Vec<(i16, u8)>) -> bool {
+    let set = arb_set(ranges, u8::MAX);
+    let clamped: RangeSetBlaze<u8> = set.iter().filter_map(|x| u8::try_from(x).ok()).collect();
+    let fits = set.iter().all(|x| u8::try_from(x).is_ok());
+    let widened: RangeSetBlaze<i64> = set.clone().into();
+    set.convert_clamped::<u8>() == clamped
+        && set.try_convert::<u8>()
+            == if fits {
+                Ok(clamped)
+            } else {
+                Err(ConversionError)
+            }
+        && widened.iter().map(|x| x as i16).eq(set.iter())
+        && widened.len() == set.len() as u128
+}
+
+#[quickcheck]
+fn minkowski_matches_elementwise(a: Vec<(i8, u8)>, b: Vec<(i8, u8)>) -> bool {
+    let (a, b) = (arb_set(a, 16), arb_set(b, 16));
+    let sum: RangeSetBlaze<i8> = a
+        .iter()
+        .flat_map(|x| b.iter().map(move |y| x.saturating_add(y)))
+        .collect();
+    let difference: RangeSetBlaze<i8> = (i8::MIN..=i8::MAX)
+        .filter(|&x| {
+            b.iter()
+                .all(|y| x.checked_add(y).is_some_and(|z| a.contains(z)))
+        })
+        .collect();
+    a.minkowski_sum(&b) == sum && a.minkowski_difference(&b) == difference
+}
+
+#[quickcheck]
+fn summary_matches_brute_force(ranges: Vec<(u8, u8)>) -> bool {
+    let set = arb_set(ranges, 16);
+    let range_lens: Vec<usize> = set.ranges().map(|range| range.len()).collect();
+    let gap_lens: Vec<usize> = set
+        .ranges()
+        .zip(set.ranges().skip(1))
+        .map(|(a, b)| (*b.start() - *a.end() - 1) as usize)
+        .collect();
+    let histogram = |lens: &[usize]| {
+        let mut histogram = vec![
+            0;
+            lens.iter()
+                .map(|len| len.ilog2() as usize + 1)
+                .max()
+                .unwrap_or(0)
+        ];
+        for len in lens {
+            histogram[len.ilog2() as usize] += 1;
+        }
+        histogram
+    };
+    let summary = set.summary();
+    summary.ranges_len == range_lens.len()
+        && summary.len == range_lens.iter().sum::<usize>()
+        && summary.min_range_len == range_lens.iter().min().copied()
+        && summary.max_range_len == range_lens.iter().max().copied()
+        && summary.gaps_len == gap_lens.len()
+        && summary.max_gap_len == gap_lens.iter().max().copied()
+        && summary.density
+            == set
+                .first()
+                .map(|first| set.len() as f64 / (set.last().unwrap() as f64 - first as f64 + 1.0))
+        && set.range_len_histogram() == histogram(&range_lens)
+        && set.gap_len_histogram() == histogram(&gap_lens)
+}
+
+#[quickcheck]
+fn similarity_matches_set_ops(a: Vec<(i8, u8)>, b: Vec<(i8, u8)>) -> bool {
+    let (a, b) = (arb_set(a, 32), arb_set(b, 32));
+    let ratio = |numerator: usize, denominator: usize| {
+        if denominator == 0 {
+            1.0
+        } else {
+            numerator as f64 / denominator as f64
+        }
+    };
+    let both = (&a & &b).len();
+    a.intersection_len(&b) == both
+        && a.union_len(&b) == (&a | &b).len()
+        && a.symmetric_difference_len(&b) == (&a ^ &b).len()
+        && a.jaccard(&b) == ratio(both, (&a | &b).len())
+        && a.overlap_coefficient(&b) == ratio(both, a.len().min(b.len()))
+        && a.containment(&b) == ratio(both, a.len())
+        && (a.ranges() - b.ranges()).intersection_len(b.ranges()) == 0
+}
+
+#[quickcheck]
+fn counting_matches_elementwise(ranges: Vec<(i8, u8)>, n: u8) -> bool {
+    let set = arb_set(ranges, 32);
+    let n = n as usize;
+    let taken: RangeSetBlaze<i8> = set.iter().take(n).collect();
+    let skipped: RangeSetBlaze<i8> = set.iter().skip(n).collect();
+    let mut len_so_far = 0;
+    set.ranges().elements_len() == set.len()
+        && set.ranges().with_running_len().all(|(range, len)| {
+            len_so_far += range.len();
+            len == len_so_far
+        })
+        && set.ranges().take_len(n).equal(taken.ranges())
+        && set.ranges().skip_len(n).equal(skipped.ranges())
+        && (set.ranges().skip_len(n) | set.ranges().take_len(n)).equal(set.ranges())
+}
+
+#[quickcheck]
+fn elements_matches_flat_map(ranges: Vec<(i8, u8)>, steps: Vec<(bool, u8)>) -> bool {
+    let set = arb_set(ranges, 32);
+    let mut expected: Vec<i8> = set.ranges().flatten().collect();
+    let mut elements = set.ranges().elements();
+    let mut iter = set.iter();
+    for (from_back, n) in steps {
+        let n = (n % 40) as usize;
+        let (actual, actual_iter, wanted) = if from_back {
+            let wanted = (n < expected.len()).then(|| expected[expected.len() - 1 - n]);
+            expected.truncate(expected.len().saturating_sub(n + 1));
+            (elements.nth_back(n), iter.nth_back(n), wanted)
+        } else {
+            let wanted = expected.get(n).copied();
+            expected.drain(..expected.len().min(n + 1));
+            (elements.nth(n), iter.nth(n), wanted)
+        };
+        if actual != wanted
+            || actual_iter != wanted
+            || iter.size_hint() != (expected.len(), Some(expected.len()))
+        {
+            return false;
+        }
+    }
+    elements.collect::<Vec<_>>() == expected
+}
+
+#[quickcheck]
+fn from_slice_matches_from_iter(runs: Vec<(i16, u8)>) -> bool {
+    // Runs of consecutive integers, some long enough to fill whole chunks.
+    let v16: Vec<i16> = runs
+        .iter()
+        .flat_map(|&(start, len)| (0..len as i16).map(move |i| start.wrapping_add(i)))
+        .collect();
+    let v8: Vec<u8> = v16.iter().map(|&x| x as u8).collect();
+    let v32: Vec<u32> = v16.iter().map(|&x| x as u32).collect();
+    let v64: Vec<i64> = v16.iter().map(|&x| x as i64).collect();
+    RangeSetBlaze::from_slice(&v16) == RangeSetBlaze::from_iter(&v16)
+        && RangeSetBlaze::from_slice(&v8) == RangeSetBlaze::from_iter(&v8)
+        && RangeSetBlaze::from_slice(&v32) == RangeSetBlaze::from_iter(&v32)
+        && RangeSetBlaze::from_slice(&v64) == RangeSetBlaze::from_iter(&v64)
+}
+
+#[quickcheck]
+fn contains_many_matches_contains(ranges: Vec<(u8, u8)>, probes: Vec<u8>, sort: bool) -> bool {
+    let set = arb_set(ranges, 16);
+    let mut probes = probes;
+    if sort {
+        probes.sort_unstable();
+    }
+    let expected: Vec<bool> = probes.iter().map(|&probe| set.contains(probe)).collect();
+    let members: Vec<u8> = probes
+        .iter()
+        .copied()
+        .filter(|&probe| set.contains(probe))
+        .collect();
+    set.contains_many(&probes) == expected && set.filter_members(probes.iter().copied()).eq(members)
+}
+
+#[quickcheck]
+fn from_sorted_integers_matches_from_iter(integers: Vec<i8>, other: Vec<i8>) -> bool {
+    let mut integers = integers;
+    integers.sort_unstable();
+    let set = RangeSetBlaze::from_iter(&integers);
+    let other = RangeSetBlaze::from_iter(&other);
+    CheckSortedDisjoint::from_sorted_integers(integers.iter().copied()).equal(set.ranges())
+        && (CheckSortedDisjoint::from_sorted_integers(integers.iter().copied()) | other.ranges())
+            .equal((&set | &other).ranges())
+}
+
+#[quickcheck]
+fn cursor_mut_edits_match_set_operations(ranges: Vec<(u8, u8)>, edits: Vec<(u8, u8, u8)>) -> bool {
+    let mut set = arb_set(ranges, 16);
+    let mut expected = set.clone();
+    {
+        let mut cursor = set.cursor_front_mut();
+        for (edit, a, b) in edits {
+            let Some(current) = cursor.range() else {
+                cursor.move_next();
+                continue;
+            };
+            let (start, end) = current.clone().into_inner();
+            let (a, b) = (a.min(b), a.max(b));
+            match edit % 6 {
+                0 => cursor.move_next(),
+                1 => cursor.move_prev(),
+                2 => {
+                    let part = a.clamp(start, end)..=b.clamp(start, end);
+                    cursor.shrink(part.clone());
+                    expected = &expected
+                        - &(RangeSetBlaze::from_iter([current]) - RangeSetBlaze::from_iter([part]));
+                }
+                3 => {
+                    let range = a.min(start)..=b.max(end);
+                    cursor.extend(range.clone());
+                    expected.ranges_insert(range);
+                }
+                4 => {
+                    if start < a && a <= b && b < end {
+                        cursor.split(a..=b);
+                        expected = &expected - &RangeSetBlaze::from_iter([a..=b]);
+                    }
+                }
+                _ => {
+                    if cursor.remove_current() != Some(current.clone()) {
+                        return false;
+                    }
+                    expected = &expected - &RangeSetBlaze::from_iter([current]);
+                }
+            }
+            let view = cursor.as_cursor();
+            if let Some(range) = view.range() {
+                if !expected.ranges().any(|r| r == range)
+                    || view.gap_before().is_some_and(|gap| {
+                        !(&expected & &RangeSetBlaze::from_iter([gap])).is_empty()
+                    })
+                    || view.gap_after().is_some_and(|gap| {
+                        !(&expected & &RangeSetBlaze::from_iter([gap])).is_empty()
+                    })
+                {
+                    return false;
+                }
+            }
+        }
+    }
+    set == expected && set.len() == RangeSetBlaze::from_iter(set.ranges()).len()
+}
+
+#[quickcheck]
+fn cursor_moves_match_vec_of_ranges(ranges: Vec<(u8, u8)>, start: u8, moves: Vec<bool>) -> bool {
+    let set = arb_set(ranges, 16);
+    let vec: Vec<_> = set.ranges().collect();
+    // The ghost position is index `vec.len()`.
+    let positions = vec.len() + 1;
+    let at = |index: usize| vec.get(index % positions).cloned();
+    let mut index = vec
+        .iter()
+        .position(|range| start <= *range.end())
+        .unwrap_or(vec.len());
+    let mut cursor = set.cursor_at(start);
+    for forward in moves {
+        if forward {
+            cursor.move_next();
+            index = (index + 1) % positions;
+        } else {
+            cursor.move_prev();
+            index = (index + positions - 1) % positions;
+        }
+        let gaps_ok = cursor.range().is_none_or(|range| {
+            let before = RangeSetBlaze::from_iter(cursor.gap_before());
+            let after = RangeSetBlaze::from_iter(cursor.gap_after());
+            let around = &before | &after | &RangeSetBlaze::from_iter([range]);
+            (&before & &set).is_empty()
+                && (&after & &set).is_empty()
+                && around.ranges_len() == 1
+                && before
+                    .first()
+                    .is_none_or(|first| first == 0 || set.contains(first - 1))
+                && after
+                    .last()
+                    .is_none_or(|last| last == 255 || set.contains(last + 1))
+        });
+        if cursor.range() != at(index)
+            || cursor.peek_next() != at(index + 1)
+            || cursor.peek_prev() != at(index + positions - 1)
+            || !gaps_ok
+        {
+            return false;
+        }
+    }
+    true
+}
+
+#[quickcheck]
+fn drain_range_and_extract_if_ranges_match_set_operations(
+    ranges: Vec<(u8, u8)>,
+    start: u8,
+    end: u8,
+    width: u8,
+) -> bool {
+    let set = arb_set(ranges, 16);
+    let (start, end) = (start.min(end), start.max(end));
+    let window = RangeSetBlaze::from_iter([start..=end]);
+
+    let mut drained = set.clone();
+    let removed = RangeSetBlaze::from_sorted_disjoint(drained.drain_range(start..=end));
+    let drain_range_ok = removed == &set & &window
+        && drained == &set - &window
+        && drained.len() == RangeSetBlaze::from_iter(drained.ranges()).len();
+
+    let is_wide = |range: &RangeInclusive<u8>| range.end() - range.start() >= width % 8;
+    let mut extracted = set.clone();
+    let wide = RangeSetBlaze::from_sorted_disjoint(extracted.extract_if_ranges(is_wide));
+    let extract_ok = wide.ranges().eq(set.ranges().filter(is_wide))
+        && extracted
+            .ranges()
+            .eq(set.ranges().filter(|range| !is_wide(range)))
+        && extracted.len() == RangeSetBlaze::from_iter(extracted.ranges()).len();
+
+    let mut emptied = set.clone();
+    let drain_ok = emptied.drain().equal(set.ranges()) && emptied.is_empty();
+
+    drain_range_ok && extract_ok && drain_ok
+}
+
+#[quickcheck]
+fn range_granular_retain_and_map_match_rebuilding(
+    ranges: Vec<(u8, u8)>,
+    edits: Vec<(i8, i8, bool)>,
+) -> bool {
+    let set = arb_set(ranges, 16);
+    if edits.is_empty() {
+        return true;
+    }
+    // Each range gets an edit that shifts its start and end, or removes it.
+    let edit_for = |range: &RangeInclusive<u8>| edits[*range.start() as usize % edits.len()];
+    let map = |range: RangeInclusive<u8>| {
+        let (start_shift, end_shift, keep) = edit_for(&range);
+        keep.then(|| {
+            range.start().saturating_add_signed(start_shift % 4)
+                ..=range.end().saturating_add_signed(end_shift % 8)
+        })
+    };
+
+    let mut retained = set.clone();
+    retained.retain_ranges(|range| edit_for(range).2);
+    let expected: RangeSetBlaze<u8> = set.ranges().filter(|range| edit_for(range).2).collect();
+    let retain_ok =
+        retained == expected && retained.len() == RangeSetBlaze::from_iter(retained.ranges()).len();
+
+    let mut mapped = set.clone();
+    mapped.map_ranges_in_place(map);
+    let expected: RangeSetBlaze<u8> = set.ranges().filter_map(map).collect();
+    let map_ok =
+        mapped == expected && mapped.len() == RangeSetBlaze::from_iter(mapped.ranges()).len();
+
+    retain_ok && map_ok
+}
+
+#[quickcheck]
+fn range_count_map_matches_counting_each_integer(edits: Vec<(u8, u8, u8, bool)>) -> bool {
+    let mut map = RangeCountMap::<u8>::new();
+    let mut expected = [0usize; 256];
+    for (start, width, k, add) in edits {
+        let range = arb_range(start, width, 32);
+        let k = usize::from(k % 3);
+        if add {
+            map.add_range(range.clone(), k);
+            range.for_each(|value| expected[value as usize] += k);
+        } else if range.clone().all(|value| expected[value as usize] >= k) {
+            map.remove_range(range.clone(), k);
+            range.for_each(|value| expected[value as usize] -= k);
+        }
+    }
+
+    let counts_ok = (0..=255u8).all(|value| map.count(value) == expected[value as usize]);
+    // Every segment is maximal: touching neighbors never share a count.
+    let maximal_ok = map
+        .segments()
+        .tuple_windows()
+        .all(|((a, a_count), (b, b_count))| a_count != b_count || *a.end() + 1 < *b.start());
+    let at_least_ok = (1..=4).all(|k| {
+        let from_map = RangeSetBlaze::from_sorted_disjoint(map.ranges_with_count_at_least(k));
+        let from_expected: RangeSetBlaze<u8> = (0..=255u8)
+            .filter(|&value| expected[value as usize] >= k)
+            .collect();
+        from_map == from_expected
+    });
+    let support_ok =
+        map.support() == RangeSetBlaze::from_sorted_disjoint(map.ranges_with_count_at_least(1));
+
+    counts_ok && maximal_ok && at_least_ok && support_ok
+}
+
+#[quickcheck]
+fn range_set_blaze_2d_matches_sets_of_points(
+    a: Vec<(u8, u8, u8, u8)>,
+    b: Vec<(u8, u8, u8, u8)>,
+) -> bool {
+    // Rectangles in a 32 × 32 corner of the plane, so the points can be listed.
+    let rectangle = |(x, width, y, height): (u8, u8, u8, u8)| {
+        let (x, y) = (x % 32, y % 32);
+        (x..=(x + width % 8).min(31), y..=(y + height % 8).min(31))
+    };
+    let points = |set: &RangeSetBlaze2D<u8, u8>| -> BTreeSet<(u8, u8)> {
+        set.rectangles()
+            .flat_map(|(x_range, y_range)| x_range.cartesian_product(y_range))
+            .collect()
+    };
+    let is_canonical = |set: &RangeSetBlaze2D<u8, u8>| {
+        set.slabs().all(|(_, y_set)| !y_set.is_empty())
+            && set.slabs().tuple_windows().all(|((a_x, a_y), (b_x, b_y))| {
+                a_x.end() < b_x.start() && (*a_x.end() + 1 < *b_x.start() || a_y != b_y)
+            })
+            && set
+                .rectangles()
+                .tuple_windows()
+                .all(|((a_x, a_y), (b_x, b_y))| (a_x.start(), a_y.start()) < (b_x.start(), b_y.start()))
+            // The rectangles are disjoint, so their areas add up to the set's area.
+            && set.area() == Some(points(set).len() as u128)
+            && set.area()
+                == Some(
+                    set.rectangles()
+                        .map(|(x_range, y_range)| x_range.len() as u128 * y_range.len() as u128)
+                        .sum::<u128>(),
+                )
+    };
+
+    let rectangle_points = |rectangles: &[(u8, u8, u8, u8)]| -> BTreeSet<(u8, u8)> {
+        rectangles
+            .iter()
+            .flat_map(|&r| {
+                let (x_range, y_range) = rectangle(r);
+                x_range.cartesian_product(y_range)
+            })
+            .collect()
+    };
+
+    let a_set: RangeSetBlaze2D<u8, u8> = a.iter().copied().map(rectangle).collect();
+    let b_set: RangeSetBlaze2D<u8, u8> = b.iter().copied().map(rectangle).collect();
+    let (a_points, b_points) = (rectangle_points(&a), rectangle_points(&b));
+
+    let results = [
+        (&a_set | &b_set, &a_points | &b_points),
+        (&a_set & &b_set, &a_points & &b_points),
+        (&a_set - &b_set, &a_points - &b_points),
+        (&a_set ^ &b_set, &a_points ^ &b_points),
+    ];
+    let ops_ok = results
+        .iter()
+        .all(|(set, expected)| is_canonical(set) && points(set) == *expected);
+    let contains_ok = (0..40u8)
+        .cartesian_product(0..40u8)
+        .all(|point| a_set.contains(point) == a_points.contains(&point));
+    let not_a = !&a_set;
+    let not_ok = is_canonical(&a_set)
+        && not_a.area().zip(a_set.area()).map(|(not_a, a)| not_a + a) == Some(65_536)
+        && (&not_a & &a_set).is_empty()
+        && !&not_a == a_set;
+
+    let mut extended = a_set.clone();
+    extended.extend(b.iter().copied().map(rectangle));
+    let mut inserted = RangeSetBlaze2D::new();
+    for (x_range, y_range) in a.iter().copied().map(rectangle) {
+        inserted.insert_rectangle(x_range, y_range);
+    }
+    let build_ok = extended == &a_set | &b_set && inserted == a_set;
+
+    ops_ok && contains_ok && not_ok && build_ok
+}
+
+#[quickcheck]
+fn range_allocator_matches_set_methods(ranges: Vec<(u8, u8)>, ops: Vec<(u8, u8, u8)>) -> bool {
+    let mut free = arb_set(ranges, 32);
+    let mut allocator = RangeAllocator::from(free.clone());
+    for (kind, n, hint) in ops {
+        let n = (n % 40) as usize + 1;
+        let (expected, actual) = match kind % 4 {
+            0 => (
+                free.allocate_first_fit(n, hint),
+                allocator.allocate_first_fit(n, hint),
+            ),
+            1 => (free.allocate_best_fit(n), allocator.allocate_best_fit(n)),
+            2 => (free.allocate_worst_fit(n), allocator.allocate_worst_fit(n)),
+            _ => {
+                let range = hint..=hint.saturating_add(n as u8);
+                free.ranges_insert(range.clone());
+                allocator.release(range);
+                (None, None)
+            }
+        };
+        if actual != expected
+            || allocator.free_set() != &free
+            || (1..=40usize).any(|n| {
+                allocator.find_best_fit(n) != free.find_best_fit(n)
+                    || allocator.find_worst_fit(n) != free.find_worst_fit(n)
+                    || allocator.find_free(n, hint) != free.find_free(n, hint)
+            })
+        {
+            return false;
+        }
+    }
+    true
+}
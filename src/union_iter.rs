@@ -4,14 +4,17 @@ use core::{
     ops::{self, RangeInclusive},
 };
 
+#[cfg(feature = "alloc")]
 use alloc::vec;
+#[cfg(feature = "alloc")]
 use itertools::Itertools;
 
+#[cfg(feature = "alloc")]
 use crate::{
     unsorted_disjoint::{AssumeSortedStarts, UnsortedDisjoint},
-    BitAndMerge, BitOrMerge, BitSubMerge, BitXOrTee, Integer, NotIter, SortedDisjoint,
-    SortedStarts,
+    BitXOrTee,
 };
+use crate::{BitAndMerge, BitOrMerge, BitSubMerge, Integer, NotIter, SortedDisjoint, SortedStarts};
 
 /// Turns any number of [`SortedDisjoint`] iterators into a [`SortedDisjoint`] iterator of their union,
 /// i.e., all the integers in any input iterator, as sorted & disjoint ranges. Uses [`Merge`]
@@ -63,18 +66,21 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer, const N: usize> From<[T; N]> for UnionIter<T, SortedRangeInclusiveVec<T>> {
     fn from(arr: [T; N]) -> Self {
         arr.as_slice().into()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> From<&[T]> for UnionIter<T, SortedRangeInclusiveVec<T>> {
     fn from(slice: &[T]) -> Self {
         slice.iter().cloned().collect()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer, const N: usize> From<[RangeInclusive<T>; N]>
     for UnionIter<T, SortedRangeInclusiveVec<T>>
 {
@@ -83,14 +89,17 @@ impl<T: Integer, const N: usize> From<[RangeInclusive<T>; N]>
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> From<&[RangeInclusive<T>]> for UnionIter<T, SortedRangeInclusiveVec<T>> {
     fn from(slice: &[RangeInclusive<T>]) -> Self {
         slice.iter().cloned().collect()
     }
 }
 
+#[cfg(feature = "alloc")]
 type SortedRangeInclusiveVec<T> = AssumeSortedStarts<T, vec::IntoIter<RangeInclusive<T>>>;
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> FromIterator<T> for UnionIter<T, SortedRangeInclusiveVec<T>> {
     fn from_iter<I>(iter: I) -> Self
     where
@@ -100,6 +109,7 @@ impl<T: Integer> FromIterator<T> for UnionIter<T, SortedRangeInclusiveVec<T>> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer> FromIterator<RangeInclusive<T>> for UnionIter<T, SortedRangeInclusiveVec<T>> {
     fn from_iter<I>(iter: I) -> Self
     where
@@ -109,6 +119,7 @@ impl<T: Integer> FromIterator<RangeInclusive<T>> for UnionIter<T, SortedRangeInc
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, I> From<UnsortedDisjoint<T, I>> for UnionIter<T, SortedRangeInclusiveVec<T>>
 where
     T: Integer,
@@ -217,6 +228,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer, R, L> ops::BitXor<R> for UnionIter<T, L>
 where
     L: SortedStarts<T>,
@@ -1,11 +1,13 @@
-use crate::{Integer, SortedDisjoint, SortedStarts};
-use core::{
-    cmp::{max, min},
-    iter::FusedIterator,
-    ops::RangeInclusive,
-};
+#[cfg(feature = "alloc")]
+use crate::SortedDisjoint;
+use crate::{Integer, SortedStarts};
+#[cfg(feature = "alloc")]
+use core::cmp::{max, min};
+use core::{iter::FusedIterator, ops::RangeInclusive};
+#[cfg(feature = "alloc")]
 use num_traits::Zero;
 
+#[cfg(feature = "alloc")]
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub(crate) struct UnsortedDisjoint<T, I>
 where
@@ -18,6 +20,7 @@ where
     two: T,
 }
 
+#[cfg(feature = "alloc")]
 impl<T, I> From<I> for UnsortedDisjoint<T, I::IntoIter>
 where
     T: Integer,
@@ -33,6 +36,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, I> FusedIterator for UnsortedDisjoint<T, I>
 where
     T: Integer,
@@ -40,6 +44,7 @@ where
 {
 }
 
+#[cfg(feature = "alloc")]
 impl<T, I> Iterator for UnsortedDisjoint<T, I>
 where
     T: Integer,
@@ -95,6 +100,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub(crate) struct SortedDisjointWithLenSoFar<T, I>
 where
@@ -105,6 +111,7 @@ where
     len: <T as Integer>::SafeLen,
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer, I> From<I> for SortedDisjointWithLenSoFar<T, I::IntoIter>
 where
     I: IntoIterator<Item = RangeInclusive<T>>,
@@ -118,6 +125,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer, I> SortedDisjointWithLenSoFar<T, I>
 where
     I: SortedDisjoint<T>,
@@ -127,11 +135,13 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer, I> FusedIterator for SortedDisjointWithLenSoFar<T, I> where
     I: SortedDisjoint<T> + FusedIterator
 {
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Integer, I> Iterator for SortedDisjointWithLenSoFar<T, I>
 where
     I: SortedDisjoint<T>,
@@ -1,24 +1,19 @@
 #![cfg(test)]
 #![cfg(not(target_arch = "wasm32"))]
 
-#[cfg(feature = "from_slice")]
 use core::mem::size_of;
-#[cfg(feature = "rog-experimental")]
-use core::ops::Bound;
-use core::ops::RangeInclusive;
+use core::ops::{Bound, RangeBounds, RangeInclusive};
 use criterion::{BatchSize, BenchmarkId, Criterion};
 use itertools::Itertools;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
-#[cfg(feature = "rog-experimental")]
-use range_set_blaze::Rog;
 use range_set_blaze::{
-    prelude::*, AssumeSortedStarts, Integer, NotIter, RangesIter, SortedStarts, UnionIter,
+    prelude::*, AssumeSortedStarts, CapacityError, ConversionError, Integer, NotIter,
+    PersistentRangeSetBlaze, RangeAllocator, RangeCountMap, RangeSetBlaze2D, RangeSetBlazeArray,
+    RangesIter, Rog, SortedStarts, UnionIter,
 };
 use std::cmp::Ordering;
-#[cfg(feature = "rog-experimental")]
 use std::panic::AssertUnwindSafe;
-#[cfg(feature = "rog-experimental")]
 use std::panic::{self};
 use std::time::Instant;
 use std::{collections::BTreeSet, ops::BitOr};
@@ -1152,6 +1147,26 @@ fn len_demo() {
     assert_eq!(<u8 as Integer>::safe_len(&(0..=255)), 256);
 }
 
+#[test]
+fn len_less_one_for_signed_lengths_that_overflow_the_type() {
+    // `b - 1` doesn't fit in the signed type, but the result does.
+    assert_eq!(<i8 as Integer>::add_len_less_one(-128, 200), 71);
+    assert_eq!(<i8 as Integer>::sub_len_less_one(127, 200), -72);
+    assert_eq!(<i8 as Integer>::add_len_less_one(-128, 256), 127);
+    assert_eq!(
+        <i16 as Integer>::add_len_less_one(i16::MIN, 65_536),
+        i16::MAX
+    );
+    assert_eq!(
+        <i128 as Integer>::add_len_less_one(i128::MIN, u128::MAX),
+        i128::MAX - 1
+    );
+    assert_eq!(
+        <i128 as Integer>::sub_len_less_one(i128::MAX - 1, u128::MAX),
+        i128::MIN
+    );
+}
+
 #[test]
 fn union_iter() {
     use range_set_blaze::{CheckSortedDisjoint, UnionIter};
@@ -1275,7 +1290,6 @@ fn print_features() {
     )*}};
 }
 
-#[cfg(feature = "from_slice")]
 #[test]
 fn from_slice_all_types() {
     syntactic_for! { ty in [i8, u8] {
@@ -1297,9 +1311,9 @@ fn from_slice_all_types() {
     }};
 }
 
-#[cfg(feature = "from_slice")]
 #[test]
 fn range_set_int_slice_constructor() {
+    #[cfg(feature = "from_slice")]
     print_features();
     let k = 1;
     let average_width = 1000;
@@ -1613,88 +1627,207 @@ fn complement_sample() {
     println!("{},{},{}", c.len(), c.ranges_len(), c);
 }
 
-#[cfg(feature = "rog-experimental")]
+// Used to test `rogs_range`.
+fn rogs_range_slow<T: Integer>(set: &RangeSetBlaze<T>, range: impl RangeBounds<T>) -> Vec<Rog<T>> {
+    let start = match range.start_bound() {
+        Bound::Included(n) => Some(*n),
+        Bound::Excluded(n) => (*n < T::safe_max_value()).then(|| *n + T::one()),
+        Bound::Unbounded => Some(T::min_value()),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(n) => Some(*n),
+        Bound::Excluded(n) => (*n > T::min_value()).then(|| *n - T::one()),
+        Bound::Unbounded => Some(T::safe_max_value()),
+    };
+    let rsb_in = RangeSetBlaze::from_iter(start.zip(end).map(|(start, end)| start..=end));
+    let ranges = (&rsb_in & set).into_ranges().map(Rog::Range);
+    let gaps = (rsb_in - set).into_ranges().map(Rog::Gap);
+    let mut result = ranges.chain(gaps).collect::<Vec<Rog<T>>>();
+    result.sort_by_key(|a| a.start());
+    result
+}
+
+// Used to test `rogs_get`.
+fn rogs_get_slow<T: Integer>(set: &RangeSetBlaze<T>, value: T) -> Rog<T> {
+    rogs_range_slow(set, ..)
+        .into_iter()
+        .find(|rog| rog.contains(value))
+        .expect("value must be in something")
+}
+
 #[test]
 fn test_rog_functionality() {
     let a = RangeSetBlaze::from_iter([1..=6, 8..=9, 11..=15]);
     // case 1:
     for end in 7..=16 {
-        println!("case 1: {:?}", a._rogs_range_slow(7..=end));
+        println!("case 1: {:?}", rogs_range_slow(&a, 7..=end));
         assert_eq!(
-            a._rogs_range_slow(7..=end),
+            rogs_range_slow(&a, 7..=end),
             a.rogs_range(7..=end).collect::<Vec<_>>()
         );
     }
     // case 2:
     for end in 7..=16 {
-        println!("case 2: {:?}", a._rogs_range_slow(4..=end));
+        println!("case 2: {:?}", rogs_range_slow(&a, 4..=end));
         assert_eq!(
-            a._rogs_range_slow(4..=end),
+            rogs_range_slow(&a, 4..=end),
             a.rogs_range(4..=end).collect::<Vec<_>>()
         );
     }
     // case 3:
     for start in 11..=15 {
         for end in start..=15 {
-            println!("case 3: {:?}", a._rogs_range_slow(start..=end));
+            println!("case 3: {:?}", rogs_range_slow(&a, start..=end));
             assert_eq!(
-                a._rogs_range_slow(start..=end),
+                rogs_range_slow(&a, start..=end),
                 a.rogs_range(start..=end).collect::<Vec<_>>()
             );
         }
     }
     // case 4:
     for end in -1..=16 {
-        println!("case 4: {:?}", a._rogs_range_slow(-1..=end));
+        println!("case 4: {:?}", rogs_range_slow(&a, -1..=end));
         assert_eq!(
-            a._rogs_range_slow(-1..=end),
+            rogs_range_slow(&a, -1..=end),
             a.rogs_range(-1..=end).collect::<Vec<_>>()
         );
     }
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rogs_get_functionality() {
     let a = RangeSetBlaze::from_iter([1..=6, 8..=9, 11..=15]);
     for value in 0..=16 {
-        println!("{:?}", a.rogs_get_slow(value));
-        assert_eq!(a.rogs_get_slow(value), a.rogs_get(value));
+        println!("{:?}", rogs_get_slow(&a, value));
+        assert_eq!(rogs_get_slow(&a, value), a.rogs_get(value));
     }
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_repro1() {
     let a = RangeSetBlaze::from_iter([1u8..=6u8]);
     assert_eq!(
-        a._rogs_range_slow(1..=7),
+        rogs_range_slow(&a, 1..=7),
         a.rogs_range(1..=7).collect::<Vec<_>>()
     );
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_repro2() {
     let a = RangeSetBlaze::from_iter([1..=6, 8..=9, 11..=15]);
     assert_eq!(
-        a._rogs_range_slow(4..=8),
+        rogs_range_slow(&a, 4..=8),
         a.rogs_range(4..=8).collect::<Vec<_>>()
     );
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
+#[allow(clippy::reversed_empty_ranges)]
 fn test_rog_coverage1() {
     let a = RangeSetBlaze::from_iter([1u8..=6u8]);
+    assert_eq!(
+        a.rogs_range((Bound::Excluded(&255), Bound::Included(&255)))
+            .next(),
+        None
+    );
+    assert_eq!(a.rogs_range(0..0).next(), None);
+    assert_eq!(a.rogs_range(..0).next_back(), None);
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| a.rogs_range(5..=4))).is_err());
     assert!(panic::catch_unwind(AssertUnwindSafe(
-        || a.rogs_range((Bound::Excluded(&255), Bound::Included(&255)))
+        || a.rogs_range((Bound::Excluded(&5), Bound::Excluded(&5)))
     ))
     .is_err());
-    assert!(panic::catch_unwind(AssertUnwindSafe(|| a.rogs_range(0..0))).is_err());
+    let b = RangeSetBlaze::from_iter([1u128..=6]);
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| b.rogs_range(..=u128::MAX))).is_err());
+    assert_eq!(
+        b.rogs_range(..u128::MAX).next_back(),
+        Some(Rog::Gap(7..=u128::safe_max_value()))
+    );
+}
+
+#[test]
+fn test_rog_double_ended_u8() {
+    let bounds = |n: u8| [Bound::Included(n), Bound::Excluded(n)];
+    for a in [
+        RangeSetBlaze::new(),
+        RangeSetBlaze::from_iter([1u8..=6u8]),
+        RangeSetBlaze::from_iter([0u8..=255u8]),
+        RangeSetBlaze::from_iter([0u8..=5u8, 8..=8, 10..=12, 20u8..=255]),
+    ] {
+        for start in (0u8..=255).step_by(3) {
+            for end in start..=255 {
+                for range in bounds(start)
+                    .into_iter()
+                    .chain([Bound::Unbounded])
+                    .cartesian_product(bounds(end).into_iter().chain([Bound::Unbounded]))
+                {
+                    if range == (Bound::Excluded(start), Bound::Excluded(end)) && start == end {
+                        continue;
+                    }
+                    let expected = rogs_range_slow(&a, range);
+                    assert_eq!(a.rogs_range(range).collect::<Vec<_>>(), expected);
+                    let mut reversed = a.rogs_range(range).rev().collect::<Vec<_>>();
+                    reversed.reverse();
+                    assert_eq!(reversed, expected);
+
+                    // alternate between the front and the back
+                    let mut iter = a.rogs_range(range);
+                    let (mut front, mut back) = (Vec::new(), Vec::new());
+                    for i in 0.. {
+                        let rog = if (i + usize::from(start)) % 3 == 0 {
+                            iter.next_back().map(|rog| back.push(rog))
+                        } else {
+                            iter.next().map(|rog| front.push(rog))
+                        };
+                        if rog.is_none() {
+                            break;
+                        }
+                    }
+                    assert_eq!(iter.next(), None);
+                    assert_eq!(iter.next_back(), None);
+                    front.extend(back.into_iter().rev());
+                    assert_eq!(front, expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rogs_and_neighbors() {
+    let a = RangeSetBlaze::from_iter([0u8..=5, 10..=12, 20..=255]);
+    assert!(a.rogs().eq(rogs_range_slow(&a, ..)));
+    assert!(a.rogs().rev().eq(rogs_range_slow(&a, ..).into_iter().rev()));
+    for value in 0u8..=255 {
+        let (before, rog, after) = a.rogs_get_neighbors(value);
+        assert_eq!(rog, rogs_get_slow(&a, value));
+        assert_eq!(
+            before,
+            rog.start().checked_sub(1).map(|v| rogs_get_slow(&a, v))
+        );
+        assert_eq!(
+            after,
+            rog.end().checked_add(1).map(|v| rogs_get_slow(&a, v))
+        );
+    }
+    let total: usize = a.rogs().map(|rog| rog.len()).sum();
+    assert_eq!(total, 256);
+
+    let b = RangeSetBlaze::from_iter([i128::MIN..=0]);
+    assert_eq!(
+        b.rogs_get_neighbors(i128::MIN),
+        (
+            None,
+            Rog::Range(i128::MIN..=0),
+            Some(Rog::Gap(1..=i128::safe_max_value()))
+        )
+    );
+    assert_eq!(
+        Rog::Gap(1..=i128::safe_max_value()).len(),
+        u128::MAX / 2 - 1
+    );
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_extremes_u8() {
     for a in [
@@ -1708,7 +1841,7 @@ fn test_rog_extremes_u8() {
             for end in start..=255 {
                 println!("{start}..={end}");
                 assert_eq!(
-                    a._rogs_range_slow(start..=end),
+                    rogs_range_slow(&a, start..=end),
                     a.rogs_range(start..=end).collect::<Vec<_>>()
                 );
             }
@@ -1716,7 +1849,6 @@ fn test_rog_extremes_u8() {
     }
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_get_extremes_u8() {
     for a in [
@@ -1728,12 +1860,11 @@ fn test_rog_get_extremes_u8() {
     ] {
         for value in 0u8..=255 {
             println!("{value}");
-            assert_eq!(a.rogs_get_slow(value), a.rogs_get(value));
+            assert_eq!(rogs_get_slow(&a, value), a.rogs_get(value));
         }
     }
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_extremes_i128() {
     for a in [
@@ -1750,7 +1881,7 @@ fn test_rog_extremes_i128() {
                 }
                 println!("{start}..={end}");
                 assert_eq!(
-                    a._rogs_range_slow(start..=end),
+                    rogs_range_slow(&a, start..=end),
                     a.rogs_range(start..=end).collect::<Vec<_>>()
                 );
             }
@@ -1758,7 +1889,6 @@ fn test_rog_extremes_i128() {
     }
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_extremes_get_i128() {
     for a in [
@@ -1770,12 +1900,11 @@ fn test_rog_extremes_get_i128() {
     ] {
         for value in [i128::MIN, i128::MIN + 1, 0, i128::MAX - 2, i128::MAX - 1] {
             println!("{value}");
-            assert_eq!(a.rogs_get_slow(value), a.rogs_get(value));
+            assert_eq!(rogs_get_slow(&a, value), a.rogs_get(value));
         }
     }
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_should_fail_i128() {
     for a in [
@@ -1792,7 +1921,7 @@ fn test_rog_should_fail_i128() {
                 }
                 println!("{start}..={end}");
                 let slow =
-                    panic::catch_unwind(AssertUnwindSafe(|| a._rogs_range_slow(start..=end))).ok();
+                    panic::catch_unwind(AssertUnwindSafe(|| rogs_range_slow(&a, start..=end))).ok();
                 let fast = panic::catch_unwind(AssertUnwindSafe(|| {
                     a.rogs_range(start..=end).collect::<Vec<_>>()
                 }))
@@ -1803,7 +1932,6 @@ fn test_rog_should_fail_i128() {
     }
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_get_should_fail_i128() {
     for a in [
@@ -1815,14 +1943,13 @@ fn test_rog_get_should_fail_i128() {
     ] {
         for value in [i128::MIN, i128::MIN + 1, 0, i128::MAX - 1, i128::MAX] {
             println!("{value}");
-            let slow = panic::catch_unwind(AssertUnwindSafe(|| a.rogs_get_slow(value))).ok();
+            let slow = panic::catch_unwind(AssertUnwindSafe(|| rogs_get_slow(&a, value))).ok();
             let fast = panic::catch_unwind(AssertUnwindSafe(|| a.rogs_get(value))).ok();
             assert_eq!(slow, fast,);
         }
     }
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_get_doc() {
     use crate::RangeSetBlaze;
@@ -1831,7 +1958,6 @@ fn test_rog_get_doc() {
     assert_eq!(range_set_blaze.rogs_get(4), Rog::Gap(4..=2_147_483_647));
 }
 
-#[cfg(feature = "rog-experimental")]
 #[test]
 fn test_rog_range_doc() {
     use core::ops::Bound::Included;
@@ -1858,3 +1984,1091 @@ fn test_rog_range_doc() {
         vec![Rog::Gap(0..=255)]
     );
 }
+
+#[test]
+fn range_set_blaze_array_extremes() {
+    let mut a = RangeSetBlazeArray::<u8, 2>::new();
+    assert_eq!(a.ranges_insert(0..=255), Ok(true));
+    assert_eq!(a.len(), 256usize);
+    assert_eq!(a.remove(0), Ok(true));
+    assert_eq!(a.remove(255), Ok(true));
+    assert_eq!(a.remove(100), Ok(true));
+    assert_eq!(a.remove(200), Err(CapacityError));
+    assert_eq!(a.to_string(), "1..=99, 101..=254");
+    assert_eq!(a.insert(100), Ok(true));
+    assert_eq!(a.ranges().collect::<Vec<_>>(), vec![1..=254]);
+
+    let mut b = RangeSetBlazeArray::<i128, 1>::new();
+    let max = i128::safe_max_value();
+    assert_eq!(b.ranges_insert(i128::MIN..=max), Ok(true));
+    assert_eq!(b.len(), RangeSetBlaze::from_iter([i128::MIN..=max]).len());
+    assert!(b.contains(max) && b.contains(i128::MIN));
+}
+
+#[test]
+fn range_set_blaze_array_ops() {
+    let a = RangeSetBlazeArray::<i32, 4>::from_sorted_disjoint(CheckSortedDisjoint::from([
+        1..=2,
+        5..=100,
+    ]))
+    .unwrap();
+    let b = RangeSetBlazeArray::<i32, 4>::from_sorted_disjoint(
+        RangeSetBlaze::from_iter([2..=6]).ranges(),
+    )
+    .unwrap();
+    let a_rsb = RangeSetBlaze::from_sorted_disjoint(a.ranges());
+    let b_rsb = RangeSetBlaze::from_sorted_disjoint(b.ranges());
+
+    assert!((a.ranges() | b.ranges()).equal(a_rsb.ranges() | b_rsb.ranges()));
+    assert!((a.ranges() & b.ranges()).equal(a_rsb.ranges() & b_rsb.ranges()));
+    assert!((a.ranges() - b.ranges()).equal(a_rsb.ranges() - b_rsb.ranges()));
+    assert!((a.ranges() ^ b.ranges()).equal(a_rsb.ranges() ^ b_rsb.ranges()));
+    assert!((!a.ranges()).equal(!a_rsb.ranges()));
+    assert!(a.iter().eq(a_rsb.iter()));
+    assert!(a.iter().rev().eq(a_rsb.iter().rev()));
+
+    let c = RangeSetBlazeArray::<i32, 1>::from_sorted_disjoint(!a.ranges());
+    assert_eq!(c, Err(CapacityError));
+    let d = RangeSetBlazeArray::<i32, 3>::from_sorted_disjoint(!a.ranges()).unwrap();
+    assert_eq!(d.to_string(), "-2147483648..=0, 3..=4, 101..=2147483647");
+}
+
+#[test]
+fn persistent_versions() {
+    let v0 = PersistentRangeSetBlaze::from_iter((0..10_000).map(|i| i * 10..=i * 10 + 5));
+    let mut versions = vec![v0.clone()];
+    for i in 0..100 {
+        let mut next = versions.last().unwrap().clone();
+        assert!(PersistentRangeSetBlaze::ptr_eq(
+            versions.last().unwrap(),
+            &next
+        ));
+        next.remove(i * 1000);
+        next.ranges_insert(i * 1000 + 6..=i * 1000 + 9);
+        versions.push(next);
+    }
+    assert_eq!(v0, versions[0]);
+    assert_eq!(v0.len(), 60_000 as I32SafeLen);
+    assert!(versions[0].contains(5000) && !versions[100].contains(5000));
+
+    let (added, removed) = PersistentRangeSetBlaze::diff(&versions[0], &versions[100]);
+    let expected_added = RangeSetBlaze::from_iter((0..100).map(|i| i * 1000 + 6..=i * 1000 + 9));
+    let expected_removed = RangeSetBlaze::from_iter((0..100).map(|i| i * 1000));
+    assert!(added.equal(expected_added.ranges()));
+    assert!(removed.equal(expected_removed.ranges()));
+
+    let (added, removed) = PersistentRangeSetBlaze::diff(&versions[100], &versions[100].clone());
+    assert_eq!(added.count() + removed.count(), 0);
+}
+
+#[test]
+fn persistent_extremes() {
+    let mut a = PersistentRangeSetBlaze::new();
+    assert!(a.ranges_insert(0u8..=255));
+    assert_eq!(a.len(), 256usize);
+    let b = a.clone();
+    assert!(a.remove(0) && a.remove(255) && a.remove(100));
+    assert_eq!(a.to_string(), "1..=99, 101..=254");
+    assert_eq!(b.to_string(), "0..=255");
+    assert_eq!((!&a).to_string(), "0..=0, 100..=100, 255..=255");
+
+    let max = i128::safe_max_value();
+    let c = PersistentRangeSetBlaze::from_iter([i128::MIN..=max]);
+    assert_eq!(c.len(), RangeSetBlaze::from_iter([i128::MIN..=max]).len());
+    assert!((!c).is_empty());
+}
+
+#[test]
+fn ranges_insert_remove_diff() {
+    let mut set = RangeSetBlaze::from_iter([10..=20]);
+    let added = set.ranges_insert_diff(0..=100);
+    assert_eq!(added.collect::<Vec<_>>(), vec![0..=9, 21..=100]);
+
+    let mut set = RangeSetBlaze::from_iter([0u8..=5, 10..=15, 250..=255]);
+    assert!(set
+        .ranges_insert_diff(6..=9)
+        .equal(CheckSortedDisjoint::from([6..=9])));
+    assert!(set
+        .ranges_insert_diff(200..=255)
+        .equal(CheckSortedDisjoint::from([200..=249])));
+    assert_eq!(set.to_string(), "0..=15, 200..=255");
+    assert!(set
+        .ranges_remove_diff(0..=255)
+        .equal(CheckSortedDisjoint::from([0..=15, 200..=255])));
+    assert!(set.is_empty());
+    assert_eq!(set.len(), 0usize);
+
+    let mut set = RangeSetBlaze::from_iter([i128::MIN..=i128::safe_max_value()]);
+    let removed = set.ranges_remove_diff(0..=i128::safe_max_value());
+    assert!(removed.equal(CheckSortedDisjoint::from([0..=i128::safe_max_value()])));
+    assert_eq!(set.to_string(), format!("{}..=-1", i128::MIN));
+}
+
+#[test]
+fn allocate_extremes() {
+    let mut free = RangeSetBlaze::from_iter([u64::MIN..=u64::MAX]);
+    assert_eq!(free.find_free(1, u64::MAX), Some(u64::MAX..=u64::MAX));
+    assert_eq!(free.find_free(2, u64::MAX), None);
+    assert_eq!(
+        free.allocate_first_fit(1, u64::MAX),
+        Some(u64::MAX..=u64::MAX)
+    );
+    assert_eq!(free.allocate(u128::from(u64::MAX)), Some(0..=u64::MAX - 1));
+    assert!(free.is_empty());
+    assert_eq!(free.allocate_best_fit(1), None);
+    assert_eq!(free.allocate_worst_fit(1), None);
+
+    let mut free = RangeSetBlaze::from_iter([i8::MIN..=-100, -10..=10, 100..=i8::MAX]);
+    assert_eq!(free.allocate_best_fit(21), Some(-10..=10));
+    assert_eq!(free.allocate_worst_fit(3), Some(-128..=-126));
+    assert_eq!(free.allocate_first_fit(28, -50), Some(100..=127));
+    assert_eq!(free.to_string(), "-125..=-100");
+    assert_eq!(free.len(), 26usize);
+}
+
+#[test]
+#[should_panic(expected = "n must be > 0")]
+fn allocate_zero() {
+    let mut free = RangeSetBlaze::from_iter([0u8..=10]);
+    let _ = free.allocate(0usize);
+}
+
+#[test]
+fn nearest_extremes() {
+    let set = RangeSetBlaze::from_iter([
+        i128::MIN..=i128::MIN,
+        i128::safe_max_value()..=i128::safe_max_value(),
+    ]);
+    assert_eq!(set.nearest(-1), Some(i128::MIN));
+    assert_eq!(set.nearest(0), Some(i128::safe_max_value()));
+    assert_eq!(set.distance_to(-1), Some(u128::MAX / 2));
+    assert_eq!(set.distance_to(0), Some(u128::MAX / 2 - 1));
+    assert_eq!(
+        set.next_at_or_after(i128::MIN + 1),
+        Some(i128::safe_max_value())
+    );
+    assert_eq!(
+        set.prev_at_or_before(i128::safe_max_value() - 1),
+        Some(i128::MIN)
+    );
+    assert_eq!(
+        set.next_range_at_or_after(i128::safe_max_value()),
+        Some(i128::safe_max_value()..=i128::safe_max_value())
+    );
+
+    let set = RangeSetBlaze::from_iter([u8::MIN..=u8::MAX]);
+    assert_eq!(set.nearest(255), Some(255));
+    assert_eq!(set.distance_to(0), Some(0));
+    assert_eq!(set.nearest_range(100), Some(0..=255));
+
+    let empty = RangeSetBlaze::<u64>::new();
+    assert_eq!(empty.next_at_or_after(0), None);
+    assert_eq!(empty.prev_at_or_before(u64::MAX), None);
+    assert_eq!(empty.nearest_range(5), None);
+    assert_eq!(empty.distance_to(5), None);
+}
+
+#[test]
+fn morphology_extremes() {
+    let max = i128::safe_max_value();
+    let a = RangeSetBlaze::from_iter([i128::MIN..=i128::MIN, -5..=5, max..=max]);
+    assert_eq!(a.dilate(0), a);
+    assert_eq!(a.erode(0), a);
+    assert_eq!(a.close(0), a);
+    assert_eq!(a.open(0), a);
+    assert_eq!(a.open(1), a);
+    assert_eq!(
+        a.dilate(u128::MAX),
+        RangeSetBlaze::from_iter([i128::MIN..=max])
+    );
+    assert_eq!(
+        a.dilate(1).to_string(),
+        format!(
+            "{}..={}, -6..=6, {}..={}",
+            i128::MIN,
+            i128::MIN + 1,
+            max - 1,
+            max
+        )
+    );
+    assert_eq!(a.erode(5), RangeSetBlaze::from_iter([0..=0]));
+    assert!(a.erode(u128::MAX).is_empty());
+    assert_eq!(
+        a.close(u128::MAX / 2 - 7),
+        RangeSetBlaze::from_iter([i128::MIN..=i128::MIN, -5..=max])
+    );
+    assert_eq!(a.close(u128::MAX / 2 - 8), a);
+    assert_eq!(
+        a.close(u128::MAX),
+        RangeSetBlaze::from_iter([i128::MIN..=max])
+    );
+    assert_eq!(a.open(11), RangeSetBlaze::from_iter([-5..=5]));
+    assert!(a.open(u128::MAX).is_empty());
+
+    let all = RangeSetBlaze::from_iter([0u8..=255]);
+    assert_eq!(all.erode(127).to_string(), "127..=128");
+    assert!(all.erode(128).is_empty());
+    assert_eq!(all.open(256), all);
+    assert_eq!(all.dilate(usize::MAX), all);
+
+    // the lazy versions combine with the other set operations
+    let b = CheckSortedDisjoint::from([1u8..=2, 10..=20]);
+    let c = CheckSortedDisjoint::from([5u8..=5]);
+    assert_eq!((b.dilate(1) - c.open(1)).to_string(), "0..=3, 9..=21");
+}
+
+#[test]
+fn affine_extremes() {
+    let max = i128::safe_max_value();
+    let mut a = RangeSetBlaze::from_iter([i128::MIN..=-1, max..=max]);
+    assert!(!a.checked_shift(1));
+    assert!(!a.checked_shift(-1));
+    assert!(a.checked_shift(0));
+    a.saturating_shift(i128::MAX);
+    assert_eq!(a.to_string(), format!("-1..={max}"));
+    a.saturating_shift(i128::MIN);
+    assert_eq!(
+        a.to_string(),
+        format!("{}..={}", i128::MIN, max + i128::MIN)
+    );
+
+    let mut b = RangeSetBlaze::from_iter([0u8..=0, 2..=2, 255..=255]);
+    b.saturating_shift(254);
+    assert_eq!(b.to_string(), "254..=255");
+    let mut b = RangeSetBlaze::from_iter([0i8..=5, 7..=9]);
+    b.saturating_shift(-6);
+    assert_eq!(b.to_string(), "-6..=-1, 1..=3");
+    b.saturating_shift(-127);
+    assert_eq!(b.to_string(), "-128..=-128, -126..=-124");
+
+    let mut c = RangeSetBlaze::from_iter([u128::MAX - 10..=u128::safe_max_value()]);
+    c.wrapping_shift(20);
+    assert_eq!(c.to_string(), "9..=18");
+    // A panic leaves the set as it was.
+    let mut c = RangeSetBlaze::from_iter([0u128..=0]);
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| c.wrapping_shift(u128::MAX)));
+    assert!(result.is_err());
+    assert_eq!(c.to_string(), "0..=0");
+
+    let mut d = RangeSetBlaze::from_iter([-1i8..=0]);
+    d.scale(64);
+    assert_eq!(d.to_string(), "-64..=63");
+    let mut d = RangeSetBlaze::from_iter([-2i8..=-2]);
+    d.scale(64);
+    assert_eq!(d.to_string(), "-128..=-65");
+    let mut d = RangeSetBlaze::from_iter([0i8..=0, 2..=2]);
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| d.scale(64)));
+    assert!(result.is_err());
+    assert_eq!(d.to_string(), "0..=0, 2..=2");
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| d.scale(0)));
+    assert!(result.is_err());
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| d.quotient(-1)));
+    assert!(result.is_err());
+    assert_eq!(d.to_string(), "0..=0, 2..=2");
+    let mut d = RangeSetBlaze::from_iter([0u128..=1]);
+    d.scale(u128::safe_max_value() / 2);
+    assert_eq!(d.len(), u128::MAX - 1);
+    d.quotient(u128::safe_max_value() / 2);
+    assert_eq!(d.to_string(), "0..=1");
+
+    let e = CheckSortedDisjoint::from([i128::MIN..=i128::MIN + 5, max - 5..=max]);
+    assert_eq!(e.quotient(i128::MAX).to_string(), "-2..=0");
+}
+
+#[test]
+fn convert_extremes() {
+    let a = RangeSetBlaze::from_iter([i128::MIN..=-1, 5..=i128::safe_max_value()]);
+    let b = a.convert_clamped::<u128>();
+    assert_eq!(b.to_string(), format!("5..={}", i128::safe_max_value()));
+    assert_eq!(a.try_convert::<u128>(), Err(ConversionError));
+    assert_eq!(b.try_convert::<i128>().unwrap().to_string(), b.to_string());
+
+    let c = RangeSetBlaze::from_iter([0u128..=u128::safe_max_value()]);
+    assert_eq!(
+        c.convert_clamped::<i128>().to_string(),
+        format!("0..={}", i128::safe_max_value())
+    );
+    assert_eq!(c.convert_clamped::<u8>().to_string(), "0..=255");
+    assert_eq!(c.try_convert::<u64>(), Err(ConversionError));
+    assert!(RangeSetBlaze::<i8>::new()
+        .try_convert::<u8>()
+        .unwrap()
+        .is_empty());
+
+    let d = RangeSetBlaze::from_iter([i8::MIN..=i8::MAX]);
+    let e: RangeSetBlaze<i128> = d.into();
+    assert_eq!(e.to_string(), "-128..=127");
+    let f: RangeSetBlaze<u64> = RangeSetBlaze::from_iter([u32::MAX..=u32::MAX]).into();
+    assert_eq!(f.to_string(), "4294967295..=4294967295");
+    assert_eq!(
+        ConversionError.to_string(),
+        "integer out of range for the target type"
+    );
+}
+
+#[test]
+fn minkowski_extremes() {
+    let max = u128::safe_max_value();
+    let a = RangeSetBlaze::from_iter([0u128..=1, max - 1..=max]);
+    let b = RangeSetBlaze::from_iter([0u128..=0, max..=max]);
+    assert_eq!(
+        a.minkowski_sum(&b).to_string(),
+        format!("0..=1, {}..={max}", max - 1)
+    );
+    assert_eq!(a.minkowski_difference(&b).to_string(), "0..=0");
+    assert_eq!(
+        a.minkowski_difference(&RangeSetBlaze::new()).to_string(),
+        format!("0..={max}")
+    );
+    assert!(RangeSetBlaze::<u128>::new().minkowski_sum(&a).is_empty());
+
+    let c = RangeSetBlaze::from_iter([i128::MIN..=i128::MIN + 1, i128::MAX - 2..=i128::MAX - 1]);
+    let d = RangeSetBlaze::from_iter([-5i128..=-5, 5..=5]);
+    assert_eq!(
+        c.minkowski_sum(&d).to_string(),
+        format!(
+            "{}..={}, {}..={}, {}..={}, {}..={}",
+            i128::MIN,
+            i128::MIN,
+            i128::MIN + 5,
+            i128::MIN + 6,
+            i128::MAX - 7,
+            i128::MAX - 6,
+            i128::MAX - 1,
+            i128::MAX - 1
+        )
+    );
+    let e = RangeSetBlaze::from_iter([i128::MIN..=i128::MAX - 1]);
+    assert_eq!(
+        e.minkowski_difference(&d).to_string(),
+        format!("{}..={}", i128::MIN + 5, i128::MAX - 6)
+    );
+    assert!(c.minkowski_difference(&e).is_empty());
+}
+
+#[test]
+fn summary_extremes() {
+    let max = u128::safe_max_value();
+    let a = RangeSetBlaze::from_iter([0u128..=max]);
+    let summary = a.summary();
+    assert_eq!(summary.len, u128::MAX);
+    assert_eq!(summary.max_range_len, Some(u128::MAX));
+    assert_eq!(summary.gaps_len, 0);
+    assert_eq!(summary.max_gap_len, None);
+    assert_eq!(summary.density, Some(1.0));
+    assert_eq!(a.range_len_histogram().len(), 128);
+    assert_eq!(a.range_len_histogram()[127], 1);
+    assert!(a.gap_len_histogram().is_empty());
+
+    let b = RangeSetBlaze::from_iter([i128::MIN..=i128::MIN, i128::MAX - 1..=i128::MAX - 1]);
+    let summary = b.summary();
+    assert_eq!(summary.ranges_len, 2);
+    assert_eq!(summary.min_range_len, Some(1));
+    assert_eq!(summary.mean_range_len, Some(1.0));
+    assert_eq!(summary.max_gap_len, Some(u128::MAX - 2));
+    assert_eq!(b.range_len_histogram(), vec![2]);
+    let gap_histogram = b.gap_len_histogram();
+    assert_eq!(gap_histogram.len(), 128);
+    assert_eq!(gap_histogram.iter().sum::<usize>(), 1);
+
+    let empty = RangeSetBlaze::<i128>::new().summary();
+    assert_eq!(empty.ranges_len, 0);
+    assert_eq!(empty.len, 0);
+    assert_eq!(empty.min_range_len, None);
+    assert_eq!(empty.mean_range_len, None);
+    assert_eq!(empty.gaps_len, 0);
+}
+
+#[test]
+fn similarity_extremes() {
+    let max = u128::safe_max_value();
+    let all = RangeSetBlaze::from_iter([0u128..=max]);
+    let ends = RangeSetBlaze::from_iter([0u128..=0, max..=max]);
+    assert_eq!(all.intersection_len(&all), u128::MAX);
+    assert_eq!(all.union_len(&all), u128::MAX);
+    assert_eq!(all.symmetric_difference_len(&all), 0);
+    assert_eq!(all.union_len(&ends), u128::MAX);
+    assert_eq!(all.symmetric_difference_len(&ends), u128::MAX - 2);
+    assert_eq!(all.jaccard(&all), 1.0);
+    assert_eq!(ends.containment(&all), 1.0);
+    assert_eq!(all.overlap_coefficient(&ends), 1.0);
+
+    let empty = RangeSetBlaze::<i128>::new();
+    let some = RangeSetBlaze::from_iter([i128::MIN..=i128::MIN + 9]);
+    assert_eq!(empty.jaccard(&empty), 1.0);
+    assert_eq!(empty.jaccard(&some), 0.0);
+    assert_eq!(empty.overlap_coefficient(&some), 1.0);
+    assert_eq!(empty.containment(&some), 1.0);
+    assert_eq!(some.containment(&empty), 0.0);
+    assert_eq!(some.union_len(&empty), 10);
+    assert_eq!(
+        (some.ranges() | empty.ranges()).symmetric_difference_len(some.ranges()),
+        0
+    );
+}
+
+#[test]
+fn counting_extremes() {
+    let max = u128::safe_max_value();
+    let a = RangeSetBlaze::from_iter([0u128..=0, 10..=max]);
+    assert_eq!(a.ranges().elements_len(), u128::MAX - 9);
+    assert_eq!(a.ranges().len(), 2);
+    assert_eq!(
+        a.ranges().with_running_len().collect::<Vec<_>>(),
+        vec![(0..=0, 1), (10..=max, u128::MAX - 9)]
+    );
+    assert_eq!(a.ranges().take_len(u128::MAX).to_string(), a.to_string());
+    assert_eq!(a.ranges().take_len(2).to_string(), "0..=0, 10..=10");
+    assert!(a.ranges().take_len(0).is_empty());
+    assert!(a.ranges().skip_len(u128::MAX - 9).is_empty());
+    assert_eq!(
+        a.ranges().skip_len(u128::MAX - 10).to_string(),
+        format!("{max}..={max}")
+    );
+    assert_eq!(a.ranges().skip_len(0).to_string(), a.to_string());
+
+    let b = RangeSetBlaze::from_iter([i128::MIN..=i128::MAX - 1]);
+    assert_eq!(b.ranges().elements_len(), u128::MAX);
+    assert_eq!(
+        b.ranges().skip_len(1).take_len(2).to_string(),
+        format!("{}..={}", i128::MIN + 1, i128::MIN + 2)
+    );
+
+    // Lengths that don't fit in a signed type still give in-range results.
+    let c = RangeSetBlaze::from_iter([-128i8..=127]);
+    assert_eq!(c.ranges().take_len(200).to_string(), "-128..=71");
+    assert_eq!(c.ranges().skip_len(200).to_string(), "72..=127");
+}
+
+#[test]
+fn elements_extremes() {
+    let max = u128::safe_max_value();
+    let a = RangeSetBlaze::from_iter([0u128..=0, 10..=max]);
+    assert_eq!(a.iter().size_hint(), (usize::MAX, None));
+    let mut elements = a.ranges().elements();
+    assert_eq!(elements.nth(usize::MAX), Some(usize::MAX as u128 + 9));
+    assert_eq!(elements.next_back(), Some(max));
+    assert_eq!(elements.nth_back(0), Some(max - 1));
+    assert_eq!(elements.next(), Some(usize::MAX as u128 + 10));
+
+    let b = RangeSetBlaze::from_iter([i128::MIN..=i128::MIN + 2]);
+    let mut iter = b.iter();
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    assert_eq!(iter.nth(1), Some(i128::MIN + 1));
+    assert_eq!(iter.size_hint(), (1, Some(1)));
+    assert_eq!(iter.nth(1), None);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next_back(), None);
+
+    let c = RangeSetBlaze::from_iter([-128i8..=127]);
+    assert_eq!(c.iter().size_hint(), (256, Some(256)));
+    assert_eq!(c.iter().nth(200), Some(72));
+    assert_eq!(c.iter().nth_back(200), Some(-73));
+    assert_eq!(c.iter().nth(256), None);
+    let d = RangeSetBlaze::from_iter([1u8..=2, 10..=14]);
+    assert_eq!(
+        d.ranges().elements().rev().collect::<Vec<_>>(),
+        [14, 13, 12, 11, 10, 2, 1]
+    );
+    assert_eq!(d.ranges().elements().size_hint(), (2, None));
+}
+
+#[test]
+fn from_slice_extremes() {
+    // A consecutive run that wraps around must not become one range.
+    let wrapped: Vec<u8> = (0..64).map(|i: u8| 200u8.wrapping_add(i)).collect();
+    assert_eq!(
+        RangeSetBlaze::from_slice(&wrapped).to_string(),
+        "0..=7, 200..=255"
+    );
+    let wrapped: Vec<i64> = (0..8).map(|i| (i64::MAX - 3).wrapping_add(i)).collect();
+    assert_eq!(
+        RangeSetBlaze::from_slice(&wrapped),
+        RangeSetBlaze::from_iter(&wrapped)
+    );
+
+    // Chunks that end at the maximum, and lengths that aren't a multiple of the chunk.
+    for len in [0, 1, 63, 64, 65, 128, 200, 256] {
+        let v: Vec<u8> = (256 - len..256).map(|x: usize| x as u8).collect();
+        assert_eq!(RangeSetBlaze::from_slice(&v), RangeSetBlaze::from_iter(&v));
+        let v: Vec<i16> = (0..len as i16).map(|i| i16::MIN + i).collect();
+        assert_eq!(RangeSetBlaze::from_slice(&v), RangeSetBlaze::from_iter(&v));
+    }
+    let v: Vec<u64> = (u64::MAX - 15..=u64::MAX).chain(0..16).collect();
+    assert_eq!(
+        RangeSetBlaze::from_slice(&v).to_string(),
+        "0..=15, 18446744073709551600..=18446744073709551615"
+    );
+
+    // Adjacent consecutive chunks merge; a repeated chunk doesn't.
+    let v: Vec<u32> = (0..32).chain(0..16).chain(16..48).collect();
+    assert_eq!(RangeSetBlaze::from_slice(&v).to_string(), "0..=47");
+    let v: Vec<isize> = (-100..100).rev().collect();
+    assert_eq!(RangeSetBlaze::from_slice(&v).to_string(), "-100..=99");
+    let v: Vec<i128> = vec![i128::MAX - 1, i128::MIN, 0];
+    assert_eq!(RangeSetBlaze::from_slice(&v), RangeSetBlaze::from_iter(&v));
+}
+
+#[test]
+fn contains_many_extremes() {
+    let max = u128::safe_max_value();
+    let a = RangeSetBlaze::from_iter([0u128..=0, 10..=20, max - 1..=max]);
+    let probes = [max, 0, 1, 20, 21, max - 2, max - 1, 9];
+    let expected = [true, true, false, true, false, false, true, false];
+    assert_eq!(a.contains_many(probes), expected);
+    let mut sorted = probes;
+    sorted.sort_unstable();
+    assert_eq!(
+        a.contains_many(sorted),
+        sorted.map(|probe| a.contains(probe))
+    );
+    assert_eq!(
+        a.filter_members(probes).collect::<Vec<_>>(),
+        [max, 0, 20, max - 1]
+    );
+
+    let b = RangeSetBlaze::from_iter([i8::MIN..=-100, 100..=i8::MAX]);
+    assert_eq!(
+        b.contains_many([i8::MIN, -99, 0, 99, 100, i8::MAX]),
+        [true, false, false, false, true, true]
+    );
+    assert_eq!(
+        b.filter_members([i8::MAX, i8::MIN, i8::MAX])
+            .collect::<Vec<_>>(),
+        [i8::MAX, i8::MIN, i8::MAX]
+    );
+
+    // Few unsorted probes against many ranges use `contains`.
+    let c: RangeSetBlaze<u32> = (0..1000u32).map(|i| i * 10..=i * 10 + 4).collect();
+    assert_eq!(c.contains_many([9994, 3, 5]), [true, true, false]);
+
+    let empty = RangeSetBlaze::<i32>::new();
+    assert_eq!(empty.contains_many([3, 1, 2]), [false, false, false]);
+    assert!(empty.contains_many([] as [i32; 0]).is_empty());
+    assert_eq!(empty.filter_members([1, 2]).next(), None);
+}
+
+#[test]
+#[should_panic = "value must be <= T::safe_max_value()"]
+fn contains_many_panics_above_safe_max() {
+    let _ = RangeSetBlaze::from_iter([0u128..=10]).contains_many([u128::MAX]);
+}
+
+#[test]
+fn from_sorted_integers_extremes() {
+    let max = u128::safe_max_value();
+    let a = CheckSortedDisjoint::from_sorted_integers([0u128, 0, 1, max - 2, max - 1, max, max]);
+    assert_eq!(
+        a.into_range_set_blaze().to_string(),
+        format!("0..=1, {}..={max}", max - 2)
+    );
+
+    let b = CheckSortedDisjoint::from_sorted_integers([i8::MIN, i8::MIN + 1, 0, i8::MAX]);
+    assert_eq!(b.to_string(), "-128..=-127, 0..=0, 127..=127");
+    let c = CheckSortedDisjoint::from_sorted_integers(i8::MIN..=i8::MAX);
+    assert_eq!(c.to_string(), "-128..=127");
+    let d = CheckSortedDisjoint::from_sorted_integers(core::iter::empty::<u8>());
+    assert!(d.is_empty());
+    let e = !CheckSortedDisjoint::from_sorted_integers([5u8, 5, 5]);
+    assert_eq!(e.to_string(), "0..=4, 6..=255");
+
+    let mut f = CheckSortedDisjoint::from_sorted_integers([1u8, 2, 4]);
+    assert_eq!(f.size_hint(), (1, Some(3)));
+    assert_eq!(f.next(), Some(1..=2));
+    assert_eq!(f.size_hint(), (1, Some(1)));
+    assert_eq!(f.next(), Some(4..=4));
+    assert_eq!(f.next(), None);
+}
+
+#[test]
+#[should_panic = "integers must be sorted"]
+fn from_sorted_integers_panics_when_unsorted() {
+    let _ = CheckSortedDisjoint::from_sorted_integers([3u8, 4, 3]).count();
+}
+
+#[test]
+#[should_panic = "end must be less than or equal to safe_max_value"]
+fn from_sorted_integers_panics_above_safe_max() {
+    let _ = CheckSortedDisjoint::from_sorted_integers([0, u128::MAX]).count();
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi_header_is_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/range_set_blaze.h"));
+    let checked_in = include_str!("../include/range_set_blaze.h");
+    assert_eq!(
+        generated, checked_in,
+        "include/range_set_blaze.h is stale; copy it from $OUT_DIR/range_set_blaze.h"
+    );
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi_u64() {
+    use range_set_blaze::ffi::*;
+    use std::ptr;
+
+    unsafe {
+        let mut a = ptr::null_mut();
+        assert_eq!(rsb_u64_new(&mut a), RsbStatus::Ok);
+        let mut changed = false;
+        assert_eq!(rsb_u64_insert(a, 5, &mut changed), RsbStatus::Ok);
+        assert!(changed);
+        assert_eq!(rsb_u64_insert(a, 5, &mut changed), RsbStatus::Ok);
+        assert!(!changed);
+        assert_eq!(
+            rsb_u64_ranges_insert(a, 10, 20, ptr::null_mut()),
+            RsbStatus::Ok
+        );
+        assert_eq!(
+            rsb_u64_ranges_insert(a, 20, 10, ptr::null_mut()),
+            RsbStatus::InvalidRange
+        );
+        assert_eq!(
+            rsb_u64_ranges_insert(a, u64::MAX - 1, u64::MAX, ptr::null_mut()),
+            RsbStatus::Ok
+        );
+        assert_eq!(rsb_u64_remove(a, 15, &mut changed), RsbStatus::Ok);
+        assert!(changed);
+        assert_eq!(rsb_u64_ranges_remove(a, 0, 4, &mut changed), RsbStatus::Ok);
+        assert!(!changed);
+
+        let mut contained = false;
+        assert_eq!(rsb_u64_contains(a, 14, &mut contained), RsbStatus::Ok);
+        assert!(contained);
+        assert_eq!(rsb_u64_contains(a, 15, &mut contained), RsbStatus::Ok);
+        assert!(!contained);
+
+        let mut len = 0;
+        assert_eq!(rsb_u64_len(a, &mut len), RsbStatus::Ok);
+        assert_eq!(len, 13);
+
+        // Ask for the length first, then fill a buffer.
+        let mut ranges_len = 0;
+        assert_eq!(
+            rsb_u64_ranges(a, ptr::null_mut(), 0, &mut ranges_len),
+            RsbStatus::BufferTooSmall
+        );
+        assert_eq!(ranges_len, 4);
+        let mut buffer = vec![RsbRangeU64::default(); ranges_len];
+        assert_eq!(
+            rsb_u64_ranges(a, buffer.as_mut_ptr(), buffer.len(), &mut ranges_len),
+            RsbStatus::Ok
+        );
+        let ranges: Vec<_> = buffer.iter().map(|range| range.start..=range.end).collect();
+        assert_eq!(ranges, [5..=5, 10..=14, 16..=20, u64::MAX - 1..=u64::MAX]);
+
+        let mut b = ptr::null_mut();
+        assert_eq!(rsb_u64_complement(a, &mut b), RsbStatus::Ok);
+        let mut c = ptr::null_mut();
+        assert_eq!(rsb_u64_union(a, b, &mut c), RsbStatus::Ok);
+        assert_eq!(rsb_u64_len(c, &mut len), RsbStatus::Overflow);
+        let mut d = ptr::null_mut();
+        assert_eq!(rsb_u64_intersection(a, b, &mut d), RsbStatus::Ok);
+        assert_eq!(rsb_u64_len(d, &mut len), RsbStatus::Ok);
+        assert_eq!(len, 0);
+        let mut e = ptr::null_mut();
+        assert_eq!(rsb_u64_symmetric_difference(c, b, &mut e), RsbStatus::Ok);
+        let mut f = ptr::null_mut();
+        assert_eq!(rsb_u64_difference(c, b, &mut f), RsbStatus::Ok);
+        let mut equal = false;
+        assert_eq!(rsb_u64_equal(e, f, &mut equal), RsbStatus::Ok);
+        assert!(equal);
+        assert_eq!(rsb_u64_equal(a, f, &mut equal), RsbStatus::Ok);
+        assert!(equal);
+
+        // Round trip through serialization.
+        let mut serialized_len = 0;
+        assert_eq!(
+            rsb_u64_serialize(a, ptr::null_mut(), 0, &mut serialized_len),
+            RsbStatus::BufferTooSmall
+        );
+        assert_eq!(serialized_len, 13 + 4 * 16);
+        let mut bytes = vec![0u8; serialized_len];
+        assert_eq!(
+            rsb_u64_serialize(a, bytes.as_mut_ptr(), bytes.len(), &mut serialized_len),
+            RsbStatus::Ok
+        );
+        let mut g = ptr::null_mut();
+        assert_eq!(
+            rsb_u64_deserialize(bytes.as_ptr(), bytes.len(), &mut g),
+            RsbStatus::Ok
+        );
+        assert_eq!(rsb_u64_equal(a, g, &mut equal), RsbStatus::Ok);
+        assert!(equal);
+        let mut h = ptr::null_mut();
+        assert_eq!(rsb_u64_clone(g, &mut h), RsbStatus::Ok);
+        assert_eq!(rsb_u64_equal(a, h, &mut equal), RsbStatus::Ok);
+        assert!(equal);
+
+        for set in [a, b, c, d, e, f, g, h] {
+            rsb_u64_free(set);
+        }
+        rsb_u64_free(ptr::null_mut());
+    }
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi_i64_and_errors() {
+    use range_set_blaze::ffi::*;
+    use std::ptr;
+
+    unsafe {
+        let mut a = ptr::null_mut();
+        assert_eq!(rsb_i64_new(&mut a), RsbStatus::Ok);
+        assert_eq!(
+            rsb_i64_ranges_insert(a, i64::MIN, -1, ptr::null_mut()),
+            RsbStatus::Ok
+        );
+        assert_eq!(rsb_i64_insert(a, 3, ptr::null_mut()), RsbStatus::Ok);
+        let mut contained = false;
+        assert_eq!(rsb_i64_contains(a, i64::MIN, &mut contained), RsbStatus::Ok);
+        assert!(contained);
+        let mut ranges_len = 0;
+        assert_eq!(rsb_i64_ranges_len(a, &mut ranges_len), RsbStatus::Ok);
+        assert_eq!(ranges_len, 2);
+        let mut buffer = [RsbRangeI64::default(); 1];
+        assert_eq!(
+            rsb_i64_ranges(a, buffer.as_mut_ptr(), buffer.len(), &mut ranges_len),
+            RsbStatus::BufferTooSmall
+        );
+        assert_eq!(buffer[0], RsbRangeI64::default());
+
+        // Null pointers are reported, not dereferenced.
+        assert_eq!(rsb_i64_new(ptr::null_mut()), RsbStatus::NullPointer);
+        assert_eq!(
+            rsb_i64_contains(ptr::null(), 0, &mut contained),
+            RsbStatus::NullPointer
+        );
+        assert_eq!(
+            rsb_i64_contains(a, 0, ptr::null_mut()),
+            RsbStatus::NullPointer
+        );
+        let mut out = ptr::null_mut();
+        assert_eq!(
+            rsb_i64_union(a, ptr::null(), &mut out),
+            RsbStatus::NullPointer
+        );
+        assert!(out.is_null());
+
+        // Malformed data is rejected.
+        let mut serialized_len = 0;
+        rsb_i64_serialize(a, ptr::null_mut(), 0, &mut serialized_len);
+        let mut bytes = vec![0u8; serialized_len];
+        assert_eq!(
+            rsb_i64_serialize(a, bytes.as_mut_ptr(), bytes.len(), &mut serialized_len),
+            RsbStatus::Ok
+        );
+        let mut u = ptr::null_mut();
+        assert_eq!(
+            rsb_u64_deserialize(bytes.as_ptr(), bytes.len(), &mut u),
+            RsbStatus::InvalidData
+        );
+        assert_eq!(
+            rsb_i64_deserialize(bytes.as_ptr(), bytes.len() - 1, &mut out),
+            RsbStatus::InvalidData
+        );
+        // Make the second range overlap the first.
+        let second_start = bytes.len() - 16;
+        bytes[second_start..second_start + 8].copy_from_slice(&(-1i64).to_le_bytes());
+        assert_eq!(
+            rsb_i64_deserialize(bytes.as_ptr(), bytes.len(), &mut out),
+            RsbStatus::InvalidData
+        );
+        // Make it touch the first, which also isn't disjoint.
+        bytes[second_start..second_start + 8].copy_from_slice(&0i64.to_le_bytes());
+        assert_eq!(
+            rsb_i64_deserialize(bytes.as_ptr(), bytes.len(), &mut out),
+            RsbStatus::InvalidData
+        );
+        bytes[second_start..second_start + 8].copy_from_slice(&1i64.to_le_bytes());
+        assert_eq!(
+            rsb_i64_deserialize(bytes.as_ptr(), bytes.len(), &mut out),
+            RsbStatus::Ok
+        );
+        rsb_i64_free(out);
+        rsb_i64_free(a);
+    }
+}
+
+#[test]
+fn cursor_extremes() {
+    let set = RangeSetBlaze::from_iter([0u8..=255]);
+    let cursor = set.cursor_at(200);
+    assert_eq!(cursor.range(), Some(0..=255));
+    assert_eq!(cursor.gap_before(), None);
+    assert_eq!(cursor.gap_after(), None);
+
+    let set = RangeSetBlaze::from_iter([1u8..=1, 254..=254]);
+    let mut cursor = set.cursor_front();
+    assert_eq!(cursor.gap_before(), Some(0..=0));
+    assert_eq!(cursor.gap_after(), Some(2..=253));
+    assert_eq!(cursor.peek_prev(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.range(), None);
+    assert_eq!(cursor.gap_before(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.range(), Some(254..=254));
+    assert_eq!(cursor.gap_after(), Some(255..=255));
+    cursor.move_next();
+    assert_eq!(cursor.range(), None);
+    cursor.move_next();
+    assert_eq!(cursor.range(), Some(1..=1));
+
+    let empty = RangeSetBlaze::<i32>::new();
+    assert_eq!(empty.cursor_front().range(), None);
+    assert_eq!(empty.cursor_back().peek_next(), None);
+
+    let max = <i128 as Integer>::safe_max_value();
+    let mut set = RangeSetBlaze::from_iter([i128::MIN..=i128::MIN, 0..=0, max..=max]);
+    let mut cursor = set.cursor_at_mut(i128::MIN);
+    assert_eq!(cursor.gap_before(), None);
+    cursor.extend(i128::MIN..=-1);
+    assert_eq!(cursor.range(), Some(i128::MIN..=0));
+    cursor.split(-5..=-5);
+    assert_eq!(cursor.range(), Some(i128::MIN..=-6));
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.gap_after(), None);
+    cursor.shrink(max..=max);
+    assert_eq!(cursor.remove_current(), Some(max..=max));
+    assert_eq!(cursor.remove_current(), None);
+    assert_eq!(set.to_string(), format!("{}..=-6, -4..=0", i128::MIN));
+    assert_eq!(set.len(), 1u128 << 127);
+}
+
+#[test]
+#[should_panic = "range must be a non-empty part of the current range"]
+fn cursor_shrink_panics_outside_current() {
+    let mut set = RangeSetBlaze::from_iter([10..=20]);
+    set.cursor_front_mut().shrink(5..=15);
+}
+
+#[test]
+#[should_panic = "cursor must point at a range"]
+fn cursor_split_panics_at_ghost() {
+    let mut set = RangeSetBlaze::from_iter([10..=20]);
+    set.cursor_at_mut(30).split(12..=13);
+}
+
+#[test]
+fn drain_extremes() {
+    use core::ops::Bound::{Excluded, Included, Unbounded};
+
+    let max = <i128 as Integer>::safe_max_value();
+    let mut set = RangeSetBlaze::from_iter([i128::MIN..=-10, 10..=max]);
+    let removed = RangeSetBlaze::from_sorted_disjoint(set.drain_range(..));
+    assert_eq!(
+        removed,
+        RangeSetBlaze::from_iter([i128::MIN..=-10, 10..=max])
+    );
+    assert!(set.is_empty());
+
+    let mut set = RangeSetBlaze::from_iter([0u8..=255]);
+    let removed = set.drain_range((Excluded(0), Excluded(255)));
+    assert_eq!(removed.to_string(), "1..=254");
+    assert_eq!(set.to_string(), "0..=0, 255..=255");
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.drain_range((Included(100), Included(100))).len(), 0);
+
+    // Ranges left empty by an excluded bound remove nothing, even at the edges of the domain.
+    let mut set = RangeSetBlaze::from_iter([0u32..=10, u32::MAX - 1..=u32::MAX]);
+    assert_eq!(set.drain_range(..0).len(), 0);
+    assert_eq!(set.drain_range(5..5).len(), 0);
+    assert_eq!(set.drain_range((Excluded(u32::MAX), Unbounded)).len(), 0);
+    assert_eq!(
+        set.to_string(),
+        format!("0..=10, {}..={}", u32::MAX - 1, u32::MAX)
+    );
+    let removed = set.drain_range((Excluded(u32::MAX - 1), Unbounded));
+    assert_eq!(removed.to_string(), format!("{0}..={0}", u32::MAX));
+    assert_eq!(set.len(), 12);
+
+    let mut set = RangeSetBlaze::from_iter([0u8..=0, 5..=6, 255..=255]);
+    let mut extract = set.extract_if_ranges(|range| range.start() != range.end());
+    assert_eq!(extract.next(), Some(5..=6));
+    assert_eq!(extract.next(), None);
+    assert_eq!(extract.next(), None);
+    assert_eq!(set.to_string(), "0..=0, 255..=255");
+    assert_eq!(set.len(), 2);
+
+    // Dropping the iterator early keeps the ranges it hasn't reached.
+    let mut set = RangeSetBlaze::from_iter([1..=1, 3..=3, 5..=5]);
+    assert_eq!(set.extract_if_ranges(|_| true).next(), Some(1..=1));
+    assert_eq!(set.to_string(), "3..=3, 5..=5");
+    let extracted: Vec<_> = set.extract_if_ranges(|_| true).collect();
+    assert_eq!(extracted, [3..=3, 5..=5]);
+    assert!(set.is_empty());
+
+    let mut set = RangeSetBlaze::from_iter([i64::MAX - 1..=i64::MAX]);
+    let all = set.drain();
+    assert_eq!(
+        (!all).to_string(),
+        format!("{}..={}", i64::MIN, i64::MAX - 2)
+    );
+    assert!(set.is_empty());
+}
+
+#[test]
+#[should_panic = "start must be <= end"]
+fn drain_range_panics_when_reversed() {
+    let mut set = RangeSetBlaze::from_iter([1..=10]);
+    #[allow(clippy::reversed_empty_ranges)]
+    let _ = set.drain_range(5..=4);
+}
+
+#[test]
+fn range_granular_retain_extremes() {
+    // Neither call expands the set into integers.
+    let mut set = RangeSetBlaze::from_iter([0..=u64::MAX / 2, u64::MAX - 1..=u64::MAX]);
+    set.retain_ranges(|range| *range.start() == 0);
+    assert_eq!(set.to_string(), format!("0..={}", u64::MAX / 2));
+    set.map_ranges_in_place(|range| Some(*range.start()..=u64::MAX));
+    assert_eq!(set.len(), 1u128 << 64);
+    set.map_ranges_in_place(|_| None);
+    assert!(set.is_empty());
+    assert_eq!(set.len(), 0u128);
+
+    let max = <i128 as Integer>::safe_max_value();
+    let mut set = RangeSetBlaze::from_iter([i128::MIN..=-3, 3..=max]);
+    // Move the second range's start down so that it touches the first.
+    set.map_ranges_in_place(|range| Some((*range.start()).min(-2)..=*range.end()));
+    assert_eq!(set.to_string(), format!("{}..={max}", i128::MIN));
+    // An empty range removes the range.
+    #[allow(clippy::reversed_empty_ranges)]
+    set.map_ranges_in_place(|_| Some(1..=0));
+    assert!(set.is_empty());
+}
+
+#[test]
+#[should_panic = "end must be <= T::safe_max_value()"]
+fn map_ranges_in_place_panics_above_safe_max() {
+    let mut set = RangeSetBlaze::from_iter([0..=10]);
+    set.map_ranges_in_place(|_| Some(0..=u128::MAX));
+}
+
+#[test]
+fn range_count_map_extremes() {
+    let max = <i128 as Integer>::safe_max_value();
+    let mut map = RangeCountMap::from_iter([i128::MIN..=max, 0..=max]);
+    assert_eq!(map.count(i128::MIN), 1);
+    assert_eq!(map.count(max), 2);
+    assert_eq!(
+        map.ranges_with_count_at_least(2).to_string(),
+        format!("0..={max}")
+    );
+    map.remove_range(0..=max, 1);
+    assert_eq!(map.segments_len(), 1);
+    assert_eq!(map.support().to_string(), format!("{}..={max}", i128::MIN));
+    map.remove_range(i128::MIN..=max, 1);
+    assert!(map.is_empty());
+
+    let mut map = RangeCountMap::new();
+    map.add_range(0..=u64::MAX, usize::MAX);
+    assert_eq!(map.count(u64::MAX), usize::MAX);
+    assert_eq!(map.ranges_with_count_at_least(usize::MAX).count(), 1);
+}
+
+#[test]
+#[should_panic = "count must be >= k for every integer in the range"]
+fn range_count_map_remove_panics_below_zero() {
+    let mut map = RangeCountMap::from_iter([0u8..=10, 5..=20]);
+    map.remove_range(0..=20, 2);
+}
+
+#[test]
+fn range_count_map_is_unchanged_after_panic() {
+    let mut map = RangeCountMap::from_iter([0u8..=10, 5..=20]);
+    let before = map.clone();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| map.add_range(0..=30, usize::MAX)));
+    assert!(result.is_err());
+    assert_eq!(map, before);
+}
+
+#[test]
+fn range_set_blaze_2d_extremes() {
+    let x_max = <i128 as Integer>::safe_max_value();
+    let point = RangeSetBlaze2D::from_iter([(0..=0, u8::MAX..=u8::MAX)]);
+    let not_point = !&point;
+    assert_eq!(
+        not_point.to_string(),
+        format!(
+            "{}..=-1: 0..=255; 0..=0: 0..=254; 1..={x_max}: 0..=255",
+            i128::MIN
+        )
+    );
+    assert!(not_point.contains((i128::MIN, 0)));
+    assert!(not_point.contains((x_max, u8::MAX)));
+    assert!(!not_point.contains((0, u8::MAX)));
+    let rectangles: Vec<_> = not_point.rectangles().collect();
+    assert_eq!(
+        rectangles,
+        [
+            (i128::MIN..=-1, 0..=255),
+            (0..=0, 0..=254),
+            (1..=x_max, 0..=255)
+        ]
+    );
+    assert_eq!(!not_point, point);
+
+    // Rectangles that reach the end of the X domain.
+    let edge =
+        RangeSetBlaze2D::from_iter([(250u8..=255, 0u8..=1), (255..=255, 2..=3), (0..=255, 5..=5)]);
+    assert_eq!(
+        edge.to_string(),
+        "0..=249: 5..=5; 250..=254: 0..=1, 5..=5; 255..=255: 0..=3, 5..=5"
+    );
+    assert_eq!(edge.area(), Some(256 + 6 * 2 + 2));
+    let mut inserted = RangeSetBlaze2D::new();
+    inserted.insert_rectangle(0..=255, 5..=5);
+    inserted.insert_rectangle(255..=255, 2..=3);
+    inserted.insert_rectangle(0..=0, 5..=5);
+    inserted.insert_rectangle(250..=255, 0..=1);
+    assert_eq!(inserted, edge);
+
+    let full = !RangeSetBlaze2D::<u32, u64>::new();
+    assert_eq!(full.area(), Some((1u128 << 32) * (1u128 << 64)));
+    assert!((&full - &full).is_empty());
+}
+
+#[test]
+fn range_set_blaze_2d_area_overflow() {
+    let full = !RangeSetBlaze2D::<u64, u64>::new();
+    assert_eq!(full.area(), None);
+    let almost_full = &full - &RangeSetBlaze2D::from_iter([(0..=u64::MAX, 1..=u64::MAX)]);
+    assert_eq!(almost_full.area(), Some(1u128 << 64));
+    let full = !RangeSetBlaze2D::<u128, u8>::new();
+    assert_eq!(full.area(), None);
+}
+
+#[test]
+fn range_allocator_extremes() {
+    // The indexes stay in step while the whole domain is allocated and released.
+    let mut free = RangeAllocator::from(RangeSetBlaze::from_iter([u64::MIN..=u64::MAX]));
+    assert_eq!(free.allocate(u128::from(u64::MAX)), Some(0..=u64::MAX - 1));
+    assert_eq!(free.allocate_best_fit(1), Some(u64::MAX..=u64::MAX));
+    assert!(free.free_set().is_empty());
+    assert_eq!(free.allocate_worst_fit(1), None);
+    free.release(u64::MAX..=u64::MAX);
+    free.release(0..=u64::MAX - 1);
+    assert_eq!(free.find_worst_fit(1u128 << 64), Some(0..=u64::MAX));
+
+    let max = <i128 as Integer>::safe_max_value();
+    let mut free = RangeAllocator::from(RangeSetBlaze::from_iter([i128::MIN..=-100, 100..=max]));
+    assert_eq!(free.allocate_worst_fit(3), Some(i128::MIN..=i128::MIN + 2));
+    assert_eq!(free.allocate_first_fit(2, max - 1), Some(max - 1..=max));
+    free.release(-99..=99);
+    assert_eq!(
+        free.into_free_set().to_string(),
+        format!("{}..={}", i128::MIN + 3, max - 2)
+    );
+}
+
+#[test]
+#[should_panic(expected = "n must be > 0")]
+fn range_allocator_allocate_zero() {
+    let mut free = RangeAllocator::from(RangeSetBlaze::from_iter([0u8..=10]));
+    let _ = free.allocate(0usize);
+}
@@ -4,9 +4,12 @@ use alloc::{string::ToString, vec::Vec};
 use range_set_blaze::RangeSetBlaze;
 use wasm_bindgen::prelude::*;
 
+// The JavaScript classes come from the bindings package.
+pub use range_set_blaze_wasm::{RangeSetI32, RangeSetSafeInteger, RangeSetU32, RangeSetU64};
+
 #[wasm_bindgen]
 pub fn disjoint_intervals(input: Vec<i32>) -> JsValue {
     let set: RangeSetBlaze<_> = input.into_iter().collect();
     let s = set.to_string();
     JsValue::from_str(&s)
-}
\ No newline at end of file
+}
@@ -1246,7 +1246,6 @@ fn complement_sample() {
     println!("{},{},{}", c.len(), c.ranges_len(), c);
 }
 
-#[cfg(feature = "from_slice")]
 #[wasm_bindgen_test]
 fn from_slice_test() {
     let range_set_blaze = RangeSetBlaze::from_iter([100, 103, 101, 102, -3, -4]);